@@ -0,0 +1,91 @@
+//! Defines request and response types for a CreateCapacityProvider action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::capacity_provider::{self, AutoScalingGroupProvider, CapacityProvider};
+use types::tag::{self, Tag};
+use error::ValidationError;
+use custom_ser;
+
+/// A CreateCapacityProvider request type which can be serialized to json and set as the body of
+/// an HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::create_capacity_provider() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct CreateCapacityProviderRequest {
+    /// The name of the capacity provider.
+    name: String,
+    /// The Auto Scaling group backing the capacity provider.
+    autoScalingGroupProvider: AutoScalingGroupProvider,
+    /// The tags to apply to the capacity provider.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    tags: Vec<Tag>,
+}
+
+/// A CreateCapacityProvider response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::create_capacity_provider().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct CreateCapacityProviderResponse {
+    /// The capacity provider which was created.
+    capacityProvider: CapacityProvider,
+}
+
+/// Used so that a CreateCapacityProviderRequest can be passed as a generic ECSRequest.
+impl ECSRequest for CreateCapacityProviderRequest {}
+
+/// Used so that a CreateCapacityProviderResponse can be returned as a generic ECSResponse.
+impl ECSResponse for CreateCapacityProviderResponse {}
+
+/// Implements some convenience methods for building a CreateCapacityProviderRequest.
+impl CreateCapacityProviderRequest {
+    /// Creates a CreateCapacityProviderRequest, or returns a ValidationError if the name is one
+    /// ECS would reject.
+    pub fn new(name: String, provider: AutoScalingGroupProvider) -> Result<Self, ValidationError> {
+        try!(capacity_provider::validate_name(&name));
+        Ok(CreateCapacityProviderRequest {
+            name: name,
+            autoScalingGroupProvider: provider,
+            tags: Vec::new(),
+        })
+    }
+
+    /// Sets the tags to apply to the capacity provider, or returns a ValidationError if there are
+    /// more tags than a resource can hold.
+    pub fn set_tags(&mut self, tags: Vec<Tag>) -> Result<(), ValidationError> {
+        try!(tag::validate_tags(&tags));
+        self.tags = tags;
+        Ok(())
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// CreateCapacityProviderResponse.
+impl CreateCapacityProviderResponse {
+    /// Gets a reference to the capacity provider which was created.
+    pub fn get_capacity_provider(&self) -> &CapacityProvider {
+        &self.capacityProvider
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CreateCapacityProviderRequest;
+    use types::capacity_provider::{AutoScalingGroupProvider, ManagedScaling, ManagedTerminationProtection};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let mut provider = AutoScalingGroupProvider::new(String::from("arn:asg"));
+        provider.set_managed_scaling(ManagedScaling::enabled(100).unwrap());
+        provider.set_managed_termination_protection(ManagedTerminationProtection::Disabled);
+        let req = CreateCapacityProviderRequest::new(String::from("my-provider"), provider).unwrap();
+        let expected: value::Value = serde_json::from_str(
+            "{\"name\":\"my-provider\",\"autoScalingGroupProvider\":{\"autoScalingGroupArn\":\"arn:asg\",\
+              \"managedScaling\":{\"status\":\"ENABLED\",\"targetCapacity\":100},\
+              \"managedTerminationProtection\":\"DISABLED\"}}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+}
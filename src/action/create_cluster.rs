@@ -0,0 +1,140 @@
+//! Defines request and response types for a CreateCluster action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::capacity_provider::{self, CapacityProviderStrategyItem};
+use types::cluster::{Cluster, ClusterSetting, ClusterConfiguration};
+use types::tag::{self, Tag};
+use error::ValidationError;
+use custom_ser;
+
+/// A CreateCluster request type which can be serialized to json and set as the body of an HTTP
+/// request.  Construct one of these and pass it to your client in the
+/// ecs_client::create_cluster() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct CreateClusterRequest {
+    /// The name of the cluster.  Left unset, a cluster named default is created.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    clusterName: Option<String>,
+    /// The tags to apply to the cluster.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    tags: Vec<Tag>,
+    /// The settings of the cluster.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    settings: Vec<ClusterSetting>,
+    /// The configuration of the cluster.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    configuration: Option<ClusterConfiguration>,
+    /// The names of the capacity providers to associate with the cluster.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    capacityProviders: Vec<String>,
+    /// The capacity provider strategy used when no other strategy or launch type is specified.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    defaultCapacityProviderStrategy: Vec<CapacityProviderStrategyItem>,
+}
+
+/// A CreateCluster response type which can be deserialized from the body of an HTTP response.  If
+/// the request was successful, one of these will be returned from ecs_client::create_cluster().
+#[derive(Deserialize, Debug)]
+pub struct CreateClusterResponse {
+    /// The cluster which was created.
+    cluster: Cluster,
+}
+
+/// Used so that a CreateClusterRequest can be passed as a generic ECSRequest.
+impl ECSRequest for CreateClusterRequest {}
+
+/// Used so that a CreateClusterResponse can be returned as a generic ECSResponse.
+impl ECSResponse for CreateClusterResponse {}
+
+/// Implements some convenience methods for building a CreateClusterRequest.
+impl CreateClusterRequest {
+    /// Creates a CreateClusterRequest for the default cluster.
+    pub fn new() -> Self {
+        CreateClusterRequest {
+            clusterName: None,
+            tags: Vec::new(),
+            settings: Vec::new(),
+            configuration: None,
+            capacityProviders: Vec::new(),
+            defaultCapacityProviderStrategy: Vec::new(),
+        }
+    }
+
+    /// Creates a CreateClusterRequest for a cluster with the given name.
+    pub fn with_name(cluster_name: String) -> Self {
+        let mut request = CreateClusterRequest::new();
+        request.clusterName = Some(cluster_name);
+        request
+    }
+
+    /// Sets the tags to apply to the cluster, or returns a ValidationError if there are more tags
+    /// than a resource can hold.
+    pub fn set_tags(&mut self, tags: Vec<Tag>) -> Result<(), ValidationError> {
+        try!(tag::validate_tags(&tags));
+        self.tags = tags;
+        Ok(())
+    }
+
+    /// Adds a setting of the cluster.
+    pub fn add_setting(&mut self, setting: ClusterSetting) {
+        self.settings.push(setting);
+    }
+
+    /// Sets the configuration of the cluster.
+    pub fn set_configuration(&mut self, configuration: ClusterConfiguration) {
+        self.configuration = Some(configuration);
+    }
+
+    /// Adds the name of a capacity provider to associate with the cluster.
+    pub fn add_capacity_provider(&mut self, capacity_provider: String) {
+        self.capacityProviders.push(capacity_provider);
+    }
+
+    /// Sets the default capacity provider strategy of the cluster.
+    pub fn set_default_capacity_provider_strategy(&mut self,
+                                                  strategy: Vec<CapacityProviderStrategyItem>)
+                                                  -> Result<(), ValidationError> {
+        try!(capacity_provider::validate_strategy(&strategy));
+        self.defaultCapacityProviderStrategy = strategy;
+        Ok(())
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a CreateClusterResponse.
+impl CreateClusterResponse {
+    /// Gets a reference to the cluster which was created.
+    pub fn get_cluster(&self) -> &Cluster {
+        &self.cluster
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CreateClusterRequest;
+    use types::cluster::ClusterSetting;
+    use types::tag::Tag;
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let mut req = CreateClusterRequest::with_name(String::from("prod"));
+        req.set_tags(vec![Tag::new(String::from("team"), String::from("platform")).unwrap()]).unwrap();
+        req.add_setting(ClusterSetting::container_insights(true));
+        req.add_capacity_provider(String::from("FARGATE"));
+        let expected: value::Value = serde_json::from_str(
+            "{\"clusterName\":\"prod\",\"tags\":[{\"key\":\"team\",\"value\":\"platform\"}],\
+              \"settings\":[{\"name\":\"containerInsights\",\"value\":\"enabled\"}],\
+              \"capacityProviders\":[\"FARGATE\"]}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_too_many_tags() {
+        let mut req = CreateClusterRequest::new();
+        let tags = (0..51).map(|i| Tag::new(format!("key{}", i), String::new()).unwrap()).collect();
+        assert!(req.set_tags(tags).is_err());
+    }
+}
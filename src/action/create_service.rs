@@ -0,0 +1,181 @@
+//! Defines request and response types for a CreateService action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::capacity_provider::{self, CapacityProviderStrategyItem};
+use types::launch_type::LaunchType;
+use types::networking::{NetworkConfiguration, LoadBalancer};
+use types::service::Service;
+use types::tag::{self, PropagateTags, Tag};
+use error::ValidationError;
+use custom_ser;
+
+/// A CreateService request type which can be serialized to json and set as the body of an HTTP
+/// request.  Construct one of these and pass it to your client in the
+/// ecs_client::create_service() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct CreateServiceRequest {
+    /// The short name or full ARN of the cluster to run the service on.
+    cluster: String,
+    /// The name of the service, unique within the cluster.
+    serviceName: String,
+    /// The family and revision or full ARN of the task definition the service runs.
+    taskDefinition: String,
+    /// The number of tasks the service should keep running.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    desiredCount: Option<i32>,
+    /// The launch type the tasks run with.  Cannot be combined with a capacity provider strategy.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    launchType: Option<LaunchType>,
+    /// The capacity provider strategy to use.  Cannot be combined with a launch type.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    capacityProviderStrategy: Vec<CapacityProviderStrategyItem>,
+    /// The Fargate platform version the tasks run on.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    platformVersion: Option<String>,
+    /// The network configuration of the tasks of the service.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    networkConfiguration: Option<NetworkConfiguration>,
+    /// The load balancers to register the tasks of the service with.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    loadBalancers: Vec<LoadBalancer>,
+    /// A unique, case-sensitive identifier ensuring the request is idempotent.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    clientToken: Option<String>,
+    /// The tags to apply to the service.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    tags: Vec<Tag>,
+    /// Where the tags of the tasks of the service are copied from.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    propagateTags: Option<PropagateTags>,
+    /// Whether ECS tags the tasks of the service with the cluster and service names.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    enableECSManagedTags: Option<bool>,
+}
+
+/// A CreateService response type which can be deserialized from the body of an HTTP response.  If
+/// the request was successful, one of these will be returned from ecs_client::create_service().
+#[derive(Deserialize, Debug)]
+pub struct CreateServiceResponse {
+    /// The service which was created.
+    service: Service,
+}
+
+/// Used so that a CreateServiceRequest can be passed as a generic ECSRequest.
+impl ECSRequest for CreateServiceRequest {}
+
+/// Used so that a CreateServiceResponse can be returned as a generic ECSResponse.
+impl ECSResponse for CreateServiceResponse {}
+
+/// Implements some convenience methods for building a CreateServiceRequest.
+impl CreateServiceRequest {
+    /// Creates a CreateServiceRequest running the given task definition in a cluster.
+    pub fn new(cluster: String, service_name: String, task_definition: String) -> Self {
+        CreateServiceRequest {
+            cluster: cluster,
+            serviceName: service_name,
+            taskDefinition: task_definition,
+            desiredCount: None,
+            launchType: None,
+            capacityProviderStrategy: Vec::new(),
+            platformVersion: None,
+            networkConfiguration: None,
+            loadBalancers: Vec::new(),
+            clientToken: None,
+            tags: Vec::new(),
+            propagateTags: None,
+            enableECSManagedTags: None,
+        }
+    }
+
+    /// Sets the number of tasks the service should keep running.
+    pub fn set_desired_count(&mut self, desired_count: i32) {
+        self.desiredCount = Some(desired_count);
+    }
+
+    /// Sets the launch type the tasks run with, replacing any capacity provider strategy.
+    pub fn set_launch_type(&mut self, launch_type: LaunchType) {
+        self.launchType = Some(launch_type);
+        self.capacityProviderStrategy.clear();
+    }
+
+    /// Sets the capacity provider strategy to use, replacing any launch type.
+    pub fn set_capacity_provider_strategy(&mut self,
+                                          strategy: Vec<CapacityProviderStrategyItem>) -> Result<(), ValidationError> {
+        try!(capacity_provider::validate_strategy(&strategy));
+        self.capacityProviderStrategy = strategy;
+        self.launchType = None;
+        Ok(())
+    }
+
+    /// Sets the Fargate platform version the tasks run on.
+    pub fn set_platform_version(&mut self, platform_version: String) {
+        self.platformVersion = Some(platform_version);
+    }
+
+    /// Sets the network configuration of the tasks of the service.
+    pub fn set_network_configuration(&mut self, network_configuration: NetworkConfiguration) {
+        self.networkConfiguration = Some(network_configuration);
+    }
+
+    /// Adds a load balancer to register the tasks of the service with.
+    pub fn add_load_balancer(&mut self, load_balancer: LoadBalancer) {
+        self.loadBalancers.push(load_balancer);
+    }
+
+    /// Sets the identifier ensuring the request is idempotent.
+    pub fn set_client_token(&mut self, client_token: String) {
+        self.clientToken = Some(client_token);
+    }
+
+    /// Sets the tags to apply to the service, or returns a ValidationError if there are more tags
+    /// than a resource can hold.
+    pub fn set_tags(&mut self, tags: Vec<Tag>) -> Result<(), ValidationError> {
+        try!(tag::validate_tags(&tags));
+        self.tags = tags;
+        Ok(())
+    }
+
+    /// Sets where the tags of the tasks of the service are copied from.
+    pub fn set_propagate_tags(&mut self, propagate_tags: PropagateTags) {
+        self.propagateTags = Some(propagate_tags);
+    }
+
+    /// Sets whether ECS tags the tasks of the service with the cluster and service names.
+    pub fn set_enable_ecs_managed_tags(&mut self, enable: bool) {
+        self.enableECSManagedTags = Some(enable);
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a CreateServiceResponse.
+impl CreateServiceResponse {
+    /// Gets a reference to the service which was created.
+    pub fn get_service(&self) -> &Service {
+        &self.service
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CreateServiceRequest;
+    use types::launch_type::LaunchType;
+    use types::tag::{PropagateTags, Tag};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let mut req = CreateServiceRequest::new(String::from("default"), String::from("web"),
+                                                String::from("web:3"));
+        req.set_desired_count(2);
+        req.set_launch_type(LaunchType::Fargate);
+        req.set_tags(vec![Tag::new(String::from("team"), String::from("platform")).unwrap()]).unwrap();
+        req.set_propagate_tags(PropagateTags::Service);
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\"serviceName\":\"web\",\"taskDefinition\":\"web:3\",\
+              \"desiredCount\":2,\"launchType\":\"FARGATE\",\
+              \"tags\":[{\"key\":\"team\",\"value\":\"platform\"}],\"propagateTags\":\"SERVICE\"}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,176 @@
+//! Defines request and response types for a CreateTaskSet action.  Task sets can only be created
+//! in services using the EXTERNAL deployment controller.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::capacity_provider::{self, CapacityProviderStrategyItem};
+use types::launch_type::LaunchType;
+use types::networking::{NetworkConfiguration, LoadBalancer};
+use types::tag::{self, Tag};
+use types::task_set::{Scale, TaskSet};
+use error::ValidationError;
+use custom_ser;
+
+/// A CreateTaskSet request type which can be serialized to json and set as the body of an HTTP
+/// request.  Construct one of these and pass it to your client in the
+/// ecs_client::create_task_set() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct CreateTaskSetRequest {
+    /// The short name or full ARN of the service to create the task set in.
+    service: String,
+    /// The short name or full ARN of the cluster hosting the service.
+    cluster: String,
+    /// An ID the deployment controller can use to identify the task set.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    externalId: Option<String>,
+    /// The family and revision or full ARN of the task definition the task set runs.
+    taskDefinition: String,
+    /// The network configuration of the tasks in the task set.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    networkConfiguration: Option<NetworkConfiguration>,
+    /// The load balancers to register the tasks in the task set with.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    loadBalancers: Vec<LoadBalancer>,
+    /// The launch type the tasks run with.  Cannot be combined with a capacity provider strategy.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    launchType: Option<LaunchType>,
+    /// The capacity provider strategy to use.  Cannot be combined with a launch type.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    capacityProviderStrategy: Vec<CapacityProviderStrategyItem>,
+    /// The Fargate platform version the tasks run on.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    platformVersion: Option<String>,
+    /// The size of the task set, relative to the desired count of the service.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    scale: Option<Scale>,
+    /// A unique, case-sensitive identifier ensuring the request is idempotent.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    clientToken: Option<String>,
+    /// The tags to apply to the task set.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    tags: Vec<Tag>,
+}
+
+/// A CreateTaskSet response type which can be deserialized from the body of an HTTP response.  If
+/// the request was successful, one of these will be returned from ecs_client::create_task_set().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct CreateTaskSetResponse {
+    /// The task set which was created.
+    taskSet: TaskSet,
+}
+
+/// Used so that a CreateTaskSetRequest can be passed as a generic ECSRequest.
+impl ECSRequest for CreateTaskSetRequest {}
+
+/// Used so that a CreateTaskSetResponse can be returned as a generic ECSResponse.
+impl ECSResponse for CreateTaskSetResponse {}
+
+/// Implements some convenience methods for building a CreateTaskSetRequest.
+impl CreateTaskSetRequest {
+    /// Creates a CreateTaskSetRequest running the given task definition in a service.
+    pub fn new(cluster: String, service: String, task_definition: String) -> Self {
+        CreateTaskSetRequest {
+            service: service,
+            cluster: cluster,
+            externalId: None,
+            taskDefinition: task_definition,
+            networkConfiguration: None,
+            loadBalancers: Vec::new(),
+            launchType: None,
+            capacityProviderStrategy: Vec::new(),
+            platformVersion: None,
+            scale: None,
+            clientToken: None,
+            tags: Vec::new(),
+        }
+    }
+
+    /// Sets the ID the deployment controller uses to identify the task set.
+    pub fn set_external_id(&mut self, external_id: String) {
+        self.externalId = Some(external_id);
+    }
+
+    /// Sets the network configuration of the tasks in the task set.
+    pub fn set_network_configuration(&mut self, network_configuration: NetworkConfiguration) {
+        self.networkConfiguration = Some(network_configuration);
+    }
+
+    /// Adds a load balancer to register the tasks in the task set with.
+    pub fn add_load_balancer(&mut self, load_balancer: LoadBalancer) {
+        self.loadBalancers.push(load_balancer);
+    }
+
+    /// Sets the launch type the tasks run with, replacing any capacity provider strategy.
+    pub fn set_launch_type(&mut self, launch_type: LaunchType) {
+        self.launchType = Some(launch_type);
+        self.capacityProviderStrategy.clear();
+    }
+
+    /// Sets the capacity provider strategy to use, replacing any launch type.
+    pub fn set_capacity_provider_strategy(&mut self,
+                                          strategy: Vec<CapacityProviderStrategyItem>) -> Result<(), ValidationError> {
+        try!(capacity_provider::validate_strategy(&strategy));
+        self.capacityProviderStrategy = strategy;
+        self.launchType = None;
+        Ok(())
+    }
+
+    /// Sets the Fargate platform version the tasks run on.
+    pub fn set_platform_version(&mut self, platform_version: String) {
+        self.platformVersion = Some(platform_version);
+    }
+
+    /// Sets the size of the task set.
+    pub fn set_scale(&mut self, scale: Scale) {
+        self.scale = Some(scale);
+    }
+
+    /// Sets the identifier ensuring the request is idempotent.
+    pub fn set_client_token(&mut self, client_token: String) {
+        self.clientToken = Some(client_token);
+    }
+
+    /// Sets the tags to apply to the task set, or returns a ValidationError if there are more tags
+    /// than a resource can hold.
+    pub fn set_tags(&mut self, tags: Vec<Tag>) -> Result<(), ValidationError> {
+        try!(tag::validate_tags(&tags));
+        self.tags = tags;
+        Ok(())
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a CreateTaskSetResponse.
+impl CreateTaskSetResponse {
+    /// Gets a reference to the task set which was created.
+    pub fn get_task_set(&self) -> &TaskSet {
+        &self.taskSet
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CreateTaskSetRequest;
+    use types::launch_type::LaunchType;
+    use types::networking::LoadBalancer;
+    use types::task_set::Scale;
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let mut req = CreateTaskSetRequest::new(String::from("default"), String::from("web"),
+                                                String::from("web:42"));
+        req.set_external_id(String::from("green"));
+        req.set_launch_type(LaunchType::Fargate);
+        req.add_load_balancer(LoadBalancer::target_group(String::from("arn:tg"), String::from("web"), 80));
+        req.set_scale(Scale::percent(0.0).unwrap());
+        let expected: value::Value = serde_json::from_str(
+            "{\"service\":\"web\",\"cluster\":\"default\",\"externalId\":\"green\",\
+              \"taskDefinition\":\"web:42\",\"launchType\":\"FARGATE\",\
+              \"loadBalancers\":[{\"targetGroupArn\":\"arn:tg\",\"containerName\":\"web\",\"containerPort\":80}],\
+              \"scale\":{\"value\":0.0,\"unit\":\"PERCENT\"}}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+}
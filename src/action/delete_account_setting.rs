@@ -0,0 +1,79 @@
+//! Defines request and response types for a DeleteAccountSetting action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::setting::{Setting, SettingName};
+use custom_ser;
+
+/// A DeleteAccountSetting request type which can be serialized to json and set as the body of an
+/// HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::delete_account_setting() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct DeleteAccountSettingRequest {
+    /// The name of the account setting to reset.
+    name: SettingName,
+    /// The ARN of the IAM user, role or root user to reset the setting for.  If omitted, the
+    /// setting is reset for the authenticated user.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    principalArn: Option<String>,
+}
+
+/// A DeleteAccountSetting response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::delete_account_setting().
+#[derive(Deserialize, Debug)]
+pub struct DeleteAccountSettingResponse {
+    /// The account setting which was deleted.
+    setting: Setting,
+}
+
+/// Used so that a DeleteAccountSettingRequest can be passed as a generic ECSRequest.
+impl ECSRequest for DeleteAccountSettingRequest {}
+
+/// Used so that a DeleteAccountSettingResponse can be returned as a generic ECSResponse.
+impl ECSResponse for DeleteAccountSettingResponse {}
+
+/// Implements some convenience methods for building a DeleteAccountSettingRequest.
+impl DeleteAccountSettingRequest {
+    /// Creates a DeleteAccountSettingRequest for the authenticated user.
+    pub fn new(name: SettingName) -> Self {
+        DeleteAccountSettingRequest {
+            name: name,
+            principalArn: None,
+        }
+    }
+
+    /// Creates a DeleteAccountSettingRequest for the given principal.
+    pub fn for_principal(name: SettingName, principal_arn: String) -> Self {
+        DeleteAccountSettingRequest {
+            name: name,
+            principalArn: Some(principal_arn),
+        }
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// DeleteAccountSettingResponse.
+impl DeleteAccountSettingResponse {
+    /// Gets a reference to the account setting which was deleted.
+    pub fn get_setting(&self) -> &Setting {
+        &self.setting
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DeleteAccountSettingRequest;
+    use types::setting::SettingName;
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_empty_principal() {
+        let req = DeleteAccountSettingRequest::new(SettingName::ContainerInstanceLongArnFormat);
+        let expected: value::Value = serde_json::from_str(
+            "{\"name\":\"containerInstanceLongArnFormat\"}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+}
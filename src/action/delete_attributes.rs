@@ -0,0 +1,82 @@
+//! Defines request and response types for a DeleteAttributes action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use action::put_attributes::validate_attribute_targets;
+use types::attribute::Attribute;
+use error::ValidationError;
+use custom_ser;
+
+/// A DeleteAttributes request type which can be serialized to json and set as the body of an HTTP
+/// request.  Construct one of these and pass it to your client in the
+/// ecs_client::delete_attributes() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct DeleteAttributesRequest {
+    /// The short name or full ARN of the cluster containing the attribute targets.  If omitted,
+    /// the default cluster is assumed.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    cluster: Option<String>,
+    /// The attributes to delete.  Each attribute must have a target.
+    attributes: Vec<Attribute>,
+}
+
+/// A DeleteAttributes response type which can be deserialized from the body of an HTTP response.
+/// If the request was successful, one of these will be returned from
+/// ecs_client::delete_attributes().
+#[derive(Deserialize, Debug)]
+pub struct DeleteAttributesResponse {
+    /// The attributes which were deleted.
+    #[serde(default)]
+    attributes: Vec<Attribute>,
+}
+
+/// Used so that a DeleteAttributesRequest can be passed as a generic ECSRequest.
+impl ECSRequest for DeleteAttributesRequest {}
+
+/// Used so that a DeleteAttributesResponse can be returned as a generic ECSResponse.
+impl ECSResponse for DeleteAttributesResponse {}
+
+/// Implements some convenience methods for building a DeleteAttributesRequest.
+impl DeleteAttributesRequest {
+    /// Creates a DeleteAttributesRequest for attributes in the default cluster.
+    pub fn new(attributes: Vec<Attribute>) -> Result<Self, ValidationError> {
+        try!(validate_attribute_targets(&attributes));
+        Ok(DeleteAttributesRequest {
+            cluster: None,
+            attributes: attributes,
+        })
+    }
+
+    /// Creates a DeleteAttributesRequest for attributes in the given cluster.
+    pub fn for_cluster(cluster: String, attributes: Vec<Attribute>) -> Result<Self, ValidationError> {
+        let mut request = try!(DeleteAttributesRequest::new(attributes));
+        request.cluster = Some(cluster);
+        Ok(request)
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// DeleteAttributesResponse.
+impl DeleteAttributesResponse {
+    /// Gets a reference to the attributes which were deleted.
+    pub fn get_attributes(&self) -> &Vec<Attribute> {
+        &self.attributes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DeleteAttributesResponse;
+    use types::attribute::TargetType;
+    use serde_json;
+
+    #[test]
+    fn test_deserialize_response() {
+        let response: DeleteAttributesResponse = serde_json::from_str(
+            "{\"attributes\":[{\"name\":\"stack\",\"targetType\":\"container-instance\",\
+                               \"targetId\":\"arn\"}]}").unwrap();
+        let attribute = &response.get_attributes()[0];
+        assert_eq!("stack", attribute.get_name());
+        assert_eq!(&Some(TargetType::ContainerInstance), attribute.get_target_type());
+    }
+}
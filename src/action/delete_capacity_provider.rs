@@ -0,0 +1,66 @@
+//! Defines request and response types for a DeleteCapacityProvider action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::capacity_provider::CapacityProvider;
+
+/// A DeleteCapacityProvider request type which can be serialized to json and set as the body of
+/// an HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::delete_capacity_provider() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct DeleteCapacityProviderRequest {
+    /// The short name or full ARN of the capacity provider to delete.
+    capacityProvider: String,
+}
+
+/// A DeleteCapacityProvider response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::delete_capacity_provider().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct DeleteCapacityProviderResponse {
+    /// The capacity provider which is being deleted.
+    capacityProvider: CapacityProvider,
+}
+
+/// Used so that a DeleteCapacityProviderRequest can be passed as a generic ECSRequest.
+impl ECSRequest for DeleteCapacityProviderRequest {}
+
+/// Used so that a DeleteCapacityProviderResponse can be returned as a generic ECSResponse.
+impl ECSResponse for DeleteCapacityProviderResponse {}
+
+/// Implements some convenience methods for building a DeleteCapacityProviderRequest.
+impl DeleteCapacityProviderRequest {
+    /// Creates a DeleteCapacityProviderRequest for the given capacity provider.
+    pub fn new(capacity_provider: String) -> Self {
+        DeleteCapacityProviderRequest {
+            capacityProvider: capacity_provider,
+        }
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// DeleteCapacityProviderResponse.
+impl DeleteCapacityProviderResponse {
+    /// Gets a reference to the capacity provider which is being deleted.
+    pub fn get_capacity_provider(&self) -> &CapacityProvider {
+        &self.capacityProvider
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DeleteCapacityProviderResponse;
+    use types::capacity_provider::{CapacityProviderStatus, CapacityProviderUpdateStatus};
+    use serde_json;
+
+    #[test]
+    fn test_deserialize_response() {
+        let response: DeleteCapacityProviderResponse = serde_json::from_str(
+            "{\"capacityProvider\":{\"name\":\"my-provider\",\"status\":\"ACTIVE\",\
+              \"updateStatus\":\"DELETE_IN_PROGRESS\"}}").unwrap();
+        let provider = response.get_capacity_provider();
+        assert_eq!(&Some(CapacityProviderStatus::Active), provider.get_status());
+        assert_eq!(&Some(CapacityProviderUpdateStatus::DeleteInProgress), provider.get_update_status());
+    }
+}
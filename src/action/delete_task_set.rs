@@ -0,0 +1,81 @@
+//! Defines request and response types for a DeleteTaskSet action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::task_set::TaskSet;
+use custom_ser;
+
+/// A DeleteTaskSet request type which can be serialized to json and set as the body of an HTTP
+/// request.  Construct one of these and pass it to your client in the
+/// ecs_client::delete_task_set() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct DeleteTaskSetRequest {
+    /// The short name or full ARN of the cluster hosting the service.
+    cluster: String,
+    /// The short name or full ARN of the service the task set belongs to.
+    service: String,
+    /// The ID or full ARN of the task set to delete.
+    taskSet: String,
+    /// Whether to delete the task set even if it has not been scaled down to zero.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    force: Option<bool>,
+}
+
+/// A DeleteTaskSet response type which can be deserialized from the body of an HTTP response.  If
+/// the request was successful, one of these will be returned from ecs_client::delete_task_set().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct DeleteTaskSetResponse {
+    /// The task set which is being deleted.
+    taskSet: TaskSet,
+}
+
+/// Used so that a DeleteTaskSetRequest can be passed as a generic ECSRequest.
+impl ECSRequest for DeleteTaskSetRequest {}
+
+/// Used so that a DeleteTaskSetResponse can be returned as a generic ECSResponse.
+impl ECSResponse for DeleteTaskSetResponse {}
+
+/// Implements some convenience methods for building a DeleteTaskSetRequest.
+impl DeleteTaskSetRequest {
+    /// Creates a DeleteTaskSetRequest for a task set which has been scaled down to zero.
+    pub fn new(cluster: String, service: String, task_set: String) -> Self {
+        DeleteTaskSetRequest {
+            cluster: cluster,
+            service: service,
+            taskSet: task_set,
+            force: None,
+        }
+    }
+
+    /// Sets whether to delete the task set even if it has not been scaled down to zero.
+    pub fn set_force(&mut self, force: bool) {
+        self.force = Some(force);
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a DeleteTaskSetResponse.
+impl DeleteTaskSetResponse {
+    /// Gets a reference to the task set which is being deleted.
+    pub fn get_task_set(&self) -> &TaskSet {
+        &self.taskSet
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DeleteTaskSetRequest;
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let mut req = DeleteTaskSetRequest::new(String::from("default"), String::from("web"),
+                                                String::from("ecs-svc/1234"));
+        req.set_force(true);
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\"service\":\"web\",\"taskSet\":\"ecs-svc/1234\",\"force\":true}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,164 @@
+//! Defines request and response types for a DescribeCapacityProviders action.
+
+use action::ecs_action::{ECSAction, ECSRequest, ECSResponse};
+use ecs_client::{self, ECSClient};
+use paginator::{PaginatedRequest, PaginatedResponse};
+use types::capacity_provider::CapacityProvider;
+use types::failure::Failure;
+use custom_ser;
+
+/// A DescribeCapacityProviders request type which can be serialized to json and set as the body
+/// of an HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::describe_capacity_providers() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Clone, Debug)]
+pub struct DescribeCapacityProvidersRequest {
+    /// The short names or full ARNs of up to 100 capacity providers to describe.  If empty, all
+    /// capacity providers are described.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    capacityProviders: Vec<String>,
+    /// Additional information to include in the response.  TAGS is the only supported value.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    include: Vec<String>,
+    /// The max number of results returned in paginated output.  Must be between 1 and 10,
+    /// inclusive.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    maxResults: Option<u8>,
+    /// A value returned by the previous request indicating where to begin the next page of
+    /// paginated output.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    nextToken: Option<String>,
+}
+
+/// A DescribeCapacityProviders response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::describe_capacity_providers().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct DescribeCapacityProvidersResponse {
+    /// The capacity providers which were described.
+    #[serde(default)]
+    capacityProviders: Vec<CapacityProvider>,
+    /// The capacity providers which could not be described.
+    #[serde(default)]
+    failures: Vec<Failure>,
+    /// A value which can be used in a subsequent request to obtain the next page of paginated
+    /// output, or None if there are no more results.
+    nextToken: Option<String>,
+}
+
+/// Used so that a DescribeCapacityProvidersRequest can be passed as a generic ECSRequest.
+impl ECSRequest for DescribeCapacityProvidersRequest {}
+
+/// Used so that a DescribeCapacityProvidersResponse can be returned as a generic ECSResponse.
+impl ECSResponse for DescribeCapacityProvidersResponse {}
+
+/// Used so that a DescribeCapacityProvidersRequest can be followed page by page with a paginator::Pages.
+impl PaginatedRequest for DescribeCapacityProvidersRequest {
+    type Response = DescribeCapacityProvidersResponse;
+
+    fn action(&self) -> ECSAction {
+        ECSAction::DescribeCapacityProviders
+    }
+
+    fn set_page_token(&mut self, next_token: String) {
+        self.nextToken = Some(next_token);
+    }
+
+    fn send_page(self, client: &ECSClient) -> ecs_client::Result<DescribeCapacityProvidersResponse> {
+        client.describe_capacity_providers(self)
+    }
+}
+
+/// Used so that the capacity providers of a DescribeCapacityProvidersResponse can be yielded by a paginator::Items.
+impl PaginatedResponse for DescribeCapacityProvidersResponse {
+    type Item = CapacityProvider;
+
+    fn page_token(&self) -> Option<String> {
+        self.nextToken.clone()
+    }
+
+    fn into_items(self) -> Vec<CapacityProvider> {
+        self.capacityProviders
+    }
+}
+
+/// Implements some convenience methods for building a DescribeCapacityProvidersRequest.
+impl DescribeCapacityProvidersRequest {
+    /// Creates a DescribeCapacityProvidersRequest describing every capacity provider.
+    pub fn new() -> Self {
+        DescribeCapacityProvidersRequest {
+            capacityProviders: Vec::new(),
+            include: Vec::new(),
+            maxResults: None,
+            nextToken: None,
+        }
+    }
+
+    /// Creates a DescribeCapacityProvidersRequest describing only the given capacity providers.
+    pub fn with_capacity_providers(capacity_providers: Vec<String>) -> Self {
+        let mut request = DescribeCapacityProvidersRequest::new();
+        request.capacityProviders = capacity_providers;
+        request
+    }
+
+    /// Requests that the tags of each capacity provider be included in the response.
+    pub fn include_tags(&mut self) {
+        self.include = vec![String::from("TAGS")];
+    }
+
+    /// Sets the max number of results returned in paginated output.
+    pub fn set_max_results(&mut self, max_results: u8) {
+        self.maxResults = Some(max_results);
+    }
+
+    /// Sets the nextToken returned by a previous request.
+    pub fn set_next_token(&mut self, next_token: String) {
+        self.nextToken = Some(next_token);
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// DescribeCapacityProvidersResponse.
+impl DescribeCapacityProvidersResponse {
+    /// Gets a reference to the capacity providers which were described.
+    pub fn get_capacity_providers(&self) -> &Vec<CapacityProvider> {
+        &self.capacityProviders
+    }
+
+    /// Gets a reference to the capacity providers which could not be described.
+    pub fn get_failures(&self) -> &Vec<Failure> {
+        &self.failures
+    }
+
+    /// Gets a reference to the nextToken value returned by the previous request.
+    pub fn get_next_token(&self) -> &Option<String> {
+        &self.nextToken
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DescribeCapacityProvidersRequest, DescribeCapacityProvidersResponse};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let mut req = DescribeCapacityProvidersRequest::with_capacity_providers(vec![String::from("FARGATE")]);
+        req.include_tags();
+        let expected: value::Value = serde_json::from_str(
+            "{\"capacityProviders\":[\"FARGATE\"],\"include\":[\"TAGS\"]}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_deserialize_response() {
+        let response: DescribeCapacityProvidersResponse = serde_json::from_str(
+            "{\"capacityProviders\":[{\"name\":\"FARGATE\",\"status\":\"ACTIVE\"}],\
+              \"failures\":[{\"arn\":\"missing\",\"reason\":\"MISSING\"}]}").unwrap();
+        assert_eq!(1, response.get_capacity_providers().len());
+        assert_eq!(&Some(String::from("MISSING")), response.get_failures()[0].get_reason());
+    }
+}
@@ -0,0 +1,126 @@
+//! Defines request and response types for a DescribeContainerInstances action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::container_instance::ContainerInstance;
+use types::failure::Failure;
+use error::ValidationError;
+use custom_ser;
+
+/// The max number of container instances which can be described in a single request.
+pub const MAX_CONTAINER_INSTANCES_PER_REQUEST: usize = 100;
+
+/// A DescribeContainerInstances request type which can be serialized to json and set as the body
+/// of an HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::describe_container_instances() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct DescribeContainerInstancesRequest {
+    /// The short name or full ARN of the cluster hosting the container instances.  If omitted, the
+    /// default cluster is assumed.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    cluster: Option<String>,
+    /// The IDs or full ARNs of the container instances to describe.
+    containerInstances: Vec<String>,
+    /// Additional information to include in the response.  TAGS is the only supported value.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    include: Vec<String>,
+}
+
+/// A DescribeContainerInstances response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::describe_container_instances().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct DescribeContainerInstancesResponse {
+    /// The container instances which were described.
+    #[serde(default)]
+    containerInstances: Vec<ContainerInstance>,
+    /// The container instances which could not be described.
+    #[serde(default)]
+    failures: Vec<Failure>,
+}
+
+/// Used so that a DescribeContainerInstancesRequest can be passed as a generic ECSRequest.
+impl ECSRequest for DescribeContainerInstancesRequest {}
+
+/// Used so that a DescribeContainerInstancesResponse can be returned as a generic ECSResponse.
+impl ECSResponse for DescribeContainerInstancesResponse {}
+
+/// Implements some convenience methods for building a DescribeContainerInstancesRequest.
+impl DescribeContainerInstancesRequest {
+    /// Creates a DescribeContainerInstancesRequest for container instances in the given cluster.
+    /// Between 1 and 100 container instances must be given.
+    pub fn new(cluster: String, container_instances: Vec<String>) -> Result<Self, ValidationError> {
+        if container_instances.is_empty() ||
+           container_instances.len() > MAX_CONTAINER_INSTANCES_PER_REQUEST {
+            return Err(ValidationError::new("containerInstances",
+                                            format!("must contain between 1 and {} container instances, got {}",
+                                                    MAX_CONTAINER_INSTANCES_PER_REQUEST,
+                                                    container_instances.len())));
+        }
+        Ok(DescribeContainerInstancesRequest {
+            cluster: Some(cluster),
+            containerInstances: container_instances,
+            include: Vec::new(),
+        })
+    }
+
+    /// Requests that the tags of each container instance be included in the response.
+    pub fn include_tags(&mut self) {
+        self.include = vec![String::from("TAGS")];
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// DescribeContainerInstancesResponse.
+impl DescribeContainerInstancesResponse {
+    /// Gets a reference to the container instances which were described.
+    pub fn get_container_instances(&self) -> &Vec<ContainerInstance> {
+        &self.containerInstances
+    }
+
+    /// Gets a reference to the container instances which could not be described.
+    pub fn get_failures(&self) -> &Vec<Failure> {
+        &self.failures
+    }
+
+    /// Consumes the response, returning the container instances which were described.
+    pub fn into_container_instances(self) -> Vec<ContainerInstance> {
+        self.containerInstances
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DescribeContainerInstancesRequest, DescribeContainerInstancesResponse};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let mut req = DescribeContainerInstancesRequest::new(String::from("default"),
+                                                             vec![String::from("c8d1b1a2")]).unwrap();
+        req.include_tags();
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\"containerInstances\":[\"c8d1b1a2\"],\"include\":[\"TAGS\"]}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_instance_count_validation() {
+        assert!(DescribeContainerInstancesRequest::new(String::from("default"), Vec::new()).is_err());
+        let too_many = (0..101).map(|i| i.to_string()).collect();
+        assert!(DescribeContainerInstancesRequest::new(String::from("default"), too_many).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_response() {
+        let resp: DescribeContainerInstancesResponse = serde_json::from_str(
+            "{\"containerInstances\":[{\"versionInfo\":{\"agentVersion\":\"1.9.0\"}}],\
+              \"failures\":[{\"arn\":\"arn:aws:ecs:us-west-2:012345678910:container-instance/missing\",\
+                             \"reason\":\"MISSING\"}]}").unwrap();
+        assert_eq!(1, resp.get_container_instances().len());
+        assert_eq!(1, resp.get_failures().len());
+    }
+}
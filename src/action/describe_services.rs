@@ -0,0 +1,118 @@
+//! Defines request and response types for a DescribeServices action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::failure::Failure;
+use types::service::Service;
+use error::ValidationError;
+use custom_ser;
+
+/// The max number of services which can be described in a single request.
+pub const MAX_SERVICES_PER_REQUEST: usize = 10;
+
+/// A DescribeServices request type which can be serialized to json and set as the body of an HTTP
+/// request.  Construct one of these and pass it to your client in the
+/// ecs_client::describe_services() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Clone, Debug)]
+pub struct DescribeServicesRequest {
+    /// The short name or full ARN of the cluster hosting the services.  If omitted, the default
+    /// cluster is assumed.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    cluster: Option<String>,
+    /// The names or full ARNs of the services to describe.
+    services: Vec<String>,
+    /// Additional information to include in the response.  TAGS is the only supported value.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    include: Vec<String>,
+}
+
+/// A DescribeServices response type which can be deserialized from the body of an HTTP response.
+/// If the request was successful, one of these will be returned from
+/// ecs_client::describe_services().
+#[derive(Deserialize, Debug)]
+pub struct DescribeServicesResponse {
+    /// The services which were described.
+    #[serde(default)]
+    services: Vec<Service>,
+    /// The services which could not be described.
+    #[serde(default)]
+    failures: Vec<Failure>,
+}
+
+/// Used so that a DescribeServicesRequest can be passed as a generic ECSRequest.
+impl ECSRequest for DescribeServicesRequest {}
+
+/// Used so that a DescribeServicesResponse can be returned as a generic ECSResponse.
+impl ECSResponse for DescribeServicesResponse {}
+
+/// Implements some convenience methods for building a DescribeServicesRequest.
+impl DescribeServicesRequest {
+    /// Creates a DescribeServicesRequest for services in the given cluster.  Between 1 and 10
+    /// services must be given.
+    pub fn new(cluster: String, services: Vec<String>) -> Result<Self, ValidationError> {
+        if services.is_empty() || services.len() > MAX_SERVICES_PER_REQUEST {
+            return Err(ValidationError::new("services",
+                                            format!("must contain between 1 and {} services, got {}",
+                                                    MAX_SERVICES_PER_REQUEST, services.len())));
+        }
+        Ok(DescribeServicesRequest {
+            cluster: Some(cluster),
+            services: services,
+            include: Vec::new(),
+        })
+    }
+
+    /// Requests that the tags of each service be included in the response.
+    pub fn include_tags(&mut self) {
+        self.include = vec![String::from("TAGS")];
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// DescribeServicesResponse.
+impl DescribeServicesResponse {
+    /// Gets a reference to the services which were described.
+    pub fn get_services(&self) -> &Vec<Service> {
+        &self.services
+    }
+
+    /// Gets a reference to the services which could not be described.
+    pub fn get_failures(&self) -> &Vec<Failure> {
+        &self.failures
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DescribeServicesRequest, DescribeServicesResponse};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let mut req = DescribeServicesRequest::new(String::from("default"),
+                                                   vec![String::from("web")]).unwrap();
+        req.include_tags();
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\"services\":[\"web\"],\"include\":[\"TAGS\"]}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_service_count_validation() {
+        assert!(DescribeServicesRequest::new(String::from("default"), Vec::new()).is_err());
+        let too_many = (0..11).map(|i| i.to_string()).collect();
+        assert!(DescribeServicesRequest::new(String::from("default"), too_many).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_response() {
+        let resp: DescribeServicesResponse = serde_json::from_str(
+            "{\"services\":[{\"serviceName\":\"web\",\"status\":\"ACTIVE\",\"desiredCount\":1,\
+                             \"runningCount\":1,\"deployments\":[{\"id\":\"ecs-svc/1\"}]}],\
+              \"failures\":[]}").unwrap();
+        assert_eq!(1, resp.get_services().len());
+        assert!(resp.get_services()[0].is_stable());
+    }
+}
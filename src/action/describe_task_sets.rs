@@ -0,0 +1,99 @@
+//! Defines request and response types for a DescribeTaskSets action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::failure::Failure;
+use types::task_set::TaskSet;
+use custom_ser;
+
+/// A DescribeTaskSets request type which can be serialized to json and set as the body of an HTTP
+/// request.  Construct one of these and pass it to your client in the
+/// ecs_client::describe_task_sets() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct DescribeTaskSetsRequest {
+    /// The short name or full ARN of the cluster hosting the service.
+    cluster: String,
+    /// The short name or full ARN of the service the task sets belong to.
+    service: String,
+    /// The IDs or full ARNs of the task sets to describe.  If empty, all task sets are described.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    taskSets: Vec<String>,
+    /// Additional information to include in the response.  TAGS is the only supported value.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    include: Vec<String>,
+}
+
+/// A DescribeTaskSets response type which can be deserialized from the body of an HTTP response.
+/// If the request was successful, one of these will be returned from
+/// ecs_client::describe_task_sets().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct DescribeTaskSetsResponse {
+    /// The task sets which were described.
+    #[serde(default)]
+    taskSets: Vec<TaskSet>,
+    /// The task sets which could not be described.
+    #[serde(default)]
+    failures: Vec<Failure>,
+}
+
+/// Used so that a DescribeTaskSetsRequest can be passed as a generic ECSRequest.
+impl ECSRequest for DescribeTaskSetsRequest {}
+
+/// Used so that a DescribeTaskSetsResponse can be returned as a generic ECSResponse.
+impl ECSResponse for DescribeTaskSetsResponse {}
+
+/// Implements some convenience methods for building a DescribeTaskSetsRequest.
+impl DescribeTaskSetsRequest {
+    /// Creates a DescribeTaskSetsRequest describing every task set of a service.
+    pub fn new(cluster: String, service: String) -> Self {
+        DescribeTaskSetsRequest {
+            cluster: cluster,
+            service: service,
+            taskSets: Vec::new(),
+            include: Vec::new(),
+        }
+    }
+
+    /// Creates a DescribeTaskSetsRequest describing only the given task sets of a service.
+    pub fn with_task_sets(cluster: String, service: String, task_sets: Vec<String>) -> Self {
+        let mut request = DescribeTaskSetsRequest::new(cluster, service);
+        request.taskSets = task_sets;
+        request
+    }
+
+    /// Requests that the tags of each task set be included in the response.
+    pub fn include_tags(&mut self) {
+        self.include = vec![String::from("TAGS")];
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// DescribeTaskSetsResponse.
+impl DescribeTaskSetsResponse {
+    /// Gets a reference to the task sets which were described.
+    pub fn get_task_sets(&self) -> &Vec<TaskSet> {
+        &self.taskSets
+    }
+
+    /// Gets a reference to the task sets which could not be described.
+    pub fn get_failures(&self) -> &Vec<Failure> {
+        &self.failures
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DescribeTaskSetsResponse;
+    use types::task_set::TaskSetStatus;
+    use serde_json;
+
+    #[test]
+    fn test_deserialize_response() {
+        let response: DescribeTaskSetsResponse = serde_json::from_str(
+            "{\"taskSets\":[{\"id\":\"ecs-svc/1\",\"status\":\"PRIMARY\"},\
+                            {\"id\":\"ecs-svc/2\",\"status\":\"ACTIVE\"}],\"failures\":[]}").unwrap();
+        assert_eq!(2, response.get_task_sets().len());
+        assert_eq!(&Some(TaskSetStatus::Active), response.get_task_sets()[1].get_status());
+    }
+}
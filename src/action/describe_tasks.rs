@@ -0,0 +1,115 @@
+//! Defines request and response types for a DescribeTasks action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::failure::Failure;
+use types::task::Task;
+use error::ValidationError;
+use custom_ser;
+
+/// The max number of tasks which can be described in a single request.
+pub const MAX_TASKS_PER_REQUEST: usize = 100;
+
+/// A DescribeTasks request type which can be serialized to json and set as the body of an HTTP
+/// request.  Construct one of these and pass it to your client in the
+/// ecs_client::describe_tasks() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Clone, Debug)]
+pub struct DescribeTasksRequest {
+    /// The short name or full ARN of the cluster hosting the tasks.  If omitted, the default
+    /// cluster is assumed.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    cluster: Option<String>,
+    /// The IDs or full ARNs of the tasks to describe.
+    tasks: Vec<String>,
+    /// Additional information to include in the response.  TAGS is the only supported value.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    include: Vec<String>,
+}
+
+/// A DescribeTasks response type which can be deserialized from the body of an HTTP response.  If
+/// the request was successful, one of these will be returned from ecs_client::describe_tasks().
+#[derive(Deserialize, Debug)]
+pub struct DescribeTasksResponse {
+    /// The tasks which were described.
+    #[serde(default)]
+    tasks: Vec<Task>,
+    /// The tasks which could not be described.
+    #[serde(default)]
+    failures: Vec<Failure>,
+}
+
+/// Used so that a DescribeTasksRequest can be passed as a generic ECSRequest.
+impl ECSRequest for DescribeTasksRequest {}
+
+/// Used so that a DescribeTasksResponse can be returned as a generic ECSResponse.
+impl ECSResponse for DescribeTasksResponse {}
+
+/// Implements some convenience methods for building a DescribeTasksRequest.
+impl DescribeTasksRequest {
+    /// Creates a DescribeTasksRequest for tasks in the given cluster.  Between 1 and 100 tasks must
+    /// be given.
+    pub fn new(cluster: String, tasks: Vec<String>) -> Result<Self, ValidationError> {
+        if tasks.is_empty() || tasks.len() > MAX_TASKS_PER_REQUEST {
+            return Err(ValidationError::new("tasks",
+                                            format!("must contain between 1 and {} tasks, got {}",
+                                                    MAX_TASKS_PER_REQUEST, tasks.len())));
+        }
+        Ok(DescribeTasksRequest {
+            cluster: Some(cluster),
+            tasks: tasks,
+            include: Vec::new(),
+        })
+    }
+
+    /// Requests that the tags of each task be included in the response.
+    pub fn include_tags(&mut self) {
+        self.include = vec![String::from("TAGS")];
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a DescribeTasksResponse.
+impl DescribeTasksResponse {
+    /// Gets a reference to the tasks which were described.
+    pub fn get_tasks(&self) -> &Vec<Task> {
+        &self.tasks
+    }
+
+    /// Gets a reference to the tasks which could not be described.
+    pub fn get_failures(&self) -> &Vec<Failure> {
+        &self.failures
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DescribeTasksRequest, DescribeTasksResponse};
+    use types::task;
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let req = DescribeTasksRequest::new(String::from("default"),
+                                            vec![String::from("0123abcd")]).unwrap();
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\"tasks\":[\"0123abcd\"]}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_task_count_validation() {
+        assert!(DescribeTasksRequest::new(String::from("default"), Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_response() {
+        let resp: DescribeTasksResponse = serde_json::from_str(
+            "{\"tasks\":[{\"taskArn\":\"arn:aws:ecs:us-west-2:012345678910:task/0123abcd\",\
+                          \"lastStatus\":\"STOPPED\",\"stopCode\":\"EssentialContainerExited\",\
+                          \"containers\":[{\"name\":\"web\",\"exitCode\":1}]}]}").unwrap();
+        assert!(resp.get_tasks()[0].has_last_status(task::STOPPED));
+        assert_eq!(&Some(1), resp.get_tasks()[0].get_containers()[0].get_exit_code());
+        assert!(resp.get_failures().is_empty());
+    }
+}
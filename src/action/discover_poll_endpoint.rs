@@ -0,0 +1,94 @@
+//! Defines request and response types for a DiscoverPollEndpoint action.  The container agent
+//! uses this action to find the endpoints it should poll for work and send telemetry to.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use custom_ser;
+
+/// A DiscoverPollEndpoint request type which can be serialized to json and set as the body of an
+/// HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::discover_poll_endpoint() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct DiscoverPollEndpointRequest {
+    /// The container instance ID or full ARN of the container instance.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    containerInstance: Option<String>,
+    /// The short name or full ARN of the cluster the container instance belongs to.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    cluster: Option<String>,
+}
+
+/// A DiscoverPollEndpoint response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::discover_poll_endpoint().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct DiscoverPollEndpointResponse {
+    /// The endpoint the container agent should poll for work.
+    endpoint: Option<String>,
+    /// The endpoint the container agent should send telemetry to.
+    telemetryEndpoint: Option<String>,
+}
+
+/// Used so that a DiscoverPollEndpointRequest can be passed as a generic ECSRequest.
+impl ECSRequest for DiscoverPollEndpointRequest {}
+
+/// Used so that a DiscoverPollEndpointResponse can be returned as a generic ECSResponse.
+impl ECSResponse for DiscoverPollEndpointResponse {}
+
+/// Implements some convenience methods for building a DiscoverPollEndpointRequest.
+impl DiscoverPollEndpointRequest {
+    /// Creates a default DiscoverPollEndpointRequest with no field values.
+    pub fn new() -> Self {
+        DiscoverPollEndpointRequest {
+            containerInstance: None,
+            cluster: None,
+        }
+    }
+
+    /// Creates a DiscoverPollEndpointRequest with all fields set.
+    pub fn with_all(container_instance: String, cluster: String) -> Self {
+        DiscoverPollEndpointRequest {
+            containerInstance: Some(container_instance),
+            cluster: Some(cluster),
+        }
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// DiscoverPollEndpointResponse.
+impl DiscoverPollEndpointResponse {
+    /// Gets the endpoint the container agent should poll for work.
+    pub fn get_endpoint(&self) -> &Option<String> {
+        &self.endpoint
+    }
+
+    /// Gets the endpoint the container agent should send telemetry to.
+    pub fn get_telemetry_endpoint(&self) -> &Option<String> {
+        &self.telemetryEndpoint
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DiscoverPollEndpointRequest, DiscoverPollEndpointResponse};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let req = DiscoverPollEndpointRequest::with_all(String::from("instance-id"), String::from("default"));
+        let expected: value::Value = serde_json::from_str(
+            "{\"containerInstance\":\"instance-id\",\"cluster\":\"default\"}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_deserialize_response() {
+        let response: DiscoverPollEndpointResponse = serde_json::from_str(
+            "{\"endpoint\":\"https://ecs-a-1.us-west-2.amazonaws.com/\",\
+              \"telemetryEndpoint\":\"https://ecs-t-1.us-west-2.amazonaws.com/\"}").unwrap();
+        assert_eq!(&Some(String::from("https://ecs-a-1.us-west-2.amazonaws.com/")), response.get_endpoint());
+    }
+}
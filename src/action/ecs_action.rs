@@ -6,9 +6,45 @@ use serde::ser::Serialize;
 use serde::de::Deserialize;
 
 /// An enum defining the possible actions which can be sent to Amazon ECS.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ECSAction {
     ListClusters,
+    RegisterContainerInstance,
+    SubmitTaskStateChange,
+    SubmitContainerStateChange,
+    DiscoverPollEndpoint,
+    TagResource,
+    UntagResource,
+    ListTagsForResource,
+    PutAttributes,
+    DeleteAttributes,
+    ListAttributes,
+    CreateCapacityProvider,
+    UpdateCapacityProvider,
+    DeleteCapacityProvider,
+    DescribeCapacityProviders,
+    PutClusterCapacityProviders,
+    PutAccountSetting,
+    PutAccountSettingDefault,
+    ListAccountSettings,
+    DeleteAccountSetting,
+    CreateTaskSet,
+    UpdateTaskSet,
+    DeleteTaskSet,
+    DescribeTaskSets,
+    UpdateServicePrimaryTaskSet,
+    ExecuteCommand,
+    UpdateCluster,
+    UpdateClusterSettings,
+    UpdateContainerAgent,
+    ListContainerInstances,
+    DescribeContainerInstances,
+    DescribeServices,
+    DescribeTasks,
+    CreateCluster,
+    CreateService,
+    RegisterTaskDefinition,
+    RunTask,
 }
 
 /// Used primarily to map an ECSAction to its corresponding string representation to be used in
@@ -17,6 +53,42 @@ impl Display for ECSAction {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         let action_str = match self {
             &ECSAction::ListClusters => "ListClusters",
+            &ECSAction::RegisterContainerInstance => "RegisterContainerInstance",
+            &ECSAction::SubmitTaskStateChange => "SubmitTaskStateChange",
+            &ECSAction::SubmitContainerStateChange => "SubmitContainerStateChange",
+            &ECSAction::DiscoverPollEndpoint => "DiscoverPollEndpoint",
+            &ECSAction::TagResource => "TagResource",
+            &ECSAction::UntagResource => "UntagResource",
+            &ECSAction::ListTagsForResource => "ListTagsForResource",
+            &ECSAction::PutAttributes => "PutAttributes",
+            &ECSAction::DeleteAttributes => "DeleteAttributes",
+            &ECSAction::ListAttributes => "ListAttributes",
+            &ECSAction::CreateCapacityProvider => "CreateCapacityProvider",
+            &ECSAction::UpdateCapacityProvider => "UpdateCapacityProvider",
+            &ECSAction::DeleteCapacityProvider => "DeleteCapacityProvider",
+            &ECSAction::DescribeCapacityProviders => "DescribeCapacityProviders",
+            &ECSAction::PutClusterCapacityProviders => "PutClusterCapacityProviders",
+            &ECSAction::PutAccountSetting => "PutAccountSetting",
+            &ECSAction::PutAccountSettingDefault => "PutAccountSettingDefault",
+            &ECSAction::ListAccountSettings => "ListAccountSettings",
+            &ECSAction::DeleteAccountSetting => "DeleteAccountSetting",
+            &ECSAction::CreateTaskSet => "CreateTaskSet",
+            &ECSAction::UpdateTaskSet => "UpdateTaskSet",
+            &ECSAction::DeleteTaskSet => "DeleteTaskSet",
+            &ECSAction::DescribeTaskSets => "DescribeTaskSets",
+            &ECSAction::UpdateServicePrimaryTaskSet => "UpdateServicePrimaryTaskSet",
+            &ECSAction::ExecuteCommand => "ExecuteCommand",
+            &ECSAction::UpdateCluster => "UpdateCluster",
+            &ECSAction::UpdateClusterSettings => "UpdateClusterSettings",
+            &ECSAction::UpdateContainerAgent => "UpdateContainerAgent",
+            &ECSAction::ListContainerInstances => "ListContainerInstances",
+            &ECSAction::DescribeContainerInstances => "DescribeContainerInstances",
+            &ECSAction::DescribeServices => "DescribeServices",
+            &ECSAction::DescribeTasks => "DescribeTasks",
+            &ECSAction::CreateCluster => "CreateCluster",
+            &ECSAction::CreateService => "CreateService",
+            &ECSAction::RegisterTaskDefinition => "RegisterTaskDefinition",
+            &ECSAction::RunTask => "RunTask",
         };
 
         write!(f, "{}", action_str)
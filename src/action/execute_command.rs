@@ -0,0 +1,141 @@
+//! Defines request and response types for an ExecuteCommand action.  The Session returned in the
+//! response can be handed to data_channel::DataChannel::open() to talk to the command.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::session::Session;
+use custom_ser;
+
+/// An ExecuteCommand request type which can be serialized to json and set as the body of an HTTP
+/// request.  Construct one of these and pass it to your client in the
+/// ecs_client::execute_command() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct ExecuteCommandRequest {
+    /// The short name or full ARN of the cluster running the task.  If omitted, the default
+    /// cluster is assumed.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    cluster: Option<String>,
+    /// The ID or full ARN of the task to run the command in.
+    task: String,
+    /// The name of the container to run the command in.  Required if the task has more than one
+    /// container.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    container: Option<String>,
+    /// The command to run.
+    command: String,
+    /// Whether to run the command interactively.  ECS only supports interactive commands.
+    interactive: bool,
+}
+
+/// An ExecuteCommand response type which can be deserialized from the body of an HTTP response.
+/// If the request was successful, one of these will be returned from
+/// ecs_client::execute_command().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct ExecuteCommandResponse {
+    /// The ARN of the cluster running the task.
+    clusterArn: Option<String>,
+    /// The ARN of the container the command runs in.
+    containerArn: Option<String>,
+    /// The name of the container the command runs in.
+    containerName: Option<String>,
+    /// Whether the command runs interactively.
+    interactive: Option<bool>,
+    /// The session used to talk to the command.
+    session: Session,
+    /// The ARN of the task the command runs in.
+    taskArn: Option<String>,
+}
+
+/// Used so that an ExecuteCommandRequest can be passed as a generic ECSRequest.
+impl ECSRequest for ExecuteCommandRequest {}
+
+/// Used so that an ExecuteCommandResponse can be returned as a generic ECSResponse.
+impl ECSResponse for ExecuteCommandResponse {}
+
+/// Implements some convenience methods for building an ExecuteCommandRequest.
+impl ExecuteCommandRequest {
+    /// Creates an interactive ExecuteCommandRequest running a command in a task of the default
+    /// cluster.
+    pub fn new(task: String, command: String) -> Self {
+        ExecuteCommandRequest {
+            cluster: None,
+            task: task,
+            container: None,
+            command: command,
+            interactive: true,
+        }
+    }
+
+    /// Sets the cluster running the task.
+    pub fn set_cluster(&mut self, cluster: String) {
+        self.cluster = Some(cluster);
+    }
+
+    /// Sets the container to run the command in.
+    pub fn set_container(&mut self, container: String) {
+        self.container = Some(container);
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in an
+/// ExecuteCommandResponse.
+impl ExecuteCommandResponse {
+    /// Gets the ARN of the cluster running the task.
+    pub fn get_cluster_arn(&self) -> &Option<String> {
+        &self.clusterArn
+    }
+
+    /// Gets the ARN of the container the command runs in.
+    pub fn get_container_arn(&self) -> &Option<String> {
+        &self.containerArn
+    }
+
+    /// Gets the name of the container the command runs in.
+    pub fn get_container_name(&self) -> &Option<String> {
+        &self.containerName
+    }
+
+    /// Gets whether the command runs interactively.
+    pub fn get_interactive(&self) -> &Option<bool> {
+        &self.interactive
+    }
+
+    /// Gets a reference to the session used to talk to the command.
+    pub fn get_session(&self) -> &Session {
+        &self.session
+    }
+
+    /// Gets the ARN of the task the command runs in.
+    pub fn get_task_arn(&self) -> &Option<String> {
+        &self.taskArn
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ExecuteCommandRequest, ExecuteCommandResponse};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let mut req = ExecuteCommandRequest::new(String::from("task-id"), String::from("/bin/sh"));
+        req.set_cluster(String::from("default"));
+        req.set_container(String::from("web"));
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\"task\":\"task-id\",\"container\":\"web\",\
+              \"command\":\"/bin/sh\",\"interactive\":true}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_deserialize_response() {
+        let response: ExecuteCommandResponse = serde_json::from_str(
+            "{\"containerName\":\"web\",\"interactive\":true,\
+              \"session\":{\"sessionId\":\"s-1\",\"streamUrl\":\"wss://example\",\"tokenValue\":\"t\"}}").unwrap();
+        assert_eq!("s-1", response.get_session().get_session_id());
+        assert_eq!("wss://example", response.get_session().get_stream_url());
+    }
+}
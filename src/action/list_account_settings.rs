@@ -0,0 +1,176 @@
+//! Defines request and response types for a ListAccountSettings action.
+
+use action::ecs_action::{ECSAction, ECSRequest, ECSResponse};
+use ecs_client::{self, ECSClient};
+use paginator::{PaginatedRequest, PaginatedResponse};
+use types::setting::{Setting, SettingName};
+use custom_ser;
+
+/// A ListAccountSettings request type which can be serialized to json and set as the body of an
+/// HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::list_account_settings() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Clone, Debug)]
+pub struct ListAccountSettingsRequest {
+    /// Only list settings with this name.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    name: Option<SettingName>,
+    /// Only list settings with this value.  Requires name to be set.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    value: Option<String>,
+    /// List the settings of this IAM user, role or root user instead of the authenticated user.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    principalArn: Option<String>,
+    /// Whether to list the effective settings, which fall back to the account defaults where a
+    /// principal has no explicit setting.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    effectiveSettings: Option<bool>,
+    /// The max number of results returned in paginated output.  Must be between 1 and 10,
+    /// inclusive.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    maxResults: Option<u8>,
+    /// A value returned by the previous request indicating where to begin the next page of
+    /// paginated output.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    nextToken: Option<String>,
+}
+
+/// A ListAccountSettings response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::list_account_settings().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct ListAccountSettingsResponse {
+    /// The account settings matching the request.
+    #[serde(default)]
+    settings: Vec<Setting>,
+    /// A value which can be used in a subsequent ListAccountSettingsRequest to obtain the next
+    /// page of paginated output, or None if there are no more results.
+    nextToken: Option<String>,
+}
+
+/// Used so that a ListAccountSettingsRequest can be passed as a generic ECSRequest.
+impl ECSRequest for ListAccountSettingsRequest {}
+
+/// Used so that a ListAccountSettingsResponse can be returned as a generic ECSResponse.
+impl ECSResponse for ListAccountSettingsResponse {}
+
+/// Used so that a ListAccountSettingsRequest can be followed page by page with a paginator::Pages.
+impl PaginatedRequest for ListAccountSettingsRequest {
+    type Response = ListAccountSettingsResponse;
+
+    fn action(&self) -> ECSAction {
+        ECSAction::ListAccountSettings
+    }
+
+    fn set_page_token(&mut self, next_token: String) {
+        self.nextToken = Some(next_token);
+    }
+
+    fn send_page(self, client: &ECSClient) -> ecs_client::Result<ListAccountSettingsResponse> {
+        client.list_account_settings(self)
+    }
+}
+
+/// Used so that the settings of a ListAccountSettingsResponse can be yielded by a paginator::Items.
+impl PaginatedResponse for ListAccountSettingsResponse {
+    type Item = Setting;
+
+    fn page_token(&self) -> Option<String> {
+        self.nextToken.clone()
+    }
+
+    fn into_items(self) -> Vec<Setting> {
+        self.settings
+    }
+}
+
+/// Implements some convenience methods for building a ListAccountSettingsRequest.
+impl ListAccountSettingsRequest {
+    /// Creates a default ListAccountSettingsRequest with no field values.
+    pub fn new() -> Self {
+        ListAccountSettingsRequest {
+            name: None,
+            value: None,
+            principalArn: None,
+            effectiveSettings: None,
+            maxResults: None,
+            nextToken: None,
+        }
+    }
+
+    /// Creates a ListAccountSettingsRequest listing the effective settings of the given
+    /// principal, which is what an audit of that principal should look at.
+    pub fn effective_for_principal(principal_arn: String) -> Self {
+        let mut request = ListAccountSettingsRequest::new();
+        request.principalArn = Some(principal_arn);
+        request.effectiveSettings = Some(true);
+        request
+    }
+
+    /// Restricts the results to settings with the given name.
+    pub fn set_name(&mut self, name: SettingName) {
+        self.name = Some(name);
+    }
+
+    /// Restricts the results to settings with the given value.
+    pub fn set_value(&mut self, value: String) {
+        self.value = Some(value);
+    }
+
+    /// Sets whether to list the effective settings.
+    pub fn set_effective_settings(&mut self, effective_settings: bool) {
+        self.effectiveSettings = Some(effective_settings);
+    }
+
+    /// Sets the max number of results returned in paginated output.
+    pub fn set_max_results(&mut self, max_results: u8) {
+        self.maxResults = Some(max_results);
+    }
+
+    /// Sets the nextToken returned by a previous request.
+    pub fn set_next_token(&mut self, next_token: String) {
+        self.nextToken = Some(next_token);
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// ListAccountSettingsResponse.
+impl ListAccountSettingsResponse {
+    /// Gets a reference to the account settings matching the request.
+    pub fn get_settings(&self) -> &Vec<Setting> {
+        &self.settings
+    }
+
+    /// Gets a reference to the nextToken value returned by the previous request.
+    pub fn get_next_token(&self) -> &Option<String> {
+        &self.nextToken
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ListAccountSettingsRequest, ListAccountSettingsResponse};
+    use types::setting::SettingName;
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let mut req = ListAccountSettingsRequest::effective_for_principal(String::from("arn:root"));
+        req.set_name(SettingName::ServiceLongArnFormat);
+        let expected: value::Value = serde_json::from_str(
+            "{\"name\":\"serviceLongArnFormat\",\"principalArn\":\"arn:root\",\
+              \"effectiveSettings\":true}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_deserialize_response() {
+        let response: ListAccountSettingsResponse = serde_json::from_str(
+            "{\"settings\":[{\"name\":\"containerInsights\",\"value\":\"disabled\"}],\"nextToken\":\"t\"}").unwrap();
+        assert!(!response.get_settings()[0].is_enabled());
+        assert_eq!(&Some(String::from("t")), response.get_next_token());
+    }
+}
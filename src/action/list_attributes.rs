@@ -0,0 +1,169 @@
+//! Defines request and response types for a ListAttributes action.
+
+use action::ecs_action::{ECSAction, ECSRequest, ECSResponse};
+use ecs_client::{self, ECSClient};
+use paginator::{PaginatedRequest, PaginatedResponse};
+use types::attribute::{self, Attribute, TargetType};
+use error::ValidationError;
+use custom_ser;
+
+/// A ListAttributes request type which can be serialized to json and set as the body of an HTTP
+/// request.  Construct one of these and pass it to your client in the
+/// ecs_client::list_attributes() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Clone, Debug)]
+pub struct ListAttributesRequest {
+    /// The short name or full ARN of the cluster to list attributes in.  If omitted, the default
+    /// cluster is assumed.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    cluster: Option<String>,
+    /// The type of the targets to list attributes of.
+    targetType: TargetType,
+    /// Only list attributes with this name.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    attributeName: Option<String>,
+    /// Only list attributes with this value.  Requires attributeName to be set.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    attributeValue: Option<String>,
+    /// The max number of results returned in paginated output.  Must be between 1 and 100,
+    /// inclusive.  If omitted, defaults to 100.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    maxResults: Option<u8>,
+    /// A value returned by the previous request indicating where to begin the next page of
+    /// paginated output.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    nextToken: Option<String>,
+}
+
+/// A ListAttributes response type which can be deserialized from the body of an HTTP response.
+/// If the request was successful, one of these will be returned from
+/// ecs_client::list_attributes().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct ListAttributesResponse {
+    /// The attributes matching the request.
+    #[serde(default)]
+    attributes: Vec<Attribute>,
+    /// A value which can be used in a subsequent ListAttributesRequest to obtain the next page of
+    /// paginated output, or None if there are no more results.
+    nextToken: Option<String>,
+}
+
+/// Used so that a ListAttributesRequest can be passed as a generic ECSRequest.
+impl ECSRequest for ListAttributesRequest {}
+
+/// Used so that a ListAttributesResponse can be returned as a generic ECSResponse.
+impl ECSResponse for ListAttributesResponse {}
+
+/// Used so that a ListAttributesRequest can be followed page by page with a paginator::Pages.
+impl PaginatedRequest for ListAttributesRequest {
+    type Response = ListAttributesResponse;
+
+    fn action(&self) -> ECSAction {
+        ECSAction::ListAttributes
+    }
+
+    fn set_page_token(&mut self, next_token: String) {
+        self.nextToken = Some(next_token);
+    }
+
+    fn send_page(self, client: &ECSClient) -> ecs_client::Result<ListAttributesResponse> {
+        client.list_attributes(self)
+    }
+}
+
+/// Used so that the attributes of a ListAttributesResponse can be yielded by a paginator::Items.
+impl PaginatedResponse for ListAttributesResponse {
+    type Item = Attribute;
+
+    fn page_token(&self) -> Option<String> {
+        self.nextToken.clone()
+    }
+
+    fn into_items(self) -> Vec<Attribute> {
+        self.attributes
+    }
+}
+
+/// Implements some convenience methods for building a ListAttributesRequest.
+impl ListAttributesRequest {
+    /// Creates a ListAttributesRequest listing every attribute of the given target type.
+    pub fn new(target_type: TargetType) -> Self {
+        ListAttributesRequest {
+            cluster: None,
+            targetType: target_type,
+            attributeName: None,
+            attributeValue: None,
+            maxResults: None,
+            nextToken: None,
+        }
+    }
+
+    /// Sets the cluster to list attributes in.
+    pub fn set_cluster(&mut self, cluster: String) {
+        self.cluster = Some(cluster);
+    }
+
+    /// Restricts the results to attributes with the given name and, optionally, value.
+    pub fn set_attribute(&mut self, name: String, value: Option<String>) -> Result<(), ValidationError> {
+        try!(attribute::validate_name(&name));
+        if let Some(ref value) = value {
+            try!(attribute::validate_value(value));
+        }
+        self.attributeName = Some(name);
+        self.attributeValue = value;
+        Ok(())
+    }
+
+    /// Sets the max number of results returned in paginated output.
+    pub fn set_max_results(&mut self, max_results: u8) {
+        self.maxResults = Some(max_results);
+    }
+
+    /// Sets the nextToken returned by a previous request.
+    pub fn set_next_token(&mut self, next_token: String) {
+        self.nextToken = Some(next_token);
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// ListAttributesResponse.
+impl ListAttributesResponse {
+    /// Gets a reference to the attributes matching the request.
+    pub fn get_attributes(&self) -> &Vec<Attribute> {
+        &self.attributes
+    }
+
+    /// Gets a reference to the nextToken value returned by the previous request.
+    pub fn get_next_token(&self) -> &Option<String> {
+        &self.nextToken
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ListAttributesRequest;
+    use types::attribute::TargetType;
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let mut req = ListAttributesRequest::new(TargetType::ContainerInstance);
+        req.set_cluster(String::from("default"));
+        req.set_attribute(String::from("stack"), Some(String::from("prod"))).unwrap();
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\"targetType\":\"container-instance\",\
+              \"attributeName\":\"stack\",\"attributeValue\":\"prod\"}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_empty_blob() {
+        let req = ListAttributesRequest::new(TargetType::ContainerInstance);
+        let expected: value::Value = serde_json::from_str("{\"targetType\":\"container-instance\"}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+}
@@ -1,13 +1,15 @@
 //! Defines request and response types for a ListClusters action.
 
-use action::ecs_action::{ECSRequest, ECSResponse};
+use action::ecs_action::{ECSAction, ECSRequest, ECSResponse};
+use ecs_client::{self, ECSClient};
+use paginator::{PaginatedRequest, PaginatedResponse};
 use custom_ser;
 
 /// A ListClusters request type which can be serialized to json and set as the body of an HTTP
 /// request.  Construct one of these and pass it to your client in the ecs_client::list_clusters()
 /// function.
 #[allow(non_snake_case)]
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Clone, Debug)]
 pub struct ListClustersRequest {
     /// The max number of cluster results returned in paginated output. 
     /// Must be between 1 and 100, inclusive.
@@ -45,6 +47,36 @@ impl ECSRequest for ListClustersRequest {}
 /// Used so that a ListClustersResponse can be returned as a generic ECSResponse.
 impl ECSResponse for ListClustersResponse {}
 
+/// Used so that a ListClustersRequest can be followed page by page with a paginator::Pages.
+impl PaginatedRequest for ListClustersRequest {
+    type Response = ListClustersResponse;
+
+    fn action(&self) -> ECSAction {
+        ECSAction::ListClusters
+    }
+
+    fn set_page_token(&mut self, next_token: String) {
+        self.nextToken = Some(next_token);
+    }
+
+    fn send_page(self, client: &ECSClient) -> ecs_client::Result<ListClustersResponse> {
+        client.list_clusters(self)
+    }
+}
+
+/// Used so that the cluster ARNs of a ListClustersResponse can be yielded by a paginator::Items.
+impl PaginatedResponse for ListClustersResponse {
+    type Item = String;
+
+    fn page_token(&self) -> Option<String> {
+        self.nextToken.clone()
+    }
+
+    fn into_items(self) -> Vec<String> {
+        self.clusterArns
+    }
+}
+
 /// Implements some convenience methods for building a ListClustersRequest.
 impl ListClustersRequest {
     /// Creates a default ListClustersRequest with no field values.
@@ -0,0 +1,187 @@
+//! Defines request and response types for a ListContainerInstances action.
+
+use action::ecs_action::{ECSAction, ECSRequest, ECSResponse};
+use ecs_client::{self, ECSClient};
+use paginator::{PaginatedRequest, PaginatedResponse};
+use custom_ser;
+
+string_enum! {
+    /// The status used to filter the results of a ListContainerInstances request.
+    pub enum ContainerInstanceStatus {
+        Active => "ACTIVE",
+        Draining => "DRAINING",
+        Registering => "REGISTERING",
+        Deregistering => "DEREGISTERING",
+        RegistrationFailed => "REGISTRATION_FAILED",
+    }
+}
+
+/// A ListContainerInstances request type which can be serialized to json and set as the body of an
+/// HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::list_container_instances() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Clone, Debug)]
+pub struct ListContainerInstancesRequest {
+    /// The short name or full ARN of the cluster to list container instances in.  If omitted, the
+    /// default cluster is assumed.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    cluster: Option<String>,
+    /// A cluster query language expression used to filter the results.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    filter: Option<String>,
+    /// Only list container instances with this status.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    status: Option<ContainerInstanceStatus>,
+    /// The max number of results returned in paginated output.  Must be between 1 and 100,
+    /// inclusive.  If omitted, defaults to 100.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    maxResults: Option<u8>,
+    /// A value returned by the previous request indicating where to begin the next page of
+    /// paginated output.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    nextToken: Option<String>,
+}
+
+/// A ListContainerInstances response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::list_container_instances().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct ListContainerInstancesResponse {
+    /// The ARNs of the container instances matching the request.
+    #[serde(default)]
+    containerInstanceArns: Vec<String>,
+    /// A value which can be used in a subsequent ListContainerInstancesRequest to obtain the next
+    /// page of paginated output, or None if there are no more results.
+    nextToken: Option<String>,
+}
+
+/// Used so that a ListContainerInstancesRequest can be passed as a generic ECSRequest.
+impl ECSRequest for ListContainerInstancesRequest {}
+
+/// Used so that a ListContainerInstancesResponse can be returned as a generic ECSResponse.
+impl ECSResponse for ListContainerInstancesResponse {}
+
+/// Used so that a ListContainerInstancesRequest can be followed page by page with a paginator::Pages.
+impl PaginatedRequest for ListContainerInstancesRequest {
+    type Response = ListContainerInstancesResponse;
+
+    fn action(&self) -> ECSAction {
+        ECSAction::ListContainerInstances
+    }
+
+    fn set_page_token(&mut self, next_token: String) {
+        self.nextToken = Some(next_token);
+    }
+
+    fn send_page(self, client: &ECSClient) -> ecs_client::Result<ListContainerInstancesResponse> {
+        client.list_container_instances(self)
+    }
+}
+
+/// Used so that the container instance ARNs of a ListContainerInstancesResponse can be yielded by a paginator::Items.
+impl PaginatedResponse for ListContainerInstancesResponse {
+    type Item = String;
+
+    fn page_token(&self) -> Option<String> {
+        self.nextToken.clone()
+    }
+
+    fn into_items(self) -> Vec<String> {
+        self.containerInstanceArns
+    }
+}
+
+/// Implements some convenience methods for building a ListContainerInstancesRequest.
+impl ListContainerInstancesRequest {
+    /// Creates a ListContainerInstancesRequest listing every container instance in the default
+    /// cluster.
+    pub fn new() -> Self {
+        ListContainerInstancesRequest {
+            cluster: None,
+            filter: None,
+            status: None,
+            maxResults: None,
+            nextToken: None,
+        }
+    }
+
+    /// Creates a ListContainerInstancesRequest listing every container instance in the given
+    /// cluster.
+    pub fn for_cluster(cluster: String) -> Self {
+        let mut request = ListContainerInstancesRequest::new();
+        request.cluster = Some(cluster);
+        request
+    }
+
+    /// Sets a cluster query language expression used to filter the results.
+    pub fn set_filter(&mut self, filter: String) {
+        self.filter = Some(filter);
+    }
+
+    /// Only lists container instances with the given status.
+    pub fn set_status(&mut self, status: ContainerInstanceStatus) {
+        self.status = Some(status);
+    }
+
+    /// Sets the max number of results returned in paginated output.
+    pub fn set_max_results(&mut self, max_results: u8) {
+        self.maxResults = Some(max_results);
+    }
+
+    /// Sets the nextToken returned by a previous request.
+    pub fn set_next_token(&mut self, next_token: String) {
+        self.nextToken = Some(next_token);
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// ListContainerInstancesResponse.
+impl ListContainerInstancesResponse {
+    /// Gets a reference to the ARNs of the container instances matching the request.
+    pub fn get_container_instance_arns(&self) -> &Vec<String> {
+        &self.containerInstanceArns
+    }
+
+    /// Gets a reference to the nextToken value returned by the previous request.
+    pub fn get_next_token(&self) -> &Option<String> {
+        &self.nextToken
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ListContainerInstancesRequest, ListContainerInstancesResponse, ContainerInstanceStatus};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_empty_blob() {
+        let req = ListContainerInstancesRequest::new();
+        let expected: value::Value = serde_json::from_str("{}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_full_blob() {
+        let mut req = ListContainerInstancesRequest::for_cluster(String::from("default"));
+        req.set_filter(String::from("attribute:ecs.instance-type == t2.micro"));
+        req.set_status(ContainerInstanceStatus::Draining);
+        req.set_max_results(10);
+        req.set_next_token(String::from("token"));
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\"filter\":\"attribute:ecs.instance-type == t2.micro\",\
+              \"status\":\"DRAINING\",\"maxResults\":10,\"nextToken\":\"token\"}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_deserialize_response() {
+        let resp: ListContainerInstancesResponse = serde_json::from_str(
+            "{\"containerInstanceArns\":[\"arn:aws:ecs:us-west-2:012345678910:container-instance/c8d1b1a2\"]}").unwrap();
+        assert_eq!(1, resp.get_container_instance_arns().len());
+        assert_eq!(&None, resp.get_next_token());
+    }
+}
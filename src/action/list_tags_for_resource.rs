@@ -0,0 +1,70 @@
+//! Defines request and response types for a ListTagsForResource action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::tag::Tag;
+
+/// A ListTagsForResource request type which can be serialized to json and set as the body of an
+/// HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::list_tags_for_resource() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct ListTagsForResourceRequest {
+    /// The Amazon Resource Name (ARN) of the resource to list the tags of.
+    resourceArn: String,
+}
+
+/// A ListTagsForResource response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::list_tags_for_resource().
+#[derive(Deserialize, Debug)]
+pub struct ListTagsForResourceResponse {
+    /// The tags applied to the resource.
+    #[serde(default)]
+    tags: Vec<Tag>,
+}
+
+/// Used so that a ListTagsForResourceRequest can be passed as a generic ECSRequest.
+impl ECSRequest for ListTagsForResourceRequest {}
+
+/// Used so that a ListTagsForResourceResponse can be returned as a generic ECSResponse.
+impl ECSResponse for ListTagsForResourceResponse {}
+
+/// Implements some convenience methods for building a ListTagsForResourceRequest.
+impl ListTagsForResourceRequest {
+    /// Creates a ListTagsForResourceRequest for the given resource.
+    pub fn new(resource_arn: String) -> Self {
+        ListTagsForResourceRequest {
+            resourceArn: resource_arn,
+        }
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// ListTagsForResourceResponse.
+impl ListTagsForResourceResponse {
+    /// Gets a reference to the tags applied to the resource.
+    pub fn get_tags(&self) -> &Vec<Tag> {
+        &self.tags
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ListTagsForResourceResponse;
+    use serde_json;
+
+    #[test]
+    fn test_deserialize_response() {
+        let response: ListTagsForResourceResponse = serde_json::from_str(
+            "{\"tags\":[{\"key\":\"team\",\"value\":\"platform\"},{\"key\":\"archived\"}]}").unwrap();
+        assert_eq!(2, response.get_tags().len());
+        assert_eq!("archived", response.get_tags()[1].get_key());
+        assert_eq!(&None, response.get_tags()[1].get_value());
+    }
+
+    #[test]
+    fn test_deserialize_no_tags() {
+        let response: ListTagsForResourceResponse = serde_json::from_str("{}").unwrap();
+        assert!(response.get_tags().is_empty());
+    }
+}
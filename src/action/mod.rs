@@ -2,3 +2,326 @@
 
 pub mod ecs_action;
 pub mod list_clusters;
+pub mod register_container_instance;
+pub mod submit_task_state_change;
+pub mod submit_container_state_change;
+pub mod discover_poll_endpoint;
+pub mod tag_resource;
+pub mod untag_resource;
+pub mod list_tags_for_resource;
+pub mod put_attributes;
+pub mod delete_attributes;
+pub mod list_attributes;
+pub mod create_capacity_provider;
+pub mod update_capacity_provider;
+pub mod delete_capacity_provider;
+pub mod describe_capacity_providers;
+pub mod put_cluster_capacity_providers;
+pub mod put_account_setting;
+pub mod put_account_setting_default;
+pub mod list_account_settings;
+pub mod delete_account_setting;
+pub mod create_task_set;
+pub mod update_task_set;
+pub mod delete_task_set;
+pub mod describe_task_sets;
+pub mod update_service_primary_task_set;
+pub mod execute_command;
+pub mod update_cluster;
+pub mod update_cluster_settings;
+pub mod update_container_agent;
+pub mod list_container_instances;
+pub mod describe_container_instances;
+pub mod describe_services;
+pub mod describe_tasks;
+pub mod create_cluster;
+pub mod create_service;
+pub mod register_task_definition;
+pub mod run_task;
+
+/// Calls the given macro with the table of the actions the clients have a method for, so that
+/// ECSClient and PooledECSClient define their per-action methods from a single list.
+///
+/// Each action is listed with its doc comment, its ECSAction variant, the names of its plain and
+/// *_with_metadata methods, and its request and response types.  The paginated actions are
+/// listed again with the doc comment of their *_all method and the names of their *_pages and
+/// *_all methods.
+macro_rules! ecs_actions {
+    ($define:ident) => {
+        $define! {
+            actions {
+                /// Lists all of your compute clusters on ECS.
+                ListClusters:
+                    list_clusters, list_clusters_with_metadata,
+                    list_clusters::ListClustersRequest,
+                    list_clusters::ListClustersResponse;
+
+                /// Registers a container instance with a cluster.  This action is intended for use
+                /// by a container agent running on the instance being registered.
+                RegisterContainerInstance:
+                    register_container_instance, register_container_instance_with_metadata,
+                    register_container_instance::RegisterContainerInstanceRequest,
+                    register_container_instance::RegisterContainerInstanceResponse;
+
+                /// Reports to ECS that a task has changed state.  This action is intended for use
+                /// by a container agent.
+                SubmitTaskStateChange:
+                    submit_task_state_change, submit_task_state_change_with_metadata,
+                    submit_task_state_change::SubmitTaskStateChangeRequest,
+                    submit_task_state_change::SubmitTaskStateChangeResponse;
+
+                /// Reports to ECS that a container has changed state.  This action is intended for
+                /// use by a container agent.
+                SubmitContainerStateChange:
+                    submit_container_state_change, submit_container_state_change_with_metadata,
+                    submit_container_state_change::SubmitContainerStateChangeRequest,
+                    submit_container_state_change::SubmitContainerStateChangeResponse;
+
+                /// Gets the endpoints a container agent should poll for work and send telemetry to.
+                /// This action is intended for use by a container agent.
+                DiscoverPollEndpoint:
+                    discover_poll_endpoint, discover_poll_endpoint_with_metadata,
+                    discover_poll_endpoint::DiscoverPollEndpointRequest,
+                    discover_poll_endpoint::DiscoverPollEndpointResponse;
+
+                /// Adds the specified tags to the ECS resource with the given ARN.
+                TagResource:
+                    tag_resource, tag_resource_with_metadata,
+                    tag_resource::TagResourceRequest,
+                    tag_resource::TagResourceResponse;
+
+                /// Removes the tags with the specified keys from the ECS resource with the given
+                /// ARN.
+                UntagResource:
+                    untag_resource, untag_resource_with_metadata,
+                    untag_resource::UntagResourceRequest,
+                    untag_resource::UntagResourceResponse;
+
+                /// Lists the tags applied to the ECS resource with the given ARN.
+                ListTagsForResource:
+                    list_tags_for_resource, list_tags_for_resource_with_metadata,
+                    list_tags_for_resource::ListTagsForResourceRequest,
+                    list_tags_for_resource::ListTagsForResourceResponse;
+
+                /// Creates or updates attributes on ECS resources, such as container instances.
+                PutAttributes:
+                    put_attributes, put_attributes_with_metadata,
+                    put_attributes::PutAttributesRequest,
+                    put_attributes::PutAttributesResponse;
+
+                /// Deletes attributes from ECS resources.
+                DeleteAttributes:
+                    delete_attributes, delete_attributes_with_metadata,
+                    delete_attributes::DeleteAttributesRequest,
+                    delete_attributes::DeleteAttributesResponse;
+
+                /// Lists the attributes of ECS resources of a given type.
+                ListAttributes:
+                    list_attributes, list_attributes_with_metadata,
+                    list_attributes::ListAttributesRequest,
+                    list_attributes::ListAttributesResponse;
+
+                /// Creates a capacity provider backed by an Auto Scaling group.
+                CreateCapacityProvider:
+                    create_capacity_provider, create_capacity_provider_with_metadata,
+                    create_capacity_provider::CreateCapacityProviderRequest,
+                    create_capacity_provider::CreateCapacityProviderResponse;
+
+                /// Updates the Auto Scaling group settings of a capacity provider.
+                UpdateCapacityProvider:
+                    update_capacity_provider, update_capacity_provider_with_metadata,
+                    update_capacity_provider::UpdateCapacityProviderRequest,
+                    update_capacity_provider::UpdateCapacityProviderResponse;
+
+                /// Deletes a capacity provider which is not associated with any cluster.
+                DeleteCapacityProvider:
+                    delete_capacity_provider, delete_capacity_provider_with_metadata,
+                    delete_capacity_provider::DeleteCapacityProviderRequest,
+                    delete_capacity_provider::DeleteCapacityProviderResponse;
+
+                /// Describes one or more of your capacity providers.
+                DescribeCapacityProviders:
+                    describe_capacity_providers, describe_capacity_providers_with_metadata,
+                    describe_capacity_providers::DescribeCapacityProvidersRequest,
+                    describe_capacity_providers::DescribeCapacityProvidersResponse;
+
+                /// Sets the capacity providers and default capacity provider strategy of a cluster.
+                PutClusterCapacityProviders:
+                    put_cluster_capacity_providers, put_cluster_capacity_providers_with_metadata,
+                    put_cluster_capacity_providers::PutClusterCapacityProvidersRequest,
+                    put_cluster_capacity_providers::PutClusterCapacityProvidersResponse;
+
+                /// Modifies an account setting for the authenticated user or a given principal.
+                PutAccountSetting:
+                    put_account_setting, put_account_setting_with_metadata,
+                    put_account_setting::PutAccountSettingRequest,
+                    put_account_setting::PutAccountSettingResponse;
+
+                /// Modifies the default value of an account setting for all principals in the
+                /// account.
+                PutAccountSettingDefault:
+                    put_account_setting_default, put_account_setting_default_with_metadata,
+                    put_account_setting_default::PutAccountSettingDefaultRequest,
+                    put_account_setting_default::PutAccountSettingDefaultResponse;
+
+                /// Lists the account settings of the authenticated user or a given principal.
+                ListAccountSettings:
+                    list_account_settings, list_account_settings_with_metadata,
+                    list_account_settings::ListAccountSettingsRequest,
+                    list_account_settings::ListAccountSettingsResponse;
+
+                /// Resets an account setting of the authenticated user or a given principal to the
+                /// account default.
+                DeleteAccountSetting:
+                    delete_account_setting, delete_account_setting_with_metadata,
+                    delete_account_setting::DeleteAccountSettingRequest,
+                    delete_account_setting::DeleteAccountSettingResponse;
+
+                /// Creates a task set in a service using the EXTERNAL deployment controller.
+                CreateTaskSet:
+                    create_task_set, create_task_set_with_metadata,
+                    create_task_set::CreateTaskSetRequest,
+                    create_task_set::CreateTaskSetResponse;
+
+                /// Changes the scale of a task set in a service using the EXTERNAL deployment
+                /// controller.
+                UpdateTaskSet:
+                    update_task_set, update_task_set_with_metadata,
+                    update_task_set::UpdateTaskSetRequest,
+                    update_task_set::UpdateTaskSetResponse;
+
+                /// Deletes a task set from a service using the EXTERNAL deployment controller.
+                DeleteTaskSet:
+                    delete_task_set, delete_task_set_with_metadata,
+                    delete_task_set::DeleteTaskSetRequest,
+                    delete_task_set::DeleteTaskSetResponse;
+
+                /// Describes the task sets of a service using the EXTERNAL deployment controller.
+                DescribeTaskSets:
+                    describe_task_sets, describe_task_sets_with_metadata,
+                    describe_task_sets::DescribeTaskSetsRequest,
+                    describe_task_sets::DescribeTaskSetsResponse;
+
+                /// Makes a task set the primary task set of its service, so that it serves
+                /// production traffic.
+                UpdateServicePrimaryTaskSet:
+                    update_service_primary_task_set, update_service_primary_task_set_with_metadata,
+                    update_service_primary_task_set::UpdateServicePrimaryTaskSetRequest,
+                    update_service_primary_task_set::UpdateServicePrimaryTaskSetResponse;
+
+                /// Runs a command in a running container.  Use data_channel::DataChannel::open()
+                /// with the returned Session to talk to the command.
+                ExecuteCommand:
+                    execute_command, execute_command_with_metadata,
+                    execute_command::ExecuteCommandRequest,
+                    execute_command::ExecuteCommandResponse;
+
+                /// Updates the settings and configuration of a cluster.
+                UpdateCluster:
+                    update_cluster, update_cluster_with_metadata,
+                    update_cluster::UpdateClusterRequest,
+                    update_cluster::UpdateClusterResponse;
+
+                /// Updates the settings of a cluster, such as whether Container Insights is turned
+                /// on.
+                UpdateClusterSettings:
+                    update_cluster_settings, update_cluster_settings_with_metadata,
+                    update_cluster_settings::UpdateClusterSettingsRequest,
+                    update_cluster_settings::UpdateClusterSettingsResponse;
+
+                /// Updates the container agent on a container instance to the latest version.  The
+                /// update runs in the background; follow its progress in the agentUpdateStatus of
+                /// the instance.
+                UpdateContainerAgent:
+                    update_container_agent, update_container_agent_with_metadata,
+                    update_container_agent::UpdateContainerAgentRequest,
+                    update_container_agent::UpdateContainerAgentResponse;
+
+                /// Lists the container instances registered with a cluster.
+                ListContainerInstances:
+                    list_container_instances, list_container_instances_with_metadata,
+                    list_container_instances::ListContainerInstancesRequest,
+                    list_container_instances::ListContainerInstancesResponse;
+
+                /// Describes container instances, including their resources, agent version and
+                /// status.
+                DescribeContainerInstances:
+                    describe_container_instances, describe_container_instances_with_metadata,
+                    describe_container_instances::DescribeContainerInstancesRequest,
+                    describe_container_instances::DescribeContainerInstancesResponse;
+
+                /// Describes services, including their deployments and running and desired task
+                /// counts.
+                DescribeServices:
+                    describe_services, describe_services_with_metadata,
+                    describe_services::DescribeServicesRequest,
+                    describe_services::DescribeServicesResponse;
+
+                /// Describes tasks, including their containers and last and desired statuses.
+                DescribeTasks:
+                    describe_tasks, describe_tasks_with_metadata,
+                    describe_tasks::DescribeTasksRequest,
+                    describe_tasks::DescribeTasksResponse;
+
+                /// Creates a cluster to run tasks and services on.
+                CreateCluster:
+                    create_cluster, create_cluster_with_metadata,
+                    create_cluster::CreateClusterRequest,
+                    create_cluster::CreateClusterResponse;
+
+                /// Creates a service which keeps the desired count of a task definition's tasks
+                /// running.
+                CreateService:
+                    create_service, create_service_with_metadata,
+                    create_service::CreateServiceRequest,
+                    create_service::CreateServiceResponse;
+
+                /// Registers a new revision of a task definition family.
+                RegisterTaskDefinition:
+                    register_task_definition, register_task_definition_with_metadata,
+                    register_task_definition::RegisterTaskDefinitionRequest,
+                    register_task_definition::RegisterTaskDefinitionResponse;
+
+                /// Starts new tasks from a task definition, placed by ECS.
+                RunTask:
+                    run_task, run_task_with_metadata,
+                    run_task::RunTaskRequest,
+                    run_task::RunTaskResponse;
+            }
+            paginated {
+                /// Returns an iterator over the cluster ARNs of every page of ListClusters output,
+                /// capped at paginator::DEFAULT_MAX_ITEMS unless changed with set_max_items().
+                list_clusters_pages, list_clusters_all,
+                    list_clusters::ListClustersRequest,
+                    list_clusters::ListClustersResponse;
+
+                /// Returns an iterator over the attributes of every page of ListAttributes output,
+                /// capped at paginator::DEFAULT_MAX_ITEMS unless changed with set_max_items().
+                list_attributes_pages, list_attributes_all,
+                    list_attributes::ListAttributesRequest,
+                    list_attributes::ListAttributesResponse;
+
+                /// Returns an iterator over the capacity providers of every page of
+                /// DescribeCapacityProviders output, capped at paginator::DEFAULT_MAX_ITEMS unless
+                /// changed with set_max_items().
+                describe_capacity_providers_pages, describe_capacity_providers_all,
+                    describe_capacity_providers::DescribeCapacityProvidersRequest,
+                    describe_capacity_providers::DescribeCapacityProvidersResponse;
+
+                /// Returns an iterator over the settings of every page of ListAccountSettings
+                /// output, capped at paginator::DEFAULT_MAX_ITEMS unless changed with
+                /// set_max_items().
+                list_account_settings_pages, list_account_settings_all,
+                    list_account_settings::ListAccountSettingsRequest,
+                    list_account_settings::ListAccountSettingsResponse;
+
+                /// Returns an iterator over the container instance ARNs of every page of
+                /// ListContainerInstances output, capped at paginator::DEFAULT_MAX_ITEMS unless
+                /// changed with set_max_items().
+                list_container_instances_pages, list_container_instances_all,
+                    list_container_instances::ListContainerInstancesRequest,
+                    list_container_instances::ListContainerInstancesResponse;
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Defines request and response types for a PutAccountSetting action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::setting::{Setting, SettingName};
+use custom_ser;
+
+/// A PutAccountSetting request type which can be serialized to json and set as the body of an
+/// HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::put_account_setting() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct PutAccountSettingRequest {
+    /// The name of the account setting to modify.
+    name: SettingName,
+    /// The new value of the account setting.
+    value: String,
+    /// The ARN of the IAM user, role or root user to modify the setting for.  If omitted, the
+    /// setting is modified for the authenticated user.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    principalArn: Option<String>,
+}
+
+/// A PutAccountSetting response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::put_account_setting().
+#[derive(Deserialize, Debug)]
+pub struct PutAccountSettingResponse {
+    /// The account setting as it was applied.
+    setting: Setting,
+}
+
+/// Used so that a PutAccountSettingRequest can be passed as a generic ECSRequest.
+impl ECSRequest for PutAccountSettingRequest {}
+
+/// Used so that a PutAccountSettingResponse can be returned as a generic ECSResponse.
+impl ECSResponse for PutAccountSettingResponse {}
+
+/// Implements some convenience methods for building a PutAccountSettingRequest.
+impl PutAccountSettingRequest {
+    /// Creates a PutAccountSettingRequest for the authenticated user.
+    pub fn new(name: SettingName, value: String) -> Self {
+        PutAccountSettingRequest {
+            name: name,
+            value: value,
+            principalArn: None,
+        }
+    }
+
+    /// Creates a PutAccountSettingRequest for the given principal.
+    pub fn for_principal(name: SettingName, value: String, principal_arn: String) -> Self {
+        PutAccountSettingRequest {
+            name: name,
+            value: value,
+            principalArn: Some(principal_arn),
+        }
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// PutAccountSettingResponse.
+impl PutAccountSettingResponse {
+    /// Gets a reference to the account setting as it was applied.
+    pub fn get_setting(&self) -> &Setting {
+        &self.setting
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PutAccountSettingRequest;
+    use types::setting::{SettingName, ENABLED};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let req = PutAccountSettingRequest::for_principal(SettingName::TaskLongArnFormat, String::from(ENABLED),
+                                                          String::from("arn:aws:iam::012345678910:role/deploy"));
+        let expected: value::Value = serde_json::from_str(
+            "{\"name\":\"taskLongArnFormat\",\"value\":\"enabled\",\
+              \"principalArn\":\"arn:aws:iam::012345678910:role/deploy\"}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,68 @@
+//! Defines request and response types for a PutAccountSettingDefault action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::setting::{Setting, SettingName};
+
+/// A PutAccountSettingDefault request type which can be serialized to json and set as the body of
+/// an HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::put_account_setting_default() function.
+#[derive(Serialize, Debug)]
+pub struct PutAccountSettingDefaultRequest {
+    /// The name of the account setting to modify.
+    name: SettingName,
+    /// The new default value of the account setting for all principals without an explicit
+    /// override.
+    value: String,
+}
+
+/// A PutAccountSettingDefault response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::put_account_setting_default().
+#[derive(Deserialize, Debug)]
+pub struct PutAccountSettingDefaultResponse {
+    /// The account setting default as it was applied.
+    setting: Setting,
+}
+
+/// Used so that a PutAccountSettingDefaultRequest can be passed as a generic ECSRequest.
+impl ECSRequest for PutAccountSettingDefaultRequest {}
+
+/// Used so that a PutAccountSettingDefaultResponse can be returned as a generic ECSResponse.
+impl ECSResponse for PutAccountSettingDefaultResponse {}
+
+/// Implements some convenience methods for building a PutAccountSettingDefaultRequest.
+impl PutAccountSettingDefaultRequest {
+    /// Creates a PutAccountSettingDefaultRequest.
+    pub fn new(name: SettingName, value: String) -> Self {
+        PutAccountSettingDefaultRequest {
+            name: name,
+            value: value,
+        }
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// PutAccountSettingDefaultResponse.
+impl PutAccountSettingDefaultResponse {
+    /// Gets a reference to the account setting default as it was applied.
+    pub fn get_setting(&self) -> &Setting {
+        &self.setting
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PutAccountSettingDefaultRequest;
+    use types::setting::{SettingName, DISABLED};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let req = PutAccountSettingDefaultRequest::new(SettingName::AwsvpcTrunking, String::from(DISABLED));
+        let expected: value::Value = serde_json::from_str(
+            "{\"name\":\"awsvpcTrunking\",\"value\":\"disabled\"}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+}
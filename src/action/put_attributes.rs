@@ -0,0 +1,118 @@
+//! Defines request and response types for a PutAttributes action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::attribute::Attribute;
+use error::ValidationError;
+use custom_ser;
+
+/// The maximum number of attributes which can be put or deleted in a single request.
+pub const MAX_ATTRIBUTES_PER_REQUEST: usize = 10;
+
+/// A PutAttributes request type which can be serialized to json and set as the body of an HTTP
+/// request.  Construct one of these and pass it to your client in the
+/// ecs_client::put_attributes() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct PutAttributesRequest {
+    /// The short name or full ARN of the cluster containing the attribute targets.  If omitted,
+    /// the default cluster is assumed.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    cluster: Option<String>,
+    /// The attributes to create or update.  Each attribute must have a target.
+    attributes: Vec<Attribute>,
+}
+
+/// A PutAttributes response type which can be deserialized from the body of an HTTP response.  If
+/// the request was successful, one of these will be returned from ecs_client::put_attributes().
+#[derive(Deserialize, Debug)]
+pub struct PutAttributesResponse {
+    /// The attributes which were applied.
+    #[serde(default)]
+    attributes: Vec<Attribute>,
+}
+
+/// Used so that a PutAttributesRequest can be passed as a generic ECSRequest.
+impl ECSRequest for PutAttributesRequest {}
+
+/// Used so that a PutAttributesResponse can be returned as a generic ECSResponse.
+impl ECSResponse for PutAttributesResponse {}
+
+/// Implements some convenience methods for building a PutAttributesRequest.
+impl PutAttributesRequest {
+    /// Creates a PutAttributesRequest for attributes in the default cluster.
+    pub fn new(attributes: Vec<Attribute>) -> Result<Self, ValidationError> {
+        try!(validate_attribute_targets(&attributes));
+        Ok(PutAttributesRequest {
+            cluster: None,
+            attributes: attributes,
+        })
+    }
+
+    /// Creates a PutAttributesRequest for attributes in the given cluster.
+    pub fn for_cluster(cluster: String, attributes: Vec<Attribute>) -> Result<Self, ValidationError> {
+        let mut request = try!(PutAttributesRequest::new(attributes));
+        request.cluster = Some(cluster);
+        Ok(request)
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a PutAttributesResponse.
+impl PutAttributesResponse {
+    /// Gets a reference to the attributes which were applied.
+    pub fn get_attributes(&self) -> &Vec<Attribute> {
+        &self.attributes
+    }
+}
+
+/// Checks that a PutAttributes or DeleteAttributes request carries between 1 and 10 attributes,
+/// each of which names the resource it applies to.
+pub fn validate_attribute_targets(attributes: &[Attribute]) -> Result<(), ValidationError> {
+    if attributes.is_empty() || attributes.len() > MAX_ATTRIBUTES_PER_REQUEST {
+        return Err(ValidationError::new("attributes",
+                                        format!("must contain between 1 and {} attributes, got {}",
+                                                MAX_ATTRIBUTES_PER_REQUEST, attributes.len())));
+    }
+    for attribute in attributes {
+        if attribute.get_target_id().is_none() {
+            return Err(ValidationError::new("attributes",
+                                            format!("attribute {} has no targetId", attribute.get_name())));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::PutAttributesRequest;
+    use types::attribute::{Attribute, TargetType};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let attribute = Attribute::with_target(String::from("stack"), Some(String::from("prod")),
+                                               TargetType::ContainerInstance, String::from("arn")).unwrap();
+        let req = PutAttributesRequest::for_cluster(String::from("default"), vec![attribute]).unwrap();
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\"attributes\":[{\"name\":\"stack\",\"value\":\"prod\",\
+              \"targetType\":\"container-instance\",\"targetId\":\"arn\"}]}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_attribute_without_target() {
+        let attribute = Attribute::new(String::from("stack"), None).unwrap();
+        assert!(PutAttributesRequest::new(vec![attribute]).is_err());
+    }
+
+    #[test]
+    fn test_attribute_count() {
+        assert!(PutAttributesRequest::new(Vec::new()).is_err());
+        let attributes: Vec<Attribute> = (0..11).map(|i| {
+            Attribute::with_target(format!("name{}", i), None, TargetType::ContainerInstance,
+                                   String::from("arn")).unwrap()
+        }).collect();
+        assert!(PutAttributesRequest::new(attributes).is_err());
+    }
+}
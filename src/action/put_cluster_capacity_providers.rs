@@ -0,0 +1,98 @@
+//! Defines request and response types for a PutClusterCapacityProviders action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::capacity_provider::{self, CapacityProviderStrategyItem};
+use types::cluster::Cluster;
+use error::ValidationError;
+
+/// A PutClusterCapacityProviders request type which can be serialized to json and set as the body
+/// of an HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::put_cluster_capacity_providers() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct PutClusterCapacityProvidersRequest {
+    /// The short name or full ARN of the cluster.
+    cluster: String,
+    /// The names of the capacity providers to associate with the cluster.  This replaces any
+    /// existing association, so an empty list removes all capacity providers.
+    capacityProviders: Vec<String>,
+    /// The capacity provider strategy to use by default for the cluster.
+    defaultCapacityProviderStrategy: Vec<CapacityProviderStrategyItem>,
+}
+
+/// A PutClusterCapacityProviders response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::put_cluster_capacity_providers().
+#[derive(Deserialize, Debug)]
+pub struct PutClusterCapacityProvidersResponse {
+    /// The cluster with its new capacity providers.
+    cluster: Cluster,
+}
+
+/// Used so that a PutClusterCapacityProvidersRequest can be passed as a generic ECSRequest.
+impl ECSRequest for PutClusterCapacityProvidersRequest {}
+
+/// Used so that a PutClusterCapacityProvidersResponse can be returned as a generic ECSResponse.
+impl ECSResponse for PutClusterCapacityProvidersResponse {}
+
+/// Implements some convenience methods for building a PutClusterCapacityProvidersRequest.
+impl PutClusterCapacityProvidersRequest {
+    /// Creates a PutClusterCapacityProvidersRequest, or returns a ValidationError if the default
+    /// strategy is malformed or refers to a capacity provider not being associated with the
+    /// cluster.
+    pub fn new(cluster: String,
+               capacity_providers: Vec<String>,
+               default_strategy: Vec<CapacityProviderStrategyItem>) -> Result<Self, ValidationError> {
+        try!(capacity_provider::validate_strategy(&default_strategy));
+        for item in &default_strategy {
+            if !capacity_providers.contains(item.get_capacity_provider()) {
+                return Err(ValidationError::new("defaultCapacityProviderStrategy",
+                                                format!("{} is not one of the cluster's capacity providers",
+                                                        item.get_capacity_provider())));
+            }
+        }
+        Ok(PutClusterCapacityProvidersRequest {
+            cluster: cluster,
+            capacityProviders: capacity_providers,
+            defaultCapacityProviderStrategy: default_strategy,
+        })
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// PutClusterCapacityProvidersResponse.
+impl PutClusterCapacityProvidersResponse {
+    /// Gets a reference to the updated cluster.
+    pub fn get_cluster(&self) -> &Cluster {
+        &self.cluster
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PutClusterCapacityProvidersRequest;
+    use types::capacity_provider::{CapacityProviderStrategyItem, FARGATE, FARGATE_SPOT};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let strategy = vec![CapacityProviderStrategyItem::new(String::from(FARGATE), 1, 1).unwrap(),
+                            CapacityProviderStrategyItem::with_weight(String::from(FARGATE_SPOT), 4).unwrap()];
+        let req = PutClusterCapacityProvidersRequest::new(
+            String::from("default"), vec![String::from(FARGATE), String::from(FARGATE_SPOT)], strategy).unwrap();
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\"capacityProviders\":[\"FARGATE\",\"FARGATE_SPOT\"],\
+              \"defaultCapacityProviderStrategy\":[{\"capacityProvider\":\"FARGATE\",\"weight\":1,\"base\":1},\
+                                                   {\"capacityProvider\":\"FARGATE_SPOT\",\"weight\":4}]}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_strategy_outside_providers() {
+        let strategy = vec![CapacityProviderStrategyItem::with_weight(String::from(FARGATE_SPOT), 1).unwrap()];
+        assert!(PutClusterCapacityProvidersRequest::new(
+            String::from("default"), vec![String::from(FARGATE)], strategy).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+//! Defines request and response types for a RegisterContainerInstance action.  This action is
+//! used by the container agent running on an instance to join a cluster, and is not normally
+//! called by control-plane clients.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::container_instance::{ContainerInstance, Resource, VersionInfo};
+use types::attribute::Attribute;
+use types::tag::{self, Tag};
+use error::ValidationError;
+use custom_ser;
+
+/// A RegisterContainerInstance request type which can be serialized to json and set as the body
+/// of an HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::register_container_instance() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct RegisterContainerInstanceRequest {
+    /// The short name or full ARN of the cluster to register with.  If omitted, the default
+    /// cluster is assumed.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    cluster: Option<String>,
+    /// The instance identity document for the EC2 instance, as returned by the instance metadata
+    /// service at /latest/dynamic/instance-identity/document.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    instanceIdentityDocument: Option<String>,
+    /// The signature of the instance identity document, as returned by the instance metadata
+    /// service at /latest/dynamic/instance-identity/signature.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    instanceIdentityDocumentSignature: Option<String>,
+    /// The resources available on the instance.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    totalResources: Vec<Resource>,
+    /// The versions of the container agent and Docker running on the instance.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    versionInfo: Option<VersionInfo>,
+    /// The ARN of a container instance which is re-registering, e.g. after an agent restart.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    containerInstanceArn: Option<String>,
+    /// The custom attributes to apply to the container instance.  Their targets are implied by
+    /// the registration and should be left unset.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    attributes: Vec<Attribute>,
+    /// The tags to apply to the container instance.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    tags: Vec<Tag>,
+}
+
+/// A RegisterContainerInstance response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::register_container_instance().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct RegisterContainerInstanceResponse {
+    /// The container instance which was registered.
+    containerInstance: ContainerInstance,
+}
+
+/// Used so that a RegisterContainerInstanceRequest can be passed as a generic ECSRequest.
+impl ECSRequest for RegisterContainerInstanceRequest {}
+
+/// Used so that a RegisterContainerInstanceResponse can be returned as a generic ECSResponse.
+impl ECSResponse for RegisterContainerInstanceResponse {}
+
+/// Implements some convenience methods for building a RegisterContainerInstanceRequest.
+impl RegisterContainerInstanceRequest {
+    /// Creates a default RegisterContainerInstanceRequest with no field values.
+    pub fn new() -> Self {
+        RegisterContainerInstanceRequest {
+            cluster: None,
+            instanceIdentityDocument: None,
+            instanceIdentityDocumentSignature: None,
+            totalResources: Vec::new(),
+            versionInfo: None,
+            containerInstanceArn: None,
+            attributes: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    /// Creates a RegisterContainerInstanceRequest for the given cluster, identified by the
+    /// instance identity document and its signature.
+    pub fn with_identity(cluster: String, document: String, signature: String) -> Self {
+        let mut request = RegisterContainerInstanceRequest::new();
+        request.cluster = Some(cluster);
+        request.instanceIdentityDocument = Some(document);
+        request.instanceIdentityDocumentSignature = Some(signature);
+        request
+    }
+
+    /// Sets the cluster to register with.
+    pub fn set_cluster(&mut self, cluster: String) {
+        self.cluster = Some(cluster);
+    }
+
+    /// Sets the instance identity document and its signature.
+    pub fn set_instance_identity(&mut self, document: String, signature: String) {
+        self.instanceIdentityDocument = Some(document);
+        self.instanceIdentityDocumentSignature = Some(signature);
+    }
+
+    /// Adds a resource to the total resources available on the instance.
+    pub fn add_total_resource(&mut self, resource: Resource) {
+        self.totalResources.push(resource);
+    }
+
+    /// Sets the container agent and Docker versions running on the instance.
+    pub fn set_version_info(&mut self, version_info: VersionInfo) {
+        self.versionInfo = Some(version_info);
+    }
+
+    /// Sets the ARN of a container instance which is re-registering.
+    pub fn set_container_instance_arn(&mut self, container_instance_arn: String) {
+        self.containerInstanceArn = Some(container_instance_arn);
+    }
+
+    /// Adds a custom attribute to apply to the container instance.
+    pub fn add_attribute(&mut self, attribute: Attribute) {
+        self.attributes.push(attribute);
+    }
+
+    /// Sets the tags to apply to the container instance, or returns a ValidationError if there
+    /// are more tags than a resource can hold.
+    pub fn set_tags(&mut self, tags: Vec<Tag>) -> Result<(), ValidationError> {
+        try!(tag::validate_tags(&tags));
+        self.tags = tags;
+        Ok(())
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// RegisterContainerInstanceResponse.
+impl RegisterContainerInstanceResponse {
+    /// Gets a reference to the container instance which was registered.
+    pub fn get_container_instance(&self) -> &ContainerInstance {
+        &self.containerInstance
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RegisterContainerInstanceRequest;
+    use types::container_instance::{Resource, VersionInfo};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let mut req = RegisterContainerInstanceRequest::with_identity(
+            String::from("default"), String::from("{\"instanceId\":\"i-1\"}"), String::from("sig"));
+        req.add_total_resource(Resource::integer(String::from("CPU"), 2048));
+        req.set_version_info(VersionInfo::new(String::from("1.9.0"), String::from("4023248"),
+                                              String::from("DockerVersion: 1.9.1")));
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\
+              \"instanceIdentityDocument\":\"{\\\"instanceId\\\":\\\"i-1\\\"}\",\
+              \"instanceIdentityDocumentSignature\":\"sig\",\
+              \"totalResources\":[{\"name\":\"CPU\",\"type\":\"INTEGER\",\"integerValue\":2048}],\
+              \"versionInfo\":{\"agentVersion\":\"1.9.0\",\"agentHash\":\"4023248\",\
+                               \"dockerVersion\":\"DockerVersion: 1.9.1\"}}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_empty_blob() {
+        let req = RegisterContainerInstanceRequest::new();
+        let expected: value::Value = serde_json::from_str("{}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,163 @@
+//! Defines request and response types for a RegisterTaskDefinition action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::launch_type::LaunchType;
+use types::tag::{self, Tag};
+use types::task_definition::{ContainerDefinition, NetworkMode, TaskDefinition};
+use error::ValidationError;
+use custom_ser;
+
+/// A RegisterTaskDefinition request type which can be serialized to json and set as the body of
+/// an HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::register_task_definition() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct RegisterTaskDefinitionRequest {
+    /// The family to register a new revision of.
+    family: String,
+    /// The containers of the task definition.
+    containerDefinitions: Vec<ContainerDefinition>,
+    /// The Docker networking mode the containers use.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    networkMode: Option<NetworkMode>,
+    /// The ARN of the IAM role the containers can assume.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    taskRoleArn: Option<String>,
+    /// The ARN of the IAM role the container agent uses to pull images and publish logs.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    executionRoleArn: Option<String>,
+    /// The number of CPU units used by the task, e.g. "256" or "1 vCPU".
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    cpu: Option<String>,
+    /// The amount of memory used by the task, e.g. "512" or "1 GB".
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    memory: Option<String>,
+    /// The launch types to validate the task definition against.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    requiresCompatibilities: Vec<LaunchType>,
+    /// The tags to apply to the task definition.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    tags: Vec<Tag>,
+}
+
+/// A RegisterTaskDefinition response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::register_task_definition().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct RegisterTaskDefinitionResponse {
+    /// The task definition revision which was registered.
+    taskDefinition: TaskDefinition,
+    /// The tags applied to the task definition.
+    tags: Option<Vec<Tag>>,
+}
+
+/// Used so that a RegisterTaskDefinitionRequest can be passed as a generic ECSRequest.
+impl ECSRequest for RegisterTaskDefinitionRequest {}
+
+/// Used so that a RegisterTaskDefinitionResponse can be returned as a generic ECSResponse.
+impl ECSResponse for RegisterTaskDefinitionResponse {}
+
+/// Implements some convenience methods for building a RegisterTaskDefinitionRequest.
+impl RegisterTaskDefinitionRequest {
+    /// Creates a RegisterTaskDefinitionRequest registering a new revision of a family.
+    pub fn new(family: String, container_definitions: Vec<ContainerDefinition>) -> Self {
+        RegisterTaskDefinitionRequest {
+            family: family,
+            containerDefinitions: container_definitions,
+            networkMode: None,
+            taskRoleArn: None,
+            executionRoleArn: None,
+            cpu: None,
+            memory: None,
+            requiresCompatibilities: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    /// Sets the Docker networking mode the containers use.
+    pub fn set_network_mode(&mut self, network_mode: NetworkMode) {
+        self.networkMode = Some(network_mode);
+    }
+
+    /// Sets the ARN of the IAM role the containers can assume.
+    pub fn set_task_role_arn(&mut self, task_role_arn: String) {
+        self.taskRoleArn = Some(task_role_arn);
+    }
+
+    /// Sets the ARN of the IAM role the container agent uses.
+    pub fn set_execution_role_arn(&mut self, execution_role_arn: String) {
+        self.executionRoleArn = Some(execution_role_arn);
+    }
+
+    /// Sets the number of CPU units used by the task.
+    pub fn set_cpu(&mut self, cpu: String) {
+        self.cpu = Some(cpu);
+    }
+
+    /// Sets the amount of memory used by the task.
+    pub fn set_memory(&mut self, memory: String) {
+        self.memory = Some(memory);
+    }
+
+    /// Adds a launch type to validate the task definition against.
+    pub fn add_requires_compatibility(&mut self, launch_type: LaunchType) {
+        self.requiresCompatibilities.push(launch_type);
+    }
+
+    /// Sets the tags to apply to the task definition, or returns a ValidationError if there are
+    /// more tags than a resource can hold.
+    pub fn set_tags(&mut self, tags: Vec<Tag>) -> Result<(), ValidationError> {
+        try!(tag::validate_tags(&tags));
+        self.tags = tags;
+        Ok(())
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// RegisterTaskDefinitionResponse.
+impl RegisterTaskDefinitionResponse {
+    /// Gets a reference to the task definition revision which was registered.
+    pub fn get_task_definition(&self) -> &TaskDefinition {
+        &self.taskDefinition
+    }
+
+    /// Gets the tags applied to the task definition.
+    pub fn get_tags(&self) -> &Option<Vec<Tag>> {
+        &self.tags
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RegisterTaskDefinitionRequest;
+    use types::launch_type::LaunchType;
+    use types::tag::Tag;
+    use types::task_definition::{ContainerDefinition, NetworkMode, PortMapping};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let mut container = ContainerDefinition::new(String::from("nginx"), String::from("nginx:latest"));
+        container.set_essential(true);
+        container.add_port_mapping(PortMapping::new(80));
+        container.add_environment(String::from("MODE"), String::from("prod"));
+        let mut req = RegisterTaskDefinitionRequest::new(String::from("web"), vec![container]);
+        req.set_network_mode(NetworkMode::AwsVpc);
+        req.set_cpu(String::from("256"));
+        req.set_memory(String::from("512"));
+        req.add_requires_compatibility(LaunchType::Fargate);
+        req.set_tags(vec![Tag::new(String::from("team"), String::from("platform")).unwrap()]).unwrap();
+        let expected: value::Value = serde_json::from_str(
+            "{\"family\":\"web\",\
+              \"containerDefinitions\":[{\"name\":\"nginx\",\"image\":\"nginx:latest\",\"essential\":true,\
+                                         \"portMappings\":[{\"containerPort\":80}],\
+                                         \"environment\":[{\"name\":\"MODE\",\"value\":\"prod\"}]}],\
+              \"networkMode\":\"awsvpc\",\"cpu\":\"256\",\"memory\":\"512\",\
+              \"requiresCompatibilities\":[\"FARGATE\"],\
+              \"tags\":[{\"key\":\"team\",\"value\":\"platform\"}]}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,212 @@
+//! Defines request and response types for a RunTask action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::capacity_provider::{self, CapacityProviderStrategyItem};
+use types::failure::Failure;
+use types::launch_type::LaunchType;
+use types::networking::NetworkConfiguration;
+use types::tag::{self, PropagateTags, Tag};
+use types::task::Task;
+use error::ValidationError;
+use custom_ser;
+
+/// The maximum number of tasks a single RunTask request can start.
+pub const MAX_COUNT: i32 = 10;
+
+/// A RunTask request type which can be serialized to json and set as the body of an HTTP request.
+/// Construct one of these and pass it to your client in the ecs_client::run_task() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct RunTaskRequest {
+    /// The short name or full ARN of the cluster to run the tasks on.
+    cluster: String,
+    /// The family and revision or full ARN of the task definition to run.
+    taskDefinition: String,
+    /// The number of tasks to start, from 1 to 10.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    count: Option<i32>,
+    /// The launch type the tasks run with.  Cannot be combined with a capacity provider strategy.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    launchType: Option<LaunchType>,
+    /// The capacity provider strategy to use.  Cannot be combined with a launch type.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    capacityProviderStrategy: Vec<CapacityProviderStrategyItem>,
+    /// The Fargate platform version the tasks run on.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    platformVersion: Option<String>,
+    /// The network configuration of the tasks.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    networkConfiguration: Option<NetworkConfiguration>,
+    /// A tag identifying what started the tasks, e.g. the name of a batch job.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    startedBy: Option<String>,
+    /// The name of the task group the tasks belong to.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    group: Option<String>,
+    /// A unique, case-sensitive identifier ensuring the request is idempotent.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    clientToken: Option<String>,
+    /// The tags to apply to the tasks.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    tags: Vec<Tag>,
+    /// Where the tags of the tasks are copied from.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    propagateTags: Option<PropagateTags>,
+    /// Whether ECS tags the tasks with the cluster name.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    enableECSManagedTags: Option<bool>,
+}
+
+/// A RunTask response type which can be deserialized from the body of an HTTP response.  If the
+/// request was successful, one of these will be returned from ecs_client::run_task().
+#[derive(Deserialize, Debug)]
+pub struct RunTaskResponse {
+    /// The tasks which were started.
+    #[serde(default)]
+    tasks: Vec<Task>,
+    /// The tasks which could not be started, along with the reason why.
+    #[serde(default)]
+    failures: Vec<Failure>,
+}
+
+/// Used so that a RunTaskRequest can be passed as a generic ECSRequest.
+impl ECSRequest for RunTaskRequest {}
+
+/// Used so that a RunTaskResponse can be returned as a generic ECSResponse.
+impl ECSResponse for RunTaskResponse {}
+
+/// Implements some convenience methods for building a RunTaskRequest.
+impl RunTaskRequest {
+    /// Creates a RunTaskRequest starting one task of the given task definition in a cluster.
+    pub fn new(cluster: String, task_definition: String) -> Self {
+        RunTaskRequest {
+            cluster: cluster,
+            taskDefinition: task_definition,
+            count: None,
+            launchType: None,
+            capacityProviderStrategy: Vec::new(),
+            platformVersion: None,
+            networkConfiguration: None,
+            startedBy: None,
+            group: None,
+            clientToken: None,
+            tags: Vec::new(),
+            propagateTags: None,
+            enableECSManagedTags: None,
+        }
+    }
+
+    /// Sets the number of tasks to start, or returns a ValidationError if it is not between 1
+    /// and 10.
+    pub fn set_count(&mut self, count: i32) -> Result<(), ValidationError> {
+        if count < 1 || count > MAX_COUNT {
+            return Err(ValidationError::new("count", format!("must be between 1 and {}, got {}", MAX_COUNT, count)));
+        }
+        self.count = Some(count);
+        Ok(())
+    }
+
+    /// Sets the launch type the tasks run with, replacing any capacity provider strategy.
+    pub fn set_launch_type(&mut self, launch_type: LaunchType) {
+        self.launchType = Some(launch_type);
+        self.capacityProviderStrategy.clear();
+    }
+
+    /// Sets the capacity provider strategy to use, replacing any launch type.
+    pub fn set_capacity_provider_strategy(&mut self,
+                                          strategy: Vec<CapacityProviderStrategyItem>) -> Result<(), ValidationError> {
+        try!(capacity_provider::validate_strategy(&strategy));
+        self.capacityProviderStrategy = strategy;
+        self.launchType = None;
+        Ok(())
+    }
+
+    /// Sets the Fargate platform version the tasks run on.
+    pub fn set_platform_version(&mut self, platform_version: String) {
+        self.platformVersion = Some(platform_version);
+    }
+
+    /// Sets the network configuration of the tasks.
+    pub fn set_network_configuration(&mut self, network_configuration: NetworkConfiguration) {
+        self.networkConfiguration = Some(network_configuration);
+    }
+
+    /// Sets the tag identifying what started the tasks.
+    pub fn set_started_by(&mut self, started_by: String) {
+        self.startedBy = Some(started_by);
+    }
+
+    /// Sets the name of the task group the tasks belong to.
+    pub fn set_group(&mut self, group: String) {
+        self.group = Some(group);
+    }
+
+    /// Sets the identifier ensuring the request is idempotent.
+    pub fn set_client_token(&mut self, client_token: String) {
+        self.clientToken = Some(client_token);
+    }
+
+    /// Sets the tags to apply to the tasks, or returns a ValidationError if there are more tags
+    /// than a resource can hold.
+    pub fn set_tags(&mut self, tags: Vec<Tag>) -> Result<(), ValidationError> {
+        try!(tag::validate_tags(&tags));
+        self.tags = tags;
+        Ok(())
+    }
+
+    /// Sets where the tags of the tasks are copied from.
+    pub fn set_propagate_tags(&mut self, propagate_tags: PropagateTags) {
+        self.propagateTags = Some(propagate_tags);
+    }
+
+    /// Sets whether ECS tags the tasks with the cluster name.
+    pub fn set_enable_ecs_managed_tags(&mut self, enable: bool) {
+        self.enableECSManagedTags = Some(enable);
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a RunTaskResponse.
+impl RunTaskResponse {
+    /// Gets a reference to the tasks which were started.
+    pub fn get_tasks(&self) -> &Vec<Task> {
+        &self.tasks
+    }
+
+    /// Gets a reference to the tasks which could not be started.
+    pub fn get_failures(&self) -> &Vec<Failure> {
+        &self.failures
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RunTaskRequest;
+    use types::launch_type::LaunchType;
+    use types::tag::{PropagateTags, Tag};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let mut req = RunTaskRequest::new(String::from("default"), String::from("batch:7"));
+        req.set_count(3).unwrap();
+        req.set_launch_type(LaunchType::EC2);
+        req.set_started_by(String::from("nightly"));
+        req.set_tags(vec![Tag::new(String::from("job"), String::from("report")).unwrap()]).unwrap();
+        req.set_propagate_tags(PropagateTags::TaskDefinition);
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\"taskDefinition\":\"batch:7\",\"count\":3,\"launchType\":\"EC2\",\
+              \"startedBy\":\"nightly\",\"tags\":[{\"key\":\"job\",\"value\":\"report\"}],\
+              \"propagateTags\":\"TASK_DEFINITION\"}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_count_limits() {
+        let mut req = RunTaskRequest::new(String::from("default"), String::from("batch:7"));
+        assert!(req.set_count(0).is_err());
+        assert!(req.set_count(11).is_err());
+        assert!(req.set_count(10).is_ok());
+    }
+}
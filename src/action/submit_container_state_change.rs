@@ -0,0 +1,129 @@
+//! Defines request and response types for a SubmitContainerStateChange action.  This action is
+//! used by the container agent to report that a single container's state has changed.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::state_change::NetworkBinding;
+use custom_ser;
+
+/// A SubmitContainerStateChange request type which can be serialized to json and set as the body
+/// of an HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::submit_container_state_change() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct SubmitContainerStateChangeRequest {
+    /// The short name or full ARN of the cluster that hosts the container.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    cluster: Option<String>,
+    /// The task ID or full ARN of the task that hosts the container.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    task: Option<String>,
+    /// The name of the container.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    containerName: Option<String>,
+    /// The ID of the Docker container.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    runtimeId: Option<String>,
+    /// The status of the container, e.g. RUNNING or STOPPED.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    status: Option<String>,
+    /// The exit code returned by the container, if it has stopped.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    exitCode: Option<i32>,
+    /// The reason for the state change.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    reason: Option<String>,
+    /// The network bindings of the container.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    networkBindings: Vec<NetworkBinding>,
+}
+
+/// A SubmitContainerStateChange response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::submit_container_state_change().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct SubmitContainerStateChangeResponse {
+    /// Acknowledgement of the state change.
+    acknowledgment: Option<String>,
+}
+
+/// Used so that a SubmitContainerStateChangeRequest can be passed as a generic ECSRequest.
+impl ECSRequest for SubmitContainerStateChangeRequest {}
+
+/// Used so that a SubmitContainerStateChangeResponse can be returned as a generic ECSResponse.
+impl ECSResponse for SubmitContainerStateChangeResponse {}
+
+/// Implements some convenience methods for building a SubmitContainerStateChangeRequest.
+impl SubmitContainerStateChangeRequest {
+    /// Creates a SubmitContainerStateChangeRequest reporting the given status for a container of
+    /// a task in a cluster.
+    pub fn new(cluster: String, task: String, container_name: String, status: String) -> Self {
+        SubmitContainerStateChangeRequest {
+            cluster: Some(cluster),
+            task: Some(task),
+            containerName: Some(container_name),
+            runtimeId: None,
+            status: Some(status),
+            exitCode: None,
+            reason: None,
+            networkBindings: Vec::new(),
+        }
+    }
+
+    /// Sets the ID of the Docker container.
+    pub fn set_runtime_id(&mut self, runtime_id: String) {
+        self.runtimeId = Some(runtime_id);
+    }
+
+    /// Sets the exit code returned by the container.
+    pub fn set_exit_code(&mut self, exit_code: i32) {
+        self.exitCode = Some(exit_code);
+    }
+
+    /// Sets the reason for the state change.
+    pub fn set_reason(&mut self, reason: String) {
+        self.reason = Some(reason);
+    }
+
+    /// Adds a network binding of the container.
+    pub fn add_network_binding(&mut self, binding: NetworkBinding) {
+        self.networkBindings.push(binding);
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// SubmitContainerStateChangeResponse.
+impl SubmitContainerStateChangeResponse {
+    /// Gets the acknowledgement returned by ECS.
+    pub fn get_acknowledgment(&self) -> &Option<String> {
+        &self.acknowledgment
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SubmitContainerStateChangeRequest, SubmitContainerStateChangeResponse};
+    use types::state_change::{NetworkBinding, TransportProtocol};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let mut req = SubmitContainerStateChangeRequest::new(
+            String::from("default"), String::from("task-id"), String::from("web"), String::from("RUNNING"));
+        req.add_network_binding(NetworkBinding::new(String::from("0.0.0.0"), 53, 53, TransportProtocol::Udp));
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\"task\":\"task-id\",\"containerName\":\"web\",\"status\":\"RUNNING\",\
+              \"networkBindings\":[{\"bindIP\":\"0.0.0.0\",\"containerPort\":53,\"hostPort\":53,\
+                                    \"protocol\":\"udp\"}]}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_deserialize_response() {
+        let response: SubmitContainerStateChangeResponse =
+            serde_json::from_str("{\"acknowledgment\":\"ACK\"}").unwrap();
+        assert_eq!(&Some(String::from("ACK")), response.get_acknowledgment());
+    }
+}
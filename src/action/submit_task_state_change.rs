@@ -0,0 +1,134 @@
+//! Defines request and response types for a SubmitTaskStateChange action.  This action is used by
+//! the container agent to report that a task's state has changed.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::state_change::{ContainerStateChange, AttachmentStateChange};
+use custom_ser;
+
+/// A SubmitTaskStateChange request type which can be serialized to json and set as the body of an
+/// HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::submit_task_state_change() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct SubmitTaskStateChangeRequest {
+    /// The short name or full ARN of the cluster that hosts the task.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    cluster: Option<String>,
+    /// The task ID or full ARN of the task in the state change request.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    task: Option<String>,
+    /// The status of the task, e.g. RUNNING or STOPPED.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    status: Option<String>,
+    /// The reason for the state change.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    reason: Option<String>,
+    /// The state changes of the containers in the task.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    containers: Vec<ContainerStateChange>,
+    /// The state changes of the attachments (e.g. elastic network interfaces) of the task.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    attachments: Vec<AttachmentStateChange>,
+    /// The time the container image pull started, in seconds since the epoch.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    pullStartedAt: Option<f64>,
+    /// The time the container image pull completed, in seconds since the epoch.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    pullStoppedAt: Option<f64>,
+    /// The time the task stopped executing, in seconds since the epoch.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    executionStoppedAt: Option<f64>,
+}
+
+/// A SubmitTaskStateChange response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::submit_task_state_change().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct SubmitTaskStateChangeResponse {
+    /// Acknowledgement of the state change.
+    acknowledgment: Option<String>,
+}
+
+/// Used so that a SubmitTaskStateChangeRequest can be passed as a generic ECSRequest.
+impl ECSRequest for SubmitTaskStateChangeRequest {}
+
+/// Used so that a SubmitTaskStateChangeResponse can be returned as a generic ECSResponse.
+impl ECSResponse for SubmitTaskStateChangeResponse {}
+
+/// Implements some convenience methods for building a SubmitTaskStateChangeRequest.
+impl SubmitTaskStateChangeRequest {
+    /// Creates a SubmitTaskStateChangeRequest reporting the given status for a task in a cluster.
+    pub fn new(cluster: String, task: String, status: String) -> Self {
+        SubmitTaskStateChangeRequest {
+            cluster: Some(cluster),
+            task: Some(task),
+            status: Some(status),
+            reason: None,
+            containers: Vec::new(),
+            attachments: Vec::new(),
+            pullStartedAt: None,
+            pullStoppedAt: None,
+            executionStoppedAt: None,
+        }
+    }
+
+    /// Sets the reason for the state change.
+    pub fn set_reason(&mut self, reason: String) {
+        self.reason = Some(reason);
+    }
+
+    /// Adds the state change of one of the task's containers.
+    pub fn add_container(&mut self, container: ContainerStateChange) {
+        self.containers.push(container);
+    }
+
+    /// Adds the state change of one of the task's attachments.
+    pub fn add_attachment(&mut self, attachment: AttachmentStateChange) {
+        self.attachments.push(attachment);
+    }
+
+    /// Sets the times the container image pull started and completed.
+    pub fn set_pull_times(&mut self, started_at: f64, stopped_at: f64) {
+        self.pullStartedAt = Some(started_at);
+        self.pullStoppedAt = Some(stopped_at);
+    }
+
+    /// Sets the time the task stopped executing.
+    pub fn set_execution_stopped_at(&mut self, stopped_at: f64) {
+        self.executionStoppedAt = Some(stopped_at);
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in a
+/// SubmitTaskStateChangeResponse.
+impl SubmitTaskStateChangeResponse {
+    /// Gets the acknowledgement returned by ECS.
+    pub fn get_acknowledgment(&self) -> &Option<String> {
+        &self.acknowledgment
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SubmitTaskStateChangeRequest;
+    use types::state_change::{ContainerStateChange, AttachmentStateChange};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let mut req = SubmitTaskStateChangeRequest::new(
+            String::from("default"), String::from("task-id"), String::from("RUNNING"));
+        req.add_container(ContainerStateChange::new(String::from("web"), String::from("RUNNING")));
+        req.add_attachment(AttachmentStateChange::new(String::from("arn:eni"), String::from("ATTACHED")));
+        req.set_pull_times(1461000000.0, 1461000010.5);
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\"task\":\"task-id\",\"status\":\"RUNNING\",\
+              \"containers\":[{\"containerName\":\"web\",\"status\":\"RUNNING\"}],\
+              \"attachments\":[{\"attachmentArn\":\"arn:eni\",\"status\":\"ATTACHED\"}],\
+              \"pullStartedAt\":1461000000.0,\"pullStoppedAt\":1461000010.5}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,61 @@
+//! Defines request and response types for a TagResource action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::tag::{self, Tag};
+use error::ValidationError;
+
+/// A TagResource request type which can be serialized to json and set as the body of an HTTP
+/// request.  Construct one of these and pass it to your client in the ecs_client::tag_resource()
+/// function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct TagResourceRequest {
+    /// The Amazon Resource Name (ARN) of the resource to add tags to.
+    resourceArn: String,
+    /// The tags to add to the resource.
+    tags: Vec<Tag>,
+}
+
+/// A TagResource response type which can be deserialized from the body of an HTTP response.
+/// ECS returns an empty json object when the tags were added successfully.
+#[derive(Deserialize, Debug)]
+pub struct TagResourceResponse {}
+
+/// Used so that a TagResourceRequest can be passed as a generic ECSRequest.
+impl ECSRequest for TagResourceRequest {}
+
+/// Used so that a TagResourceResponse can be returned as a generic ECSResponse.
+impl ECSResponse for TagResourceResponse {}
+
+/// Implements some convenience methods for building a TagResourceRequest.
+impl TagResourceRequest {
+    /// Creates a TagResourceRequest adding the given tags to the resource, or returns a
+    /// ValidationError if there are more tags than a resource can hold.
+    pub fn new(resource_arn: String, tags: Vec<Tag>) -> Result<Self, ValidationError> {
+        try!(tag::validate_tags(&tags));
+        Ok(TagResourceRequest {
+            resourceArn: resource_arn,
+            tags: tags,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TagResourceRequest;
+    use types::tag::Tag;
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let tags = vec![Tag::new(String::from("team"), String::from("platform")).unwrap()];
+        let req = TagResourceRequest::new(String::from("arn:aws:ecs:us-west-2:012345678910:cluster/default"),
+                                          tags).unwrap();
+        let expected: value::Value = serde_json::from_str(
+            "{\"resourceArn\":\"arn:aws:ecs:us-west-2:012345678910:cluster/default\",\
+              \"tags\":[{\"key\":\"team\",\"value\":\"platform\"}]}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+}
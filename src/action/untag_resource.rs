@@ -0,0 +1,68 @@
+//! Defines request and response types for an UntagResource action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::tag;
+use error::ValidationError;
+
+/// An UntagResource request type which can be serialized to json and set as the body of an HTTP
+/// request.  Construct one of these and pass it to your client in the
+/// ecs_client::untag_resource() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct UntagResourceRequest {
+    /// The Amazon Resource Name (ARN) of the resource to remove tags from.
+    resourceArn: String,
+    /// The keys of the tags to remove.
+    tagKeys: Vec<String>,
+}
+
+/// An UntagResource response type which can be deserialized from the body of an HTTP response.
+/// ECS returns an empty json object when the tags were removed successfully.
+#[derive(Deserialize, Debug)]
+pub struct UntagResourceResponse {}
+
+/// Used so that an UntagResourceRequest can be passed as a generic ECSRequest.
+impl ECSRequest for UntagResourceRequest {}
+
+/// Used so that an UntagResourceResponse can be returned as a generic ECSResponse.
+impl ECSResponse for UntagResourceResponse {}
+
+/// Implements some convenience methods for building an UntagResourceRequest.
+impl UntagResourceRequest {
+    /// Creates an UntagResourceRequest removing the tags with the given keys from the resource,
+    /// or returns a ValidationError if any of the keys could never name a tag.
+    pub fn new(resource_arn: String, tag_keys: Vec<String>) -> Result<Self, ValidationError> {
+        if tag_keys.len() > tag::MAX_TAGS {
+            return Err(ValidationError::new("tagKeys",
+                                            format!("at most {} keys can be removed, got {}", tag::MAX_TAGS, tag_keys.len())));
+        }
+        for key in &tag_keys {
+            try!(tag::validate_key(key));
+        }
+        Ok(UntagResourceRequest {
+            resourceArn: resource_arn,
+            tagKeys: tag_keys,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::UntagResourceRequest;
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let req = UntagResourceRequest::new(String::from("arn"), vec![String::from("team")]).unwrap();
+        let expected: value::Value = serde_json::from_str(
+            "{\"resourceArn\":\"arn\",\"tagKeys\":[\"team\"]}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_reserved_key() {
+        assert!(UntagResourceRequest::new(String::from("arn"), vec![String::from("aws:name")]).is_err());
+    }
+}
@@ -0,0 +1,72 @@
+//! Defines request and response types for an UpdateCapacityProvider action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::capacity_provider::{AutoScalingGroupProviderUpdate, CapacityProvider};
+
+/// An UpdateCapacityProvider request type which can be serialized to json and set as the body of
+/// an HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::update_capacity_provider() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct UpdateCapacityProviderRequest {
+    /// The name of the capacity provider to update.
+    name: String,
+    /// The changes to make to the Auto Scaling group settings of the capacity provider.
+    autoScalingGroupProvider: AutoScalingGroupProviderUpdate,
+}
+
+/// An UpdateCapacityProvider response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::update_capacity_provider().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct UpdateCapacityProviderResponse {
+    /// The capacity provider which was updated.
+    capacityProvider: CapacityProvider,
+}
+
+/// Used so that an UpdateCapacityProviderRequest can be passed as a generic ECSRequest.
+impl ECSRequest for UpdateCapacityProviderRequest {}
+
+/// Used so that an UpdateCapacityProviderResponse can be returned as a generic ECSResponse.
+impl ECSResponse for UpdateCapacityProviderResponse {}
+
+/// Implements some convenience methods for building an UpdateCapacityProviderRequest.
+impl UpdateCapacityProviderRequest {
+    /// Creates an UpdateCapacityProviderRequest applying the given changes.
+    pub fn new(name: String, update: AutoScalingGroupProviderUpdate) -> Self {
+        UpdateCapacityProviderRequest {
+            name: name,
+            autoScalingGroupProvider: update,
+        }
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in an
+/// UpdateCapacityProviderResponse.
+impl UpdateCapacityProviderResponse {
+    /// Gets a reference to the capacity provider which was updated.
+    pub fn get_capacity_provider(&self) -> &CapacityProvider {
+        &self.capacityProvider
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::UpdateCapacityProviderRequest;
+    use types::capacity_provider::{AutoScalingGroupProviderUpdate, ManagedScaling};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let mut update = AutoScalingGroupProviderUpdate::new();
+        update.set_managed_scaling(ManagedScaling::disabled());
+        let req = UpdateCapacityProviderRequest::new(String::from("my-provider"), update);
+        let expected: value::Value = serde_json::from_str(
+            "{\"name\":\"my-provider\",\"autoScalingGroupProvider\":\
+              {\"managedScaling\":{\"status\":\"DISABLED\"}}}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,86 @@
+//! Defines request and response types for an UpdateCluster action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::cluster::{Cluster, ClusterSetting, ClusterConfiguration};
+use custom_ser;
+
+/// An UpdateCluster request type which can be serialized to json and set as the body of an HTTP
+/// request.  Construct one of these and pass it to your client in the
+/// ecs_client::update_cluster() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct UpdateClusterRequest {
+    /// The short name or full ARN of the cluster to update.
+    cluster: String,
+    /// The settings to change on the cluster.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    settings: Vec<ClusterSetting>,
+    /// The new configuration of the cluster.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    configuration: Option<ClusterConfiguration>,
+}
+
+/// An UpdateCluster response type which can be deserialized from the body of an HTTP response.  If
+/// the request was successful, one of these will be returned from ecs_client::update_cluster().
+#[derive(Deserialize, Debug)]
+pub struct UpdateClusterResponse {
+    /// The cluster as it was updated.
+    cluster: Cluster,
+}
+
+/// Used so that an UpdateClusterRequest can be passed as a generic ECSRequest.
+impl ECSRequest for UpdateClusterRequest {}
+
+/// Used so that an UpdateClusterResponse can be returned as a generic ECSResponse.
+impl ECSResponse for UpdateClusterResponse {}
+
+/// Implements some convenience methods for building an UpdateClusterRequest.
+impl UpdateClusterRequest {
+    /// Creates an UpdateClusterRequest which changes nothing on the cluster.
+    pub fn new(cluster: String) -> Self {
+        UpdateClusterRequest {
+            cluster: cluster,
+            settings: Vec::new(),
+            configuration: None,
+        }
+    }
+
+    /// Adds a setting to change on the cluster.
+    pub fn add_setting(&mut self, setting: ClusterSetting) {
+        self.settings.push(setting);
+    }
+
+    /// Sets the new configuration of the cluster.
+    pub fn set_configuration(&mut self, configuration: ClusterConfiguration) {
+        self.configuration = Some(configuration);
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in an UpdateClusterResponse.
+impl UpdateClusterResponse {
+    /// Gets a reference to the cluster as it was updated.
+    pub fn get_cluster(&self) -> &Cluster {
+        &self.cluster
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::UpdateClusterRequest;
+    use types::cluster::{ClusterSetting, ClusterConfiguration, ExecuteCommandConfiguration, ExecuteCommandLogging};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let mut req = UpdateClusterRequest::new(String::from("default"));
+        req.add_setting(ClusterSetting::container_insights(true));
+        let exec = ExecuteCommandConfiguration::new(ExecuteCommandLogging::Default).unwrap();
+        req.set_configuration(ClusterConfiguration::with_execute_command_configuration(exec));
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\"settings\":[{\"name\":\"containerInsights\",\"value\":\"enabled\"}],\
+              \"configuration\":{\"executeCommandConfiguration\":{\"logging\":\"DEFAULT\"}}}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,68 @@
+//! Defines request and response types for an UpdateClusterSettings action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::cluster::{Cluster, ClusterSetting};
+
+/// An UpdateClusterSettings request type which can be serialized to json and set as the body of an
+/// HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::update_cluster_settings() function.
+#[derive(Serialize, Debug)]
+pub struct UpdateClusterSettingsRequest {
+    /// The short name or full ARN of the cluster to update.
+    cluster: String,
+    /// The settings to change on the cluster.
+    settings: Vec<ClusterSetting>,
+}
+
+/// An UpdateClusterSettings response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::update_cluster_settings().
+#[derive(Deserialize, Debug)]
+pub struct UpdateClusterSettingsResponse {
+    /// The cluster as it was updated.
+    cluster: Cluster,
+}
+
+/// Used so that an UpdateClusterSettingsRequest can be passed as a generic ECSRequest.
+impl ECSRequest for UpdateClusterSettingsRequest {}
+
+/// Used so that an UpdateClusterSettingsResponse can be returned as a generic ECSResponse.
+impl ECSResponse for UpdateClusterSettingsResponse {}
+
+/// Implements some convenience methods for building an UpdateClusterSettingsRequest.
+impl UpdateClusterSettingsRequest {
+    /// Creates an UpdateClusterSettingsRequest changing the given settings.
+    pub fn new(cluster: String, settings: Vec<ClusterSetting>) -> Self {
+        UpdateClusterSettingsRequest {
+            cluster: cluster,
+            settings: settings,
+        }
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in an
+/// UpdateClusterSettingsResponse.
+impl UpdateClusterSettingsResponse {
+    /// Gets a reference to the cluster as it was updated.
+    pub fn get_cluster(&self) -> &Cluster {
+        &self.cluster
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::UpdateClusterSettingsRequest;
+    use types::cluster::ClusterSetting;
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let req = UpdateClusterSettingsRequest::new(String::from("default"),
+                                                    vec![ClusterSetting::container_insights(false)]);
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\"settings\":[{\"name\":\"containerInsights\",\"value\":\"disabled\"}]}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+}
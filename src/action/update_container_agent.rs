@@ -0,0 +1,90 @@
+//! Defines request and response types for an UpdateContainerAgent action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::container_instance::ContainerInstance;
+use custom_ser;
+
+/// An UpdateContainerAgent request type which can be serialized to json and set as the body of an
+/// HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::update_container_agent() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct UpdateContainerAgentRequest {
+    /// The short name or full ARN of the cluster the container instance is registered with.  If
+    /// omitted, the default cluster is assumed.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    cluster: Option<String>,
+    /// The ID or full ARN of the container instance whose agent should be updated.
+    containerInstance: String,
+}
+
+/// An UpdateContainerAgent response type which can be deserialized from the body of an HTTP
+/// response.  If the request was successful, one of these will be returned from
+/// ecs_client::update_container_agent().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct UpdateContainerAgentResponse {
+    /// The container instance whose agent is being updated.
+    containerInstance: ContainerInstance,
+}
+
+/// Used so that an UpdateContainerAgentRequest can be passed as a generic ECSRequest.
+impl ECSRequest for UpdateContainerAgentRequest {}
+
+/// Used so that an UpdateContainerAgentResponse can be returned as a generic ECSResponse.
+impl ECSResponse for UpdateContainerAgentResponse {}
+
+/// Implements some convenience methods for building an UpdateContainerAgentRequest.
+impl UpdateContainerAgentRequest {
+    /// Creates an UpdateContainerAgentRequest for a container instance in the default cluster.
+    pub fn new(container_instance: String) -> Self {
+        UpdateContainerAgentRequest {
+            cluster: None,
+            containerInstance: container_instance,
+        }
+    }
+
+    /// Creates an UpdateContainerAgentRequest for a container instance in the given cluster.
+    pub fn for_cluster(cluster: String, container_instance: String) -> Self {
+        let mut request = UpdateContainerAgentRequest::new(container_instance);
+        request.cluster = Some(cluster);
+        request
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in an
+/// UpdateContainerAgentResponse.
+impl UpdateContainerAgentResponse {
+    /// Gets a reference to the container instance whose agent is being updated.  Its
+    /// agentUpdateStatus reports the progress of the update.
+    pub fn get_container_instance(&self) -> &ContainerInstance {
+        &self.containerInstance
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{UpdateContainerAgentRequest, UpdateContainerAgentResponse};
+    use types::container_instance::AgentUpdateStatus;
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let req = UpdateContainerAgentRequest::for_cluster(String::from("default"),
+                                                           String::from("c8d1b1a2"));
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\"containerInstance\":\"c8d1b1a2\"}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_deserialize_response() {
+        let resp: UpdateContainerAgentResponse = serde_json::from_str(
+            "{\"containerInstance\":{\"containerInstanceArn\":\"arn:aws:ecs:us-west-2:012345678910:container-instance/c8d1b1a2\",\
+              \"agentUpdateStatus\":\"PENDING\"}}").unwrap();
+        assert_eq!(&Some(AgentUpdateStatus::Pending),
+                   resp.get_container_instance().get_agent_update_status());
+    }
+}
@@ -0,0 +1,72 @@
+//! Defines request and response types for an UpdateServicePrimaryTaskSet action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::task_set::TaskSet;
+
+/// An UpdateServicePrimaryTaskSet request type which can be serialized to json and set as the
+/// body of an HTTP request.  Construct one of these and pass it to your client in the
+/// ecs_client::update_service_primary_task_set() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct UpdateServicePrimaryTaskSetRequest {
+    /// The short name or full ARN of the cluster hosting the service.
+    cluster: String,
+    /// The short name or full ARN of the service.
+    service: String,
+    /// The ID or full ARN of the task set to make the primary task set of the service.
+    primaryTaskSet: String,
+}
+
+/// An UpdateServicePrimaryTaskSet response type which can be deserialized from the body of an
+/// HTTP response.  If the request was successful, one of these will be returned from
+/// ecs_client::update_service_primary_task_set().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct UpdateServicePrimaryTaskSetResponse {
+    /// The task set which is now the primary task set of the service.
+    taskSet: TaskSet,
+}
+
+/// Used so that an UpdateServicePrimaryTaskSetRequest can be passed as a generic ECSRequest.
+impl ECSRequest for UpdateServicePrimaryTaskSetRequest {}
+
+/// Used so that an UpdateServicePrimaryTaskSetResponse can be returned as a generic ECSResponse.
+impl ECSResponse for UpdateServicePrimaryTaskSetResponse {}
+
+/// Implements some convenience methods for building an UpdateServicePrimaryTaskSetRequest.
+impl UpdateServicePrimaryTaskSetRequest {
+    /// Creates an UpdateServicePrimaryTaskSetRequest promoting the given task set.
+    pub fn new(cluster: String, service: String, primary_task_set: String) -> Self {
+        UpdateServicePrimaryTaskSetRequest {
+            cluster: cluster,
+            service: service,
+            primaryTaskSet: primary_task_set,
+        }
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in an
+/// UpdateServicePrimaryTaskSetResponse.
+impl UpdateServicePrimaryTaskSetResponse {
+    /// Gets a reference to the task set which is now the primary task set of the service.
+    pub fn get_task_set(&self) -> &TaskSet {
+        &self.taskSet
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::UpdateServicePrimaryTaskSetRequest;
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let req = UpdateServicePrimaryTaskSetRequest::new(String::from("default"), String::from("web"),
+                                                          String::from("ecs-svc/2"));
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\"service\":\"web\",\"primaryTaskSet\":\"ecs-svc/2\"}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,75 @@
+//! Defines request and response types for an UpdateTaskSet action.
+
+use action::ecs_action::{ECSRequest, ECSResponse};
+use types::task_set::{Scale, TaskSet};
+
+/// An UpdateTaskSet request type which can be serialized to json and set as the body of an HTTP
+/// request.  Construct one of these and pass it to your client in the
+/// ecs_client::update_task_set() function.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct UpdateTaskSetRequest {
+    /// The short name or full ARN of the cluster hosting the service.
+    cluster: String,
+    /// The short name or full ARN of the service the task set belongs to.
+    service: String,
+    /// The ID or full ARN of the task set to update.
+    taskSet: String,
+    /// The new size of the task set.
+    scale: Scale,
+}
+
+/// An UpdateTaskSet response type which can be deserialized from the body of an HTTP response.  If
+/// the request was successful, one of these will be returned from ecs_client::update_task_set().
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct UpdateTaskSetResponse {
+    /// The task set which was updated.
+    taskSet: TaskSet,
+}
+
+/// Used so that an UpdateTaskSetRequest can be passed as a generic ECSRequest.
+impl ECSRequest for UpdateTaskSetRequest {}
+
+/// Used so that an UpdateTaskSetResponse can be returned as a generic ECSResponse.
+impl ECSResponse for UpdateTaskSetResponse {}
+
+/// Implements some convenience methods for building an UpdateTaskSetRequest.
+impl UpdateTaskSetRequest {
+    /// Creates an UpdateTaskSetRequest resizing a task set.
+    pub fn new(cluster: String, service: String, task_set: String, scale: Scale) -> Self {
+        UpdateTaskSetRequest {
+            cluster: cluster,
+            service: service,
+            taskSet: task_set,
+            scale: scale,
+        }
+    }
+}
+
+/// Implements some convenience methods for looking at values returned in an UpdateTaskSetResponse.
+impl UpdateTaskSetResponse {
+    /// Gets a reference to the task set which was updated.
+    pub fn get_task_set(&self) -> &TaskSet {
+        &self.taskSet
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::UpdateTaskSetRequest;
+    use types::task_set::Scale;
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_full_blob() {
+        let req = UpdateTaskSetRequest::new(String::from("default"), String::from("web"),
+                                            String::from("ecs-svc/1234"), Scale::percent(25.0).unwrap());
+        let expected: value::Value = serde_json::from_str(
+            "{\"cluster\":\"default\",\"service\":\"web\",\"taskSet\":\"ecs-svc/1234\",\
+              \"scale\":{\"value\":25.0,\"unit\":\"PERCENT\"}}").unwrap();
+        let actual: value::Value = value::to_value(&req);
+        assert_eq!(expected, actual);
+    }
+}
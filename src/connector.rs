@@ -0,0 +1,175 @@
+//! This module contains the ProxyConnector which the HyperTransport built from a ClientConfig
+//! opens its connections with.  hyper 0.8's HttpConnector can neither bound the time spent
+//! connecting nor go through a proxy, so connections are opened by hand and, when a proxy applies,
+//! tunnelled through it with HTTP CONNECT before TLS is negotiated with the endpoint itself.
+//!
+//! CONNECT is used for http endpoints too, which every common forward proxy supports, so that the
+//! request line does not need rewriting to an absolute URL.  Proxies requiring authentication are
+//! sent the Basic credentials from the userinfo of their URL in a Proxy-Authorization header.
+
+use builder::ProxyConfig;
+
+use hyper;
+use hyper::net::{HttpStream, HttpsStream, NetworkConnector, Ssl};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// The max size of a proxy's reply to CONNECT, which is only a status line and a few headers.
+const MAX_CONNECT_REPLY_BYTES: usize = 8192;
+
+/// A NetworkConnector which opens connections with a connect timeout, through a proxy if one
+/// applies to the host, and wraps https connections with the given Ssl implementation.
+pub struct ProxyConnector<S: Ssl> {
+    ssl: S,
+    proxy: ProxyConfig,
+    connect_timeout: Option<Duration>,
+}
+
+impl<S: Ssl> ProxyConnector<S> {
+    /// Creates a ProxyConnector.
+    pub fn new(ssl: S, proxy: ProxyConfig, connect_timeout: Option<Duration>) -> Self {
+        ProxyConnector {
+            ssl: ssl,
+            proxy: proxy,
+            connect_timeout: connect_timeout,
+        }
+    }
+
+    /// Opens a TCP connection to the host, or to the proxy and through it to the host.
+    fn open(&self, host: &str, port: u16, scheme: &str) -> io::Result<TcpStream> {
+        match self.proxy.proxy_for(scheme, host) {
+            Some(proxy) => {
+                let endpoint = proxy.get_endpoint();
+                let mut stream = try!(self.connect(endpoint.get_hostname(), endpoint.get_port_or_default()));
+                try!(tunnel(&mut stream, host, port, proxy.get_authorization().as_ref().map(|value| &value[..])));
+                Ok(stream)
+            },
+            None => self.connect(host, port),
+        }
+    }
+
+    /// Connects to the first address of the host which accepts, within the connect timeout.
+    fn connect(&self, host: &str, port: u16) -> io::Result<TcpStream> {
+        let addresses: Vec<SocketAddr> = try!((host, port).to_socket_addrs()).collect();
+        let mut last_error = io::Error::new(io::ErrorKind::NotFound,
+                                            format!("{} did not resolve to any address", host));
+        for address in addresses {
+            let result = match self.connect_timeout {
+                Some(timeout) => TcpStream::connect_timeout(&address, timeout),
+                None => TcpStream::connect(&address),
+            };
+            match result {
+                Ok(stream) => return Ok(stream),
+                Err(error) => last_error = error,
+            }
+        }
+        Err(last_error)
+    }
+}
+
+impl<S: Ssl> NetworkConnector for ProxyConnector<S> {
+    type Stream = HttpsStream<S::Stream>;
+
+    fn connect(&self, host: &str, port: u16, scheme: &str) -> hyper::Result<Self::Stream> {
+        let stream = HttpStream(try!(self.open(host, port, scheme)));
+        match scheme {
+            "http" => Ok(HttpsStream::Http(stream)),
+            "https" => Ok(HttpsStream::Https(try!(self.ssl.wrap_client(stream, host)))),
+            _ => Err(hyper::Error::Io(io::Error::new(io::ErrorKind::InvalidInput,
+                                                     format!("unsupported scheme: {}", scheme)))),
+        }
+    }
+}
+
+impl<S: Ssl> fmt::Debug for ProxyConnector<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ProxyConnector")
+         .field("proxy", &self.proxy)
+         .field("connect_timeout", &self.connect_timeout)
+         .finish()
+    }
+}
+
+/// Asks the proxy at the other end of the stream to open a tunnel to host:port, authenticating
+/// with the given Proxy-Authorization header value if any, and fails unless it answers with a 2xx
+/// status.
+fn tunnel(stream: &mut TcpStream, host: &str, port: u16, authorization: Option<&str>) -> io::Result<()> {
+    let mut request = format!("CONNECT {0}:{1} HTTP/1.1\r\nHost: {0}:{1}\r\n", host, port);
+    if let Some(authorization) = authorization {
+        request.push_str(&format!("Proxy-Authorization: {}\r\n", authorization));
+    }
+    request.push_str("\r\n");
+    try!(stream.write_all(request.as_bytes()));
+    try!(stream.flush());
+
+    // read byte by byte so that nothing past the end of the reply is consumed
+    let mut reply = Vec::new();
+    let mut byte = [0; 1];
+    while !reply.ends_with(b"\r\n\r\n") {
+        if reply.len() >= MAX_CONNECT_REPLY_BYTES {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "proxy reply to CONNECT is too long"));
+        }
+        if try!(stream.read(&mut byte)) == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "proxy closed the connection during CONNECT"));
+        }
+        reply.push(byte[0]);
+    }
+
+    let reply = String::from_utf8_lossy(&reply);
+    let status = reply.split_whitespace().nth(1).unwrap_or("");
+    if status.starts_with('2') && status.len() == 3 {
+        Ok(())
+    } else {
+        let status_line = reply.lines().next().unwrap_or("");
+        Err(io::Error::new(io::ErrorKind::ConnectionRefused,
+                           format!("proxy refused to CONNECT to {}:{}: {}", host, port, status_line)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::tunnel;
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    fn tunnel_with_reply(reply: &'static str, authorization: Option<&str>) -> (Result<(), String>, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let proxy = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buffer = [0; 1024];
+            let read = stream.read(&mut buffer).unwrap();
+            stream.write_all(reply.as_bytes()).unwrap();
+            String::from_utf8_lossy(&buffer[..read]).into_owned()
+        });
+        let mut stream = TcpStream::connect(address).unwrap();
+        let result = tunnel(&mut stream, "ecs.us-west-2.amazonaws.com", 443, authorization)
+            .map_err(|error| error.to_string());
+        (result, proxy.join().unwrap())
+    }
+
+    #[test]
+    fn test_tunnel_established() {
+        let (result, request) = tunnel_with_reply("HTTP/1.1 200 Connection established\r\n\r\n", None);
+        assert!(result.is_ok());
+        assert!(request.starts_with("CONNECT ecs.us-west-2.amazonaws.com:443 HTTP/1.1\r\n"));
+        assert!(!request.contains("Proxy-Authorization"));
+    }
+
+    #[test]
+    fn test_tunnel_authenticated() {
+        let (result, request) = tunnel_with_reply("HTTP/1.1 200 Connection established\r\n\r\n",
+                                                  Some("Basic dXNlcjpwYXNz"));
+        assert!(result.is_ok());
+        assert!(request.contains("\r\nProxy-Authorization: Basic dXNlcjpwYXNz\r\n\r\n"));
+    }
+
+    #[test]
+    fn test_tunnel_refused() {
+        let (result, _) = tunnel_with_reply("HTTP/1.1 407 Proxy Authentication Required\r\n\r\n", None);
+        assert!(result.unwrap_err().contains("407"));
+    }
+}
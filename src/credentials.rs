@@ -0,0 +1,404 @@
+//! This module contains the AWS Credentials used to sign requests and the providers which source
+//! them: the environment, the shared config and credentials files, the ECS container credentials
+//! endpoint and the EC2 instance metadata service.  The DefaultCredentialsChain tries each of
+//! them in that order, like the official AWS SDKs.
+
+use error::ECSError;
+use metadata::{self, InstanceMetadataClient, CONTAINER_METADATA_ADDRESS};
+use profile::{self, ProfileFile};
+
+use serde_json;
+use std::env;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Mutex;
+use std::time::Duration;
+use time::{self, Timespec};
+
+/// The name of the environment variable in which your AWS Access Key ID may be stored.
+pub const AWS_ACCESS_KEY_ID: &'static str = "AWS_ACCESS_KEY_ID";
+/// The name of the environment variable in which your AWS Secret Access Key may be stored.
+pub const AWS_SECRET_ACCESS_KEY: &'static str = "AWS_SECRET_ACCESS_KEY";
+/// The name of the environment variable in which a session token for temporary credentials may be
+/// stored.
+pub const AWS_SESSION_TOKEN: &'static str = "AWS_SESSION_TOKEN";
+/// The name of the environment variable ECS sets to the path of a task's credentials on the
+/// container metadata service.
+pub const AWS_CONTAINER_CREDENTIALS_RELATIVE_URI: &'static str = "AWS_CONTAINER_CREDENTIALS_RELATIVE_URI";
+/// The path under which the instance metadata service lists the role of the instance.
+const SECURITY_CREDENTIALS_PATH: &'static str = "/latest/meta-data/iam/security-credentials/";
+/// The time before their expiration at which cached credentials are refreshed, in seconds.
+const EXPIRATION_MARGIN_SECS: i64 = 300;
+
+/// An AWS access key pair, with the session token and expiration of temporary credentials.  The
+/// Debug representation never includes the secret access key or the session token.
+#[derive(Clone, PartialEq)]
+pub struct Credentials {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+    expiration: Option<Timespec>,
+}
+
+/// Supplies the Credentials an ECSClient signs its requests with.
+pub trait CredentialsProvider : Debug + Send + Sync {
+    /// Gets the current Credentials, or an ECSError::Credentials if none are available.
+    fn get_credentials(&self) -> Result<Credentials, ECSError>;
+}
+
+/// Reads Credentials from AWS_ACCESS_KEY_ID, AWS_SECRET_ACCESS_KEY and AWS_SESSION_TOKEN.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EnvironmentProvider;
+
+/// Reads Credentials from a profile of the shared credentials file, or failing that the shared
+/// config file.
+#[derive(Clone, Debug)]
+pub struct ProfileProvider {
+    profile: String,
+}
+
+/// Fetches a task's Credentials from the ECS container metadata service, at the path ECS sets in
+/// AWS_CONTAINER_CREDENTIALS_RELATIVE_URI.
+#[derive(Clone, Debug)]
+pub struct ContainerProvider {
+    address: String,
+    timeout: Duration,
+}
+
+/// Fetches the Credentials of the role attached to the EC2 instance from the instance metadata
+/// service.
+#[derive(Clone, Debug)]
+pub struct InstanceMetadataProvider {
+    client: InstanceMetadataClient,
+}
+
+/// Tries each of its providers in turn and returns the first Credentials found.  Temporary
+/// Credentials are cached until shortly before they expire.
+#[derive(Debug)]
+pub struct DefaultCredentialsChain {
+    providers: Vec<Box<CredentialsProvider>>,
+    cached: Mutex<Option<Credentials>>,
+}
+
+/// The json document the container and instance metadata services return credentials in.
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+struct MetadataCredentials {
+    AccessKeyId: String,
+    SecretAccessKey: String,
+    Token: Option<String>,
+    Expiration: Option<String>,
+}
+
+impl Credentials {
+    /// Creates long-term Credentials from an access key pair.
+    pub fn new(access_key_id: String, secret_access_key: String) -> Self {
+        Credentials {
+            access_key_id: access_key_id,
+            secret_access_key: secret_access_key,
+            session_token: None,
+            expiration: None,
+        }
+    }
+
+    /// Sets the session token of temporary Credentials.
+    pub fn set_session_token(&mut self, session_token: String) {
+        self.session_token = Some(session_token);
+    }
+
+    /// Sets the time at which temporary Credentials expire.
+    pub fn set_expiration(&mut self, expiration: Timespec) {
+        self.expiration = Some(expiration);
+    }
+
+    /// Gets the access key ID.
+    pub fn get_access_key_id(&self) -> &str {
+        &self.access_key_id
+    }
+
+    /// Gets the secret access key.
+    pub fn get_secret_access_key(&self) -> &str {
+        &self.secret_access_key
+    }
+
+    /// Gets the session token of temporary Credentials.
+    pub fn get_session_token(&self) -> &Option<String> {
+        &self.session_token
+    }
+
+    /// Gets the time at which temporary Credentials expire.
+    pub fn get_expiration(&self) -> &Option<Timespec> {
+        &self.expiration
+    }
+
+    /// Returns true if the Credentials expire within five minutes of the given time.
+    pub fn expires_soon(&self, now: Timespec) -> bool {
+        self.expiration.map_or(false, |expiration| (expiration - now).num_seconds() < EXPIRATION_MARGIN_SECS)
+    }
+}
+
+impl Debug for Credentials {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Credentials")
+         .field("access_key_id", &self.access_key_id)
+         .field("secret_access_key", &"<redacted>")
+         .field("session_token", &self.session_token.as_ref().map(|_| "<redacted>"))
+         .field("expiration", &self.expiration)
+         .finish()
+    }
+}
+
+/// Fixed Credentials provide themselves.
+impl CredentialsProvider for Credentials {
+    fn get_credentials(&self) -> Result<Credentials, ECSError> {
+        Ok(self.clone())
+    }
+}
+
+impl CredentialsProvider for EnvironmentProvider {
+    fn get_credentials(&self) -> Result<Credentials, ECSError> {
+        let lookup = |variable: &str| env::var(variable).ok().and_then(|value| {
+            if value.is_empty() { None } else { Some(value) }
+        });
+        match (lookup(AWS_ACCESS_KEY_ID), lookup(AWS_SECRET_ACCESS_KEY)) {
+            (Some(access_key_id), Some(secret_access_key)) => {
+                let mut credentials = Credentials::new(access_key_id, secret_access_key);
+                if let Some(session_token) = lookup(AWS_SESSION_TOKEN) {
+                    credentials.set_session_token(session_token);
+                }
+                Ok(credentials)
+            },
+            _ => Err(ECSError::Credentials(format!("{} and {} are not both set",
+                                                   AWS_ACCESS_KEY_ID, AWS_SECRET_ACCESS_KEY))),
+        }
+    }
+}
+
+impl ProfileProvider {
+    /// Creates a ProfileProvider for the profile named by AWS_PROFILE, or the default profile.
+    pub fn new() -> Self {
+        ProfileProvider::with_profile(&profile::active_profile())
+    }
+
+    /// Creates a ProfileProvider for the given profile.
+    pub fn with_profile(profile: &str) -> Self {
+        ProfileProvider {
+            profile: String::from(profile),
+        }
+    }
+}
+
+impl CredentialsProvider for ProfileProvider {
+    fn get_credentials(&self) -> Result<Credentials, ECSError> {
+        for file in &[try!(ProfileFile::load_credentials()), try!(ProfileFile::load_config())] {
+            let access_key_id = file.get(&self.profile, "aws_access_key_id");
+            let secret_access_key = file.get(&self.profile, "aws_secret_access_key");
+            if let (Some(access_key_id), Some(secret_access_key)) = (access_key_id, secret_access_key) {
+                let mut credentials = Credentials::new(access_key_id.clone(), secret_access_key.clone());
+                if let Some(session_token) = file.get(&self.profile, "aws_session_token") {
+                    credentials.set_session_token(session_token.clone());
+                }
+                return Ok(credentials);
+            }
+        }
+        Err(ECSError::Credentials(format!("profile {} has no access key", self.profile)))
+    }
+}
+
+impl ContainerProvider {
+    /// Creates a ContainerProvider for the ECS container metadata service.
+    pub fn new() -> Self {
+        ContainerProvider {
+            address: String::from(CONTAINER_METADATA_ADDRESS),
+            timeout: Duration::from_secs(1),
+        }
+    }
+}
+
+impl CredentialsProvider for ContainerProvider {
+    fn get_credentials(&self) -> Result<Credentials, ECSError> {
+        let path = match env::var(AWS_CONTAINER_CREDENTIALS_RELATIVE_URI) {
+            Ok(ref path) if !path.is_empty() => path.clone(),
+            _ => return Err(ECSError::Credentials(format!("{} is not set", AWS_CONTAINER_CREDENTIALS_RELATIVE_URI))),
+        };
+        let response = try!(metadata::send(&self.address, "GET", &path, &[], self.timeout));
+        parse_metadata_credentials(&try!(response.into_body(&path)))
+    }
+}
+
+impl InstanceMetadataProvider {
+    /// Creates an InstanceMetadataProvider for the EC2 instance metadata service.
+    pub fn new() -> Self {
+        InstanceMetadataProvider::with_client(InstanceMetadataClient::new())
+    }
+
+    /// Creates an InstanceMetadataProvider which fetches credentials through the given client.
+    pub fn with_client(client: InstanceMetadataClient) -> Self {
+        InstanceMetadataProvider {
+            client: client,
+        }
+    }
+}
+
+impl CredentialsProvider for InstanceMetadataProvider {
+    fn get_credentials(&self) -> Result<Credentials, ECSError> {
+        let roles = try!(self.client.get(SECURITY_CREDENTIALS_PATH));
+        let role = match roles.lines().next() {
+            Some(role) if !role.trim().is_empty() => role.trim(),
+            _ => return Err(ECSError::Credentials(String::from("no role is attached to the instance"))),
+        };
+        let document = try!(self.client.get(&format!("{}{}", SECURITY_CREDENTIALS_PATH, role)));
+        parse_metadata_credentials(&document)
+    }
+}
+
+impl DefaultCredentialsChain {
+    /// Creates a chain which tries the environment, the active profile, the ECS container
+    /// credentials and finally the EC2 instance metadata service.
+    pub fn new() -> Self {
+        DefaultCredentialsChain::with_providers(vec![
+            Box::new(EnvironmentProvider),
+            Box::new(ProfileProvider::new()),
+            Box::new(ContainerProvider::new()),
+            Box::new(InstanceMetadataProvider::new()),
+        ])
+    }
+
+    /// Creates a chain which tries the given providers in order.
+    pub fn with_providers(providers: Vec<Box<CredentialsProvider>>) -> Self {
+        DefaultCredentialsChain {
+            providers: providers,
+            cached: Mutex::new(None),
+        }
+    }
+}
+
+impl CredentialsProvider for DefaultCredentialsChain {
+    fn get_credentials(&self) -> Result<Credentials, ECSError> {
+        let mut cached = self.cached.lock().unwrap();
+        if let Some(ref credentials) = *cached {
+            if !credentials.expires_soon(time::get_time()) {
+                return Ok(credentials.clone());
+            }
+        }
+        let mut failures = Vec::new();
+        for provider in &self.providers {
+            match provider.get_credentials() {
+                Ok(credentials) => {
+                    // long-term credentials are read afresh each time, so that rotating them
+                    // takes effect without a restart
+                    *cached = if credentials.get_expiration().is_some() { Some(credentials.clone()) } else { None };
+                    return Ok(credentials);
+                },
+                Err(error) => failures.push(error.to_string()),
+            }
+        }
+        Err(ECSError::Credentials(format!("no credentials found: {}", failures.join("; "))))
+    }
+}
+
+/// Parses the credentials document of the container or instance metadata service.
+fn parse_metadata_credentials(document: &str) -> Result<Credentials, ECSError> {
+    let parsed: MetadataCredentials = try!(serde_json::from_str(document));
+    let mut credentials = Credentials::new(parsed.AccessKeyId, parsed.SecretAccessKey);
+    if let Some(token) = parsed.Token {
+        credentials.set_session_token(token);
+    }
+    if let Some(expiration) = parsed.Expiration {
+        match time::strptime(&expiration, "%Y-%m-%dT%H:%M:%SZ") {
+            Ok(expiration) => credentials.set_expiration(expiration.to_timespec()),
+            Err(_) => return Err(ECSError::Credentials(format!("invalid expiration: {}", expiration))),
+        }
+    }
+    Ok(credentials)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Credentials, CredentialsProvider, DefaultCredentialsChain, parse_metadata_credentials};
+    use error::ECSError;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use time::{self, Duration};
+
+    /// Counts the credentials it hands out, each of which expires at the given offset from now.
+    #[derive(Debug)]
+    struct CountingProvider {
+        calls: Arc<AtomicUsize>,
+        expires_in: Option<Duration>,
+    }
+
+    impl CredentialsProvider for CountingProvider {
+        fn get_credentials(&self) -> Result<Credentials, ECSError> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            let mut credentials = Credentials::new(format!("AKID{}", call), String::from("secret"));
+            if let Some(expires_in) = self.expires_in {
+                credentials.set_expiration(time::get_time() + expires_in);
+            }
+            Ok(credentials)
+        }
+    }
+
+    #[derive(Debug)]
+    struct FailingProvider;
+
+    impl CredentialsProvider for FailingProvider {
+        fn get_credentials(&self) -> Result<Credentials, ECSError> {
+            Err(ECSError::Credentials(String::from("nothing here")))
+        }
+    }
+
+    fn chain(expires_in: Option<Duration>) -> (DefaultCredentialsChain, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let chain = DefaultCredentialsChain::with_providers(vec![
+            Box::new(FailingProvider),
+            Box::new(CountingProvider { calls: calls.clone(), expires_in: expires_in }),
+        ]);
+        (chain, calls)
+    }
+
+    #[test]
+    fn test_chain_caches_temporary_credentials() {
+        let (chain, calls) = chain(Some(Duration::hours(1)));
+        assert_eq!("AKID0", chain.get_credentials().unwrap().get_access_key_id());
+        assert_eq!("AKID0", chain.get_credentials().unwrap().get_access_key_id());
+        assert_eq!(1, calls.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_chain_refreshes_expiring_credentials() {
+        let (chain, calls) = chain(Some(Duration::minutes(1)));
+        chain.get_credentials().unwrap();
+        assert_eq!("AKID1", chain.get_credentials().unwrap().get_access_key_id());
+        assert_eq!(2, calls.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_chain_failure() {
+        let chain = DefaultCredentialsChain::with_providers(vec![Box::new(FailingProvider)]);
+        match chain.get_credentials() {
+            Err(ECSError::Credentials(message)) => assert!(message.contains("nothing here")),
+            other => panic!("expected a credentials error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_metadata_credentials() {
+        let credentials = parse_metadata_credentials(
+            "{\"Code\":\"Success\",\"AccessKeyId\":\"ASIAEXAMPLE\",\"SecretAccessKey\":\"secret\",\
+              \"Token\":\"token\",\"Expiration\":\"2026-10-18T12:00:00Z\"}").unwrap();
+        assert_eq!("ASIAEXAMPLE", credentials.get_access_key_id());
+        assert_eq!(Some(String::from("token")), *credentials.get_session_token());
+        let expiration = time::strptime("2026-10-18T12:00:00Z", "%Y-%m-%dT%H:%M:%SZ").unwrap().to_timespec();
+        assert_eq!(Some(expiration), *credentials.get_expiration());
+    }
+
+    #[test]
+    fn test_debug_redacts_secrets() {
+        let mut credentials = Credentials::new(String::from("AKIDEXAMPLE"), String::from("top-secret"));
+        credentials.set_session_token(String::from("session-secret"));
+        let debug = format!("{:?}", credentials);
+        assert!(debug.contains("AKIDEXAMPLE"));
+        assert!(!debug.contains("top-secret"));
+        assert!(!debug.contains("session-secret"));
+    }
+}
@@ -1,4 +1,6 @@
-//! Defines the X-Amz-Target and X-Amz-Date custom HTTP headers using hyper macros
+//! Defines the X-Amz-Target, X-Amz-Date and X-Amz-Security-Token custom HTTP headers using hyper
+//! macros
 
 header! { (XAmzTarget, "X-Amz-Target") => [String] }
 header! { (XAmzDate, "X-Amz-Date") => [String] }
+header! { (XAmzSecurityToken, "X-Amz-Security-Token") => [String] }
@@ -3,3 +3,126 @@
 pub fn is_none<T>(field: &Option<T>) -> bool {
     field.is_none()
 }
+
+pub fn is_empty<T>(field: &Vec<T>) -> bool {
+    field.is_empty()
+}
+
+/// Defines an enum whose variants map one-to-one onto the string values ECS uses on the wire, along
+/// with Display, FromStr, serde::ser::Serialize and serde::de::Deserialize implementations that go
+/// through that string representation.
+macro_rules! string_enum {
+    (@impls $name:ident { $($variant:ident => $value:expr),+ }) => {
+        /// Parses the String representation used by ECS back into a variant.
+        impl ::std::str::FromStr for $name {
+            type Err = String;
+
+            fn from_str(value: &str) -> ::std::result::Result<$name, String> {
+                match value {
+                    $(v if v == $value => Ok($name::$variant),)+
+                    _ => Err(format!("unknown {} value: {}", stringify!($name), value)),
+                }
+            }
+        }
+
+        impl ::serde::ser::Serialize for $name {
+            fn serialize<S>(&self, serializer: &mut S) -> ::std::result::Result<(), S::Error>
+                where S: ::serde::ser::Serializer
+            {
+                serializer.serialize_str(&self.to_string())
+            }
+        }
+    };
+    ($(#[$attr:meta])* pub enum $name:ident { $($(#[$var_attr:meta])* $variant:ident => $value:expr),+ $(,)* }) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$var_attr])* $variant),+
+        }
+
+        /// Used primarily to map each variant to the String representation used by ECS.
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
+                let value = match self {
+                    $(&$name::$variant => $value),+
+                };
+
+                write!(f, "{}", value)
+            }
+        }
+
+        string_enum!(@impls $name { $($variant => $value),+ });
+
+        impl ::serde::de::Deserialize for $name {
+            fn deserialize<D>(deserializer: &mut D) -> ::std::result::Result<$name, D::Error>
+                where D: ::serde::de::Deserializer
+            {
+                struct StringEnumVisitor;
+
+                impl ::serde::de::Visitor for StringEnumVisitor {
+                    type Value = $name;
+
+                    fn visit_str<E>(&mut self, value: &str) -> ::std::result::Result<$name, E>
+                        where E: ::serde::de::Error
+                    {
+                        value.parse().map_err(|_| E::unknown_variant(value))
+                    }
+                }
+
+                deserializer.deserialize_str(StringEnumVisitor)
+            }
+        }
+    }
+}
+
+/// Defines an enum like string_enum! does, plus an Unknown(String) variant which a value this version
+/// of the crate does not know about deserializes to, rather than failing the whole response.
+/// FromStr still rejects unknown values, so typos in input are caught.
+///
+/// The Unknown variant means the enum cannot be Copy, so this is kept for the enums ECS extends
+/// often, such as the account setting names.
+macro_rules! open_string_enum {
+    ($(#[$attr:meta])* pub enum $name:ident { $($(#[$var_attr:meta])* $variant:ident => $value:expr),+ $(,)* }) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$var_attr])* $variant,)+
+            /// A value ECS sent which this version of the crate does not know about.
+            Unknown(String),
+        }
+
+        /// Used primarily to map each variant to the String representation used by ECS.
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
+                let value = match self {
+                    $(&$name::$variant => $value,)+
+                    &$name::Unknown(ref value) => &value[..],
+                };
+
+                write!(f, "{}", value)
+            }
+        }
+
+        string_enum!(@impls $name { $($variant => $value),+ });
+
+        impl ::serde::de::Deserialize for $name {
+            fn deserialize<D>(deserializer: &mut D) -> ::std::result::Result<$name, D::Error>
+                where D: ::serde::de::Deserializer
+            {
+                struct StringEnumVisitor;
+
+                impl ::serde::de::Visitor for StringEnumVisitor {
+                    type Value = $name;
+
+                    fn visit_str<E>(&mut self, value: &str) -> ::std::result::Result<$name, E>
+                        where E: ::serde::de::Error
+                    {
+                        Ok(value.parse().unwrap_or_else(|_| $name::Unknown(String::from(value))))
+                    }
+                }
+
+                deserializer.deserialize_str(StringEnumVisitor)
+            }
+        }
+    }
+}
@@ -0,0 +1,284 @@
+//! Encodes and decodes the binary messages exchanged over a Session Manager data channel.  Every
+//! message is a fixed-size, big-endian header followed by a payload:
+//!
+//! ```text
+//! offset  size  field
+//!      0     4  header length (always 116, the offset of the payload length field)
+//!      4    32  message type, padded with spaces
+//!     36     4  schema version
+//!     40     8  created date, in milliseconds since the epoch
+//!     48     8  sequence number
+//!     56     8  flags
+//!     64    16  message ID (a UUID with its two 8-byte halves swapped)
+//!     80    32  SHA256 digest of the payload
+//!    112     4  payload type
+//!    116     4  payload length
+//!    120     *  payload
+//! ```
+
+use std::io::{Error, ErrorKind, Result};
+use sodiumoxide::crypto::hash::sha256;
+use sodiumoxide::randombytes;
+use time;
+
+/// The message type of data sent from the client to the command.
+pub const INPUT_STREAM_DATA: &'static str = "input_stream_data";
+/// The message type of data sent from the command to the client.
+pub const OUTPUT_STREAM_DATA: &'static str = "output_stream_data";
+/// The message type used to acknowledge a stream data message.
+pub const ACKNOWLEDGE: &'static str = "acknowledge";
+/// The message type sent when the session has ended.
+pub const CHANNEL_CLOSED: &'static str = "channel_closed";
+/// The message type asking the client to resume sending input.
+pub const START_PUBLICATION: &'static str = "start_publication";
+/// The message type asking the client to stop sending input for a while.
+pub const PAUSE_PUBLICATION: &'static str = "pause_publication";
+
+/// The value of the header length field, which is the offset of the payload length field.
+const HEADER_LENGTH: u32 = 116;
+/// The length of the message type field.
+const MESSAGE_TYPE_LENGTH: usize = 32;
+/// The schema version of the messages this module produces.
+const SCHEMA_VERSION: u32 = 1;
+
+/// The kind of payload carried by a stream data message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PayloadType {
+    Undefined,
+    Output,
+    Error,
+    Size,
+    Parameter,
+    HandshakeRequest,
+    HandshakeResponse,
+    HandshakeComplete,
+    EncChallengeRequest,
+    EncChallengeResponse,
+    Flag,
+    StdErr,
+    ExitCode,
+}
+
+impl PayloadType {
+    /// Gets the numeric code of the payload type which is written into the message header.
+    pub fn to_code(&self) -> u32 {
+        match self {
+            &PayloadType::Undefined => 0,
+            &PayloadType::Output => 1,
+            &PayloadType::Error => 2,
+            &PayloadType::Size => 3,
+            &PayloadType::Parameter => 4,
+            &PayloadType::HandshakeRequest => 5,
+            &PayloadType::HandshakeResponse => 6,
+            &PayloadType::HandshakeComplete => 7,
+            &PayloadType::EncChallengeRequest => 8,
+            &PayloadType::EncChallengeResponse => 9,
+            &PayloadType::Flag => 10,
+            &PayloadType::StdErr => 11,
+            &PayloadType::ExitCode => 12,
+        }
+    }
+
+    /// Gets the payload type with the given numeric code.
+    pub fn from_code(code: u32) -> Result<PayloadType> {
+        let payload_type = match code {
+            0 => PayloadType::Undefined,
+            1 => PayloadType::Output,
+            2 => PayloadType::Error,
+            3 => PayloadType::Size,
+            4 => PayloadType::Parameter,
+            5 => PayloadType::HandshakeRequest,
+            6 => PayloadType::HandshakeResponse,
+            7 => PayloadType::HandshakeComplete,
+            8 => PayloadType::EncChallengeRequest,
+            9 => PayloadType::EncChallengeResponse,
+            10 => PayloadType::Flag,
+            11 => PayloadType::StdErr,
+            12 => PayloadType::ExitCode,
+            _ => return Err(invalid_data(format!("unknown payload type {}", code))),
+        };
+        Ok(payload_type)
+    }
+}
+
+/// A single message sent over a data channel.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClientMessage {
+    /// The type of the message, e.g. input_stream_data.
+    pub message_type: String,
+    /// The schema version of the message.
+    pub schema_version: u32,
+    /// The time the message was created, in milliseconds since the epoch.
+    pub created_date: u64,
+    /// The position of the message in its stream.
+    pub sequence_number: i64,
+    /// Flags describing the message's position, e.g. the start or end of a stream.
+    pub flags: u64,
+    /// The unique ID of the message.
+    pub message_id: [u8; 16],
+    /// The kind of payload the message carries.
+    pub payload_type: PayloadType,
+    /// The payload of the message.
+    pub payload: Vec<u8>,
+}
+
+impl ClientMessage {
+    /// Creates a message of the given type with a fresh message ID and the current time.
+    pub fn new(message_type: &str, sequence_number: i64, payload_type: PayloadType, payload: Vec<u8>) -> Self {
+        let now = time::get_time();
+        ClientMessage {
+            message_type: String::from(message_type),
+            schema_version: SCHEMA_VERSION,
+            created_date: (now.sec as u64) * 1000 + (now.nsec as u64) / 1000000,
+            sequence_number: sequence_number,
+            flags: 0,
+            message_id: new_message_id(),
+            payload_type: payload_type,
+            payload: payload,
+        }
+    }
+
+    /// Serializes the message to the binary format described in the module documentation.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LENGTH as usize + 4 + self.payload.len());
+        put_u32(&mut bytes, HEADER_LENGTH);
+        let mut message_type = self.message_type.clone().into_bytes();
+        message_type.resize(MESSAGE_TYPE_LENGTH, b' ');
+        bytes.extend_from_slice(&message_type);
+        put_u32(&mut bytes, self.schema_version);
+        put_u64(&mut bytes, self.created_date);
+        put_u64(&mut bytes, self.sequence_number as u64);
+        put_u64(&mut bytes, self.flags);
+        bytes.extend_from_slice(&self.message_id[8..]);
+        bytes.extend_from_slice(&self.message_id[..8]);
+        bytes.extend_from_slice(&sha256::hash(&self.payload).0);
+        put_u32(&mut bytes, self.payload_type.to_code());
+        put_u32(&mut bytes, self.payload.len() as u32);
+        bytes.extend_from_slice(&self.payload);
+        bytes
+    }
+
+    /// Deserializes a message from the binary format described in the module documentation,
+    /// checking the payload against its digest.
+    pub fn deserialize(bytes: &[u8]) -> Result<ClientMessage> {
+        if bytes.len() < HEADER_LENGTH as usize + 4 {
+            return Err(invalid_data(format!("message of {} bytes is shorter than its header", bytes.len())));
+        }
+        let header_length = get_u32(bytes, 0) as usize;
+        if header_length + 4 > bytes.len() {
+            return Err(invalid_data(format!("header length {} exceeds message length", header_length)));
+        }
+        let message_type = String::from_utf8_lossy(&bytes[4..36]).trim_right_matches(|c| c == ' ' || c == '\0')
+                                                                 .to_string();
+        let mut message_id = [0u8; 16];
+        message_id[..8].copy_from_slice(&bytes[72..80]);
+        message_id[8..].copy_from_slice(&bytes[64..72]);
+        let payload_length = get_u32(bytes, header_length) as usize;
+        let payload_start = header_length + 4;
+        if payload_start + payload_length > bytes.len() {
+            return Err(invalid_data(format!("payload length {} exceeds message length", payload_length)));
+        }
+        let payload = bytes[payload_start..payload_start + payload_length].to_vec();
+        if sha256::hash(&payload).0[..] != bytes[80..112] {
+            return Err(invalid_data(String::from("payload does not match its digest")));
+        }
+        Ok(ClientMessage {
+            message_type: message_type,
+            schema_version: get_u32(bytes, 36),
+            created_date: get_u64(bytes, 40),
+            sequence_number: get_u64(bytes, 48) as i64,
+            flags: get_u64(bytes, 56),
+            message_id: message_id,
+            payload_type: try!(PayloadType::from_code(get_u32(bytes, 112))),
+            payload: payload,
+        })
+    }
+
+    /// Gets the message ID formatted as a UUID string.
+    pub fn message_id_string(&self) -> String {
+        format_uuid(&self.message_id)
+    }
+}
+
+/// Generates a random (version 4) UUID.
+pub fn new_message_id() -> [u8; 16] {
+    let mut id = [0u8; 16];
+    id.copy_from_slice(&randombytes::randombytes(16));
+    id[6] = (id[6] & 0x0f) | 0x40;
+    id[8] = (id[8] & 0x3f) | 0x80;
+    id
+}
+
+/// Formats a UUID in its canonical 8-4-4-4-12 hexadecimal form.
+pub fn format_uuid(id: &[u8; 16]) -> String {
+    let hex: Vec<String> = id.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}-{}-{}-{}-{}", hex[0..4].concat(), hex[4..6].concat(), hex[6..8].concat(),
+            hex[8..10].concat(), hex[10..16].concat())
+}
+
+fn invalid_data(message: String) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+fn put_u32(bytes: &mut Vec<u8>, value: u32) {
+    for shift in [24, 16, 8, 0].iter() {
+        bytes.push((value >> *shift) as u8);
+    }
+}
+
+fn put_u64(bytes: &mut Vec<u8>, value: u64) {
+    put_u32(bytes, (value >> 32) as u32);
+    put_u32(bytes, value as u32);
+}
+
+fn get_u32(bytes: &[u8], offset: usize) -> u32 {
+    bytes[offset..offset + 4].iter().fold(0, |acc, &b| (acc << 8) | b as u32)
+}
+
+fn get_u64(bytes: &[u8], offset: usize) -> u64 {
+    ((get_u32(bytes, offset) as u64) << 32) | get_u32(bytes, offset + 4) as u64
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ClientMessage, PayloadType, INPUT_STREAM_DATA, format_uuid};
+
+    #[test]
+    fn test_round_trip() {
+        let message = ClientMessage::new(INPUT_STREAM_DATA, 7, PayloadType::Output, b"ls -l\n".to_vec());
+        let bytes = message.serialize();
+        assert_eq!(120 + 6, bytes.len());
+        assert_eq!(b"input_stream_data               ", &bytes[4..36]);
+        assert_eq!(message, ClientMessage::deserialize(&bytes).unwrap());
+    }
+
+    #[test]
+    fn test_payload_type_codes() {
+        assert_eq!(5, PayloadType::HandshakeRequest.to_code());
+        assert_eq!(PayloadType::ExitCode, PayloadType::from_code(12).unwrap());
+        assert!(PayloadType::from_code(99).is_err());
+    }
+
+    #[test]
+    fn test_rejects_tampered_payload() {
+        let message = ClientMessage::new(INPUT_STREAM_DATA, 0, PayloadType::Output, b"abc".to_vec());
+        let mut bytes = message.serialize();
+        let last = bytes.len() - 1;
+        bytes[last] = b'x';
+        assert!(ClientMessage::deserialize(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_rejects_truncated_message() {
+        let message = ClientMessage::new(INPUT_STREAM_DATA, 0, PayloadType::Output, b"abc".to_vec());
+        let bytes = message.serialize();
+        assert!(ClientMessage::deserialize(&bytes[..100]).is_err());
+        assert!(ClientMessage::deserialize(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_format_uuid() {
+        let id = [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef];
+        assert_eq!("12345678-9abc-def0-0123-456789abcdef", format_uuid(&id));
+    }
+}
@@ -0,0 +1,405 @@
+//! This module speaks the Session Manager data channel protocol used by ExecuteCommand sessions.
+//! Once ECS has returned a Session, a DataChannel connects to its stream URL over a websocket,
+//! authenticates with the session's token, and then exchanges the binary messages defined in the
+//! message module: input typed by the user flows to the command, output flows back, and every
+//! output message is acknowledged.  The handshake the agent starts each session with is answered
+//! transparently while reading output.
+//!
+//! ```no_run
+//! use ecs_client::data_channel::{DataChannel, Output};
+//! # use ecs_client::types::session::Session;
+//! # fn run(session: &Session) -> std::io::Result<()> {
+//! let mut channel = try!(DataChannel::open(session));
+//! try!(channel.send_input(b"ls /\n"));
+//! loop {
+//!     match try!(channel.read_output()) {
+//!         Output::Stdout(bytes) | Output::Stderr(bytes) => print!("{}", String::from_utf8_lossy(&bytes)),
+//!         Output::Closed(_) => break,
+//!         _ => {}
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod message;
+pub mod websocket;
+
+use self::message::{ClientMessage, PayloadType};
+use self::websocket::{WebSocket, Message};
+use types::session::Session;
+use serde_json;
+use serde_json::value::Value;
+use std::collections::BTreeMap;
+use std::io::{self, Error, ErrorKind};
+
+/// The client version reported to the agent.
+const CLIENT_VERSION: &'static str = "1.2.0.0";
+/// The schema version of the message which opens the data channel.
+const OPEN_SCHEMA_VERSION: &'static str = "1.0";
+/// The flags set on acknowledge messages.
+const ACKNOWLEDGE_FLAGS: u64 = 3;
+/// The client action which tells the client what kind of session it is in.
+const SESSION_TYPE_ACTION: &'static str = "SessionType";
+/// The status reported for a client action which was carried out.
+const ACTION_SUCCESS: i32 = 1;
+/// The status reported for a client action this client does not support.
+const ACTION_UNSUPPORTED: i32 = 3;
+
+/// Something received from the command on the other end of a data channel.
+#[derive(Debug, PartialEq)]
+pub enum Output {
+    /// Bytes written by the command to its standard output (or to the terminal).
+    Stdout(Vec<u8>),
+    /// Bytes written by the command to its standard error.
+    Stderr(Vec<u8>),
+    /// The exit code of the command.
+    ExitCode(String),
+    /// The agent has finished the handshake; carries any message it wants shown to the user.
+    Ready(Option<String>),
+    /// The session has ended; carries the final message from the agent.
+    Closed(String),
+}
+
+/// The message which opens a data channel, sent as text immediately after connecting.
+#[allow(non_snake_case)]
+#[derive(Serialize)]
+struct OpenDataChannelInput {
+    MessageSchemaVersion: String,
+    RequestId: String,
+    TokenValue: String,
+    ClientId: String,
+    ClientVersion: String,
+}
+
+/// The payload of an acknowledge message.
+#[allow(non_snake_case)]
+#[derive(Serialize)]
+struct AcknowledgeContent {
+    AcknowledgedMessageType: String,
+    AcknowledgedMessageId: String,
+    AcknowledgedMessageSequenceNumber: i64,
+    IsSequentialMessage: bool,
+}
+
+/// The payload of a handshake request sent by the agent.
+#[allow(non_snake_case)]
+#[derive(Deserialize)]
+struct HandshakeRequest {
+    #[serde(default)]
+    RequestedClientActions: Vec<RequestedClientAction>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Deserialize)]
+struct RequestedClientAction {
+    ActionType: String,
+}
+
+/// The payload of the handshake response sent back to the agent.
+#[allow(non_snake_case)]
+#[derive(Serialize)]
+struct HandshakeResponse {
+    ClientVersion: String,
+    ProcessedClientActions: Vec<ProcessedClientAction>,
+    Errors: Vec<String>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize)]
+struct ProcessedClientAction {
+    ActionType: String,
+    ActionStatus: i32,
+    Error: String,
+}
+
+/// The payload of the message sent by the agent once the handshake is complete.
+#[allow(non_snake_case)]
+#[derive(Deserialize)]
+struct HandshakeComplete {
+    CustomerMessage: Option<String>,
+}
+
+/// The payload of a terminal size message.
+#[derive(Serialize)]
+struct SizeData {
+    cols: u16,
+    rows: u16,
+}
+
+/// A connection to the data channel of an ExecuteCommand session.
+pub struct DataChannel {
+    socket: WebSocket,
+    /// The sequence number of the next input message.
+    next_sequence_number: i64,
+    /// The sequence number of the next output message to hand to the caller.
+    expected_sequence_number: i64,
+    /// Output messages which arrived ahead of expected_sequence_number.
+    out_of_order: BTreeMap<i64, ClientMessage>,
+    /// Whether the agent has paused publication of input.
+    paused: bool,
+}
+
+impl DataChannel {
+    /// Connects to the data channel of a session returned by ecs_client::execute_command().
+    pub fn open(session: &Session) -> io::Result<DataChannel> {
+        DataChannel::open_url(session.get_stream_url(), session.get_token_value())
+    }
+
+    /// Connects to the data channel at the given stream URL, authenticating with the token.
+    pub fn open_url(stream_url: &str, token_value: &str) -> io::Result<DataChannel> {
+        let socket = try!(WebSocket::connect(stream_url));
+        DataChannel::over(socket, token_value)
+    }
+
+    /// Opens a data channel over an already connected websocket.
+    pub fn over(mut socket: WebSocket, token_value: &str) -> io::Result<DataChannel> {
+        let open = OpenDataChannelInput {
+            MessageSchemaVersion: String::from(OPEN_SCHEMA_VERSION),
+            RequestId: message::format_uuid(&message::new_message_id()),
+            TokenValue: String::from(token_value),
+            ClientId: message::format_uuid(&message::new_message_id()),
+            ClientVersion: String::from(CLIENT_VERSION),
+        };
+        try!(socket.send_text(&try!(to_json(&open))));
+        Ok(DataChannel {
+            socket: socket,
+            next_sequence_number: 0,
+            expected_sequence_number: 0,
+            out_of_order: BTreeMap::new(),
+            paused: false,
+        })
+    }
+
+    /// Sends bytes to the command, as if typed at its terminal.
+    pub fn send_input(&mut self, input: &[u8]) -> io::Result<()> {
+        if self.paused {
+            return Err(Error::new(ErrorKind::WouldBlock, "the agent has paused input on this data channel"));
+        }
+        self.send_stream_data(PayloadType::Output, input.to_vec())
+    }
+
+    /// Tells the command the size of the user's terminal.
+    pub fn resize(&mut self, cols: u16, rows: u16) -> io::Result<()> {
+        let size = try!(to_json(&SizeData { cols: cols, rows: rows }));
+        self.send_stream_data(PayloadType::Size, size.into_bytes())
+    }
+
+    /// Whether the agent has asked the client to stop sending input for now.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Blocks until the next piece of output is available.  Acknowledgements and the handshake
+    /// are handled along the way, and output is always returned in sequence order.
+    pub fn read_output(&mut self) -> io::Result<Output> {
+        loop {
+            if let Some(message) = self.out_of_order.remove(&self.expected_sequence_number) {
+                self.expected_sequence_number += 1;
+                if let Some(output) = try!(self.handle_output(message)) {
+                    return Ok(output);
+                }
+                continue;
+            }
+
+            let bytes = match try!(self.socket.receive()) {
+                Message::Binary(bytes) => bytes,
+                Message::Text(_) => continue,
+                Message::Close => return Ok(Output::Closed(String::from("the data channel was closed"))),
+            };
+            let message = try!(ClientMessage::deserialize(&bytes));
+            let message_type = message.message_type.clone();
+            match &message_type[..] {
+                message::OUTPUT_STREAM_DATA => {
+                    try!(self.acknowledge(&message));
+                    if message.sequence_number >= self.expected_sequence_number {
+                        self.out_of_order.insert(message.sequence_number, message);
+                    }
+                }
+                message::CHANNEL_CLOSED => {
+                    let output = serde_json::from_slice::<Value>(&message.payload).ok()
+                        .and_then(|v| v.find("Output").and_then(|o| o.as_string()).map(String::from))
+                        .unwrap_or_else(String::new);
+                    return Ok(Output::Closed(output));
+                }
+                message::PAUSE_PUBLICATION => self.paused = true,
+                message::START_PUBLICATION => self.paused = false,
+                // acknowledgements of our input need no action since input is not resent
+                _ => {}
+            }
+        }
+    }
+
+    /// Closes the websocket under the data channel.
+    pub fn close(mut self) -> io::Result<()> {
+        self.socket.close()
+    }
+
+    /// Turns an in-sequence output message into Output for the caller, answering handshake
+    /// messages along the way.
+    fn handle_output(&mut self, message: ClientMessage) -> io::Result<Option<Output>> {
+        let output = match message.payload_type {
+            PayloadType::Output => Some(Output::Stdout(message.payload)),
+            PayloadType::StdErr => Some(Output::Stderr(message.payload)),
+            PayloadType::ExitCode => Some(Output::ExitCode(String::from_utf8_lossy(&message.payload).into_owned())),
+            PayloadType::HandshakeRequest => {
+                try!(self.answer_handshake(&message.payload));
+                None
+            }
+            PayloadType::HandshakeComplete => {
+                let complete: HandshakeComplete = try!(from_json(&message.payload));
+                Some(Output::Ready(complete.CustomerMessage))
+            }
+            _ => None,
+        };
+        Ok(output)
+    }
+
+    /// Answers the agent's handshake request.  Only the SessionType action is supported; in
+    /// particular sessions requiring KMS encryption of the stream are reported as unsupported.
+    fn answer_handshake(&mut self, payload: &[u8]) -> io::Result<()> {
+        let request: HandshakeRequest = try!(from_json(payload));
+        let processed = request.RequestedClientActions.into_iter().map(|action| {
+            if action.ActionType == SESSION_TYPE_ACTION {
+                ProcessedClientAction {
+                    ActionType: action.ActionType,
+                    ActionStatus: ACTION_SUCCESS,
+                    Error: String::new(),
+                }
+            } else {
+                ProcessedClientAction {
+                    Error: format!("{} is not supported by this client", action.ActionType),
+                    ActionType: action.ActionType,
+                    ActionStatus: ACTION_UNSUPPORTED,
+                }
+            }
+        }).collect();
+        let response = HandshakeResponse {
+            ClientVersion: String::from(CLIENT_VERSION),
+            ProcessedClientActions: processed,
+            Errors: Vec::new(),
+        };
+        let response = try!(to_json(&response));
+        self.send_stream_data(PayloadType::HandshakeResponse, response.into_bytes())
+    }
+
+    fn send_stream_data(&mut self, payload_type: PayloadType, payload: Vec<u8>) -> io::Result<()> {
+        let message = ClientMessage::new(message::INPUT_STREAM_DATA, self.next_sequence_number, payload_type, payload);
+        self.next_sequence_number += 1;
+        self.socket.send_binary(&message.serialize())
+    }
+
+    fn acknowledge(&mut self, message: &ClientMessage) -> io::Result<()> {
+        let content = AcknowledgeContent {
+            AcknowledgedMessageType: message.message_type.clone(),
+            AcknowledgedMessageId: message.message_id_string(),
+            AcknowledgedMessageSequenceNumber: message.sequence_number,
+            IsSequentialMessage: true,
+        };
+        let mut ack = ClientMessage::new(message::ACKNOWLEDGE, 0, PayloadType::Undefined,
+                                         try!(to_json(&content)).into_bytes());
+        ack.flags = ACKNOWLEDGE_FLAGS;
+        self.socket.send_binary(&ack.serialize())
+    }
+}
+
+fn to_json<T: ::serde::ser::Serialize>(value: &T) -> io::Result<String> {
+    serde_json::to_string(value).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+}
+
+fn from_json<T: ::serde::de::Deserialize>(bytes: &[u8]) -> io::Result<T> {
+    serde_json::from_slice(bytes).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DataChannel, Output};
+    use super::message::{self, ClientMessage, PayloadType};
+    use super::websocket::{self, OPCODE_BINARY, OPCODE_TEXT};
+    use serde_json;
+    use serde_json::value::Value;
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    /// Accepts one websocket connection on the listener, answering the opening handshake the way a
+    /// Session Manager endpoint would.
+    fn accept(listener: &TcpListener) -> TcpStream {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut byte = [0u8; 1];
+        while !request.ends_with(b"\r\n\r\n") {
+            stream.read(&mut byte).unwrap();
+            request.push(byte[0]);
+        }
+        assert!(String::from_utf8(request).unwrap().starts_with("GET /v1/data-channel/s-1 HTTP/1.1"));
+        stream.write_all(b"HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\r\n")
+              .unwrap();
+        stream
+    }
+
+    fn send(stream: &mut TcpStream, sequence_number: i64, payload_type: PayloadType, payload: &[u8]) {
+        let message = ClientMessage::new(message::OUTPUT_STREAM_DATA, sequence_number, payload_type, payload.to_vec());
+        websocket::write_frame(stream, OPCODE_BINARY, &message.serialize(), false).unwrap();
+    }
+
+    fn receive(stream: &mut TcpStream) -> ClientMessage {
+        let frame = websocket::read_frame(stream).unwrap();
+        assert_eq!(OPCODE_BINARY, frame.opcode);
+        ClientMessage::deserialize(&frame.payload).unwrap()
+    }
+
+    #[test]
+    fn test_session_against_local_stand_in() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("ws://{}/v1/data-channel/s-1", listener.local_addr().unwrap());
+
+        let agent = thread::spawn(move || {
+            let mut stream = accept(&listener);
+
+            // the client opens the channel with its token
+            let open = websocket::read_frame(&mut stream).unwrap();
+            assert_eq!(OPCODE_TEXT, open.opcode);
+            let open: Value = serde_json::from_slice(&open.payload).unwrap();
+            assert_eq!(Some("secret-token"), open.find("TokenValue").and_then(|v| v.as_string()));
+
+            // handshake
+            send(&mut stream, 0, PayloadType::HandshakeRequest,
+                 b"{\"AgentVersion\":\"3.0\",\"RequestedClientActions\":[{\"ActionType\":\"SessionType\",\
+                    \"ActionParameters\":{\"SessionType\":\"InteractiveCommands\"}}]}");
+            let ack = receive(&mut stream);
+            assert_eq!(message::ACKNOWLEDGE, ack.message_type);
+            let response = receive(&mut stream);
+            assert_eq!(PayloadType::HandshakeResponse, response.payload_type);
+            let response: Value = serde_json::from_slice(&response.payload).unwrap();
+            let status = response.find("ProcessedClientActions").unwrap().as_array().unwrap()[0]
+                                 .find("ActionStatus").unwrap().as_i64();
+            assert_eq!(Some(1), status);
+            send(&mut stream, 1, PayloadType::HandshakeComplete, b"{\"CustomerMessage\":\"hello\"}");
+            receive(&mut stream);
+
+            // input from the client, then output sent out of order
+            let input = receive(&mut stream);
+            assert_eq!(message::INPUT_STREAM_DATA, input.message_type);
+            assert_eq!(b"ls\n".to_vec(), input.payload);
+            send(&mut stream, 3, PayloadType::StdErr, b"oops\n");
+            send(&mut stream, 2, PayloadType::Output, b"bin\n");
+            let first_ack = receive(&mut stream);
+            let ack: Value = serde_json::from_slice(&first_ack.payload).unwrap();
+            assert_eq!(Some(3), ack.find("AcknowledgedMessageSequenceNumber").and_then(|v| v.as_i64()));
+            receive(&mut stream);
+
+            let closed = ClientMessage::new(message::CHANNEL_CLOSED, 0, PayloadType::Undefined,
+                                            b"{\"Output\":\"Exiting session\"}".to_vec());
+            websocket::write_frame(&mut stream, OPCODE_BINARY, &closed.serialize(), false).unwrap();
+        });
+
+        let mut channel = DataChannel::open_url(&url, "secret-token").unwrap();
+        assert_eq!(Output::Ready(Some(String::from("hello"))), channel.read_output().unwrap());
+        channel.send_input(b"ls\n").unwrap();
+        assert_eq!(Output::Stdout(b"bin\n".to_vec()), channel.read_output().unwrap());
+        assert_eq!(Output::Stderr(b"oops\n".to_vec()), channel.read_output().unwrap());
+        assert_eq!(Output::Closed(String::from("Exiting session")), channel.read_output().unwrap());
+        agent.join().unwrap();
+    }
+}
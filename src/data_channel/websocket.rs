@@ -0,0 +1,475 @@
+//! A minimal websocket (RFC 6455) client, implementing just enough of the protocol to carry a
+//! Session Manager data channel: the opening handshake, masked client frames, fragmented
+//! messages, and answering pings.  wss:// URLs are secured with hyper's OpenSSL connector.
+//!
+//! Connecting and the opening handshake are bounded by a timeout, and the server's
+//! Sec-WebSocket-Accept header is checked against the key sent with the handshake as RFC 6455
+//! requires.  Once the handshake is done, reads wait for as long as the command stays quiet unless
+//! a read timeout is set with set_read_timeout().
+
+use std::io::{self, Read, Write, Error, ErrorKind};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+use hyper::net::{HttpStream, Openssl, SslClient};
+use openssl::crypto::hash::{self, Type};
+use sodiumoxide::randombytes;
+
+/// The opcode of a frame continuing a fragmented message.
+pub const OPCODE_CONTINUATION: u8 = 0x0;
+/// The opcode of a frame carrying UTF-8 text.
+pub const OPCODE_TEXT: u8 = 0x1;
+/// The opcode of a frame carrying binary data.
+pub const OPCODE_BINARY: u8 = 0x2;
+/// The opcode of a frame closing the connection.
+pub const OPCODE_CLOSE: u8 = 0x8;
+/// The opcode of a ping frame.
+pub const OPCODE_PING: u8 = 0x9;
+/// The opcode of a pong frame.
+pub const OPCODE_PONG: u8 = 0xA;
+
+/// The largest frame payload, and the largest reassembled message, accepted from a peer.  Data
+/// channel messages are at most a few KB, so anything bigger is treated as a protocol error rather
+/// than allocated.
+pub const MAX_PAYLOAD_LENGTH: u64 = 4 * 1024 * 1024;
+
+/// The number of seconds connect() allows for connecting and for each read and write of the
+/// opening handshake.
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// The max size of the server's response to the opening handshake, which is only a status line
+/// and a few headers.
+const MAX_HANDSHAKE_RESPONSE_BYTES: usize = 8192;
+
+/// The GUID RFC 6455 appends to Sec-WebSocket-Key to derive Sec-WebSocket-Accept.
+const ACCEPT_GUID: &'static str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// The characters used by base64 encoding.
+const BASE64_CHARS: &'static [u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// A bidirectional byte stream a websocket can be run over, such as a TcpStream or an SslStream.
+pub trait Stream: Read + Write + Send {}
+
+impl<T: Read + Write + Send> Stream for T {}
+
+/// A single websocket frame.
+#[derive(Debug, PartialEq)]
+pub struct Frame {
+    /// Whether this is the final frame of its message.
+    pub fin: bool,
+    /// The opcode of the frame.
+    pub opcode: u8,
+    /// The unmasked payload of the frame.
+    pub payload: Vec<u8>,
+}
+
+/// A complete message received over a websocket.
+#[derive(Debug, PartialEq)]
+pub enum Message {
+    Text(String),
+    Binary(Vec<u8>),
+    /// The peer closed the connection.
+    Close,
+}
+
+/// A client connection to a websocket server.
+pub struct WebSocket {
+    stream: Box<Stream>,
+    /// The TCP connection under the stream when connect() opened it, kept to adjust its timeouts.
+    tcp: Option<TcpStream>,
+}
+
+impl WebSocket {
+    /// Connects to a ws:// or wss:// URL and performs the opening handshake, allowing
+    /// DEFAULT_TIMEOUT_SECS for connecting and for each read and write.
+    pub fn connect(url: &str) -> io::Result<WebSocket> {
+        WebSocket::connect_with_timeout(url, Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+    }
+
+    /// Connects to a ws:// or wss:// URL and performs the opening handshake, allowing the timeout
+    /// for connecting and for each read of the handshake.  Writes keep the timeout afterwards,
+    /// while reads wait for the next message for as long as it takes.
+    pub fn connect_with_timeout(url: &str, timeout: Duration) -> io::Result<WebSocket> {
+        let (secure, host, port, resource) = try!(parse_url(url));
+        let tcp = try!(connect_tcp(&host, port, timeout));
+        try!(tcp.set_read_timeout(Some(timeout)));
+        try!(tcp.set_write_timeout(Some(timeout)));
+        let control = try!(tcp.try_clone());
+        let stream: Box<Stream> = if secure {
+            let ssl = try!(Openssl::default().wrap_client(HttpStream(tcp), &host)
+                                             .map_err(|e| Error::new(ErrorKind::Other, e)));
+            Box::new(ssl)
+        } else {
+            Box::new(tcp)
+        };
+        let host_header = if port == 80 || port == 443 { host } else { format!("{}:{}", host, port) };
+        let mut socket = try!(WebSocket::handshake(stream, &host_header, &resource));
+        try!(control.set_read_timeout(None));
+        socket.tcp = Some(control);
+        Ok(socket)
+    }
+
+    /// Performs the opening handshake over an already connected stream.  Any timeouts are up to
+    /// whoever connected the stream.
+    pub fn handshake(mut stream: Box<Stream>, host: &str, resource: &str) -> io::Result<WebSocket> {
+        let key = base64_encode(&randombytes::randombytes(16));
+        let request = format!("GET {} HTTP/1.1\r\nHost: {}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+                               Sec-WebSocket-Key: {}\r\nSec-WebSocket-Version: 13\r\n\r\n",
+                              resource, host, key);
+        try!(stream.write_all(request.as_bytes()));
+        try!(stream.flush());
+
+        // read the response a byte at a time so nothing past the headers is consumed
+        let mut response = Vec::new();
+        let mut byte = [0u8; 1];
+        while !response.ends_with(b"\r\n\r\n") {
+            if response.len() >= MAX_HANDSHAKE_RESPONSE_BYTES {
+                return Err(Error::new(ErrorKind::InvalidData, "websocket handshake response is too long"));
+            }
+            if try!(stream.read(&mut byte)) == 0 {
+                return Err(Error::new(ErrorKind::UnexpectedEof, "connection closed during websocket handshake"));
+            }
+            response.push(byte[0]);
+        }
+        let response = String::from_utf8_lossy(&response).into_owned();
+        let status_line = response.lines().next().unwrap_or("");
+        if status_line.split_whitespace().nth(1) != Some("101") {
+            return Err(Error::new(ErrorKind::ConnectionRefused,
+                                  format!("websocket handshake rejected: {}", status_line)));
+        }
+        let accept = response.lines().skip(1).filter_map(|line| {
+            let mut parts = line.splitn(2, ':');
+            match (parts.next(), parts.next()) {
+                (Some(name), Some(value)) if name.trim().to_lowercase() == "sec-websocket-accept" => {
+                    Some(value.trim())
+                }
+                _ => None,
+            }
+        }).next();
+        if accept != Some(&accept_key(&key)[..]) {
+            return Err(Error::new(ErrorKind::InvalidData,
+                                  format!("websocket handshake has the wrong Sec-WebSocket-Accept: {:?}", accept)));
+        }
+        Ok(WebSocket {
+            stream: stream,
+            tcp: None,
+        })
+    }
+
+    /// Sets how long receive() waits for data before failing with a timeout, or None to wait
+    /// forever.  Has no effect on a websocket created with handshake(), whose stream is not known
+    /// to be a TcpStream.
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        match self.tcp {
+            Some(ref tcp) => tcp.set_read_timeout(timeout),
+            None => Ok(()),
+        }
+    }
+
+    /// Sends a text message.
+    pub fn send_text(&mut self, text: &str) -> io::Result<()> {
+        write_frame(&mut self.stream, OPCODE_TEXT, text.as_bytes(), true)
+    }
+
+    /// Sends a binary message.
+    pub fn send_binary(&mut self, data: &[u8]) -> io::Result<()> {
+        write_frame(&mut self.stream, OPCODE_BINARY, data, true)
+    }
+
+    /// Receives the next complete message, reassembling fragments and answering any pings which
+    /// arrive in the meantime.
+    pub fn receive(&mut self) -> io::Result<Message> {
+        let mut opcode = None;
+        let mut payload = Vec::new();
+        loop {
+            let frame = try!(read_frame(&mut self.stream));
+            match frame.opcode {
+                OPCODE_PING => {
+                    try!(write_frame(&mut self.stream, OPCODE_PONG, &frame.payload, true));
+                    continue;
+                }
+                OPCODE_PONG => continue,
+                OPCODE_CLOSE => {
+                    // echo the close frame; the peer may already have gone away
+                    let _ = write_frame(&mut self.stream, OPCODE_CLOSE, &[], true);
+                    return Ok(Message::Close);
+                }
+                OPCODE_CONTINUATION if opcode.is_none() => {
+                    return Err(Error::new(ErrorKind::InvalidData, "continuation frame without a message"));
+                }
+                OPCODE_CONTINUATION => {}
+                other => opcode = Some(other),
+            }
+            if (payload.len() + frame.payload.len()) as u64 > MAX_PAYLOAD_LENGTH {
+                return Err(Error::new(ErrorKind::InvalidData,
+                                      format!("websocket message exceeds the limit of {} bytes", MAX_PAYLOAD_LENGTH)));
+            }
+            payload.extend_from_slice(&frame.payload);
+            if frame.fin {
+                break;
+            }
+        }
+        match opcode {
+            Some(OPCODE_TEXT) => {
+                String::from_utf8(payload).map(Message::Text).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+            }
+            Some(OPCODE_BINARY) => Ok(Message::Binary(payload)),
+            other => Err(Error::new(ErrorKind::InvalidData, format!("unsupported websocket opcode {:?}", other))),
+        }
+    }
+
+    /// Sends a close frame to the server.
+    pub fn close(&mut self) -> io::Result<()> {
+        write_frame(&mut self.stream, OPCODE_CLOSE, &[], true)
+    }
+}
+
+/// Writes a single, final frame.  Frames sent by a client must be masked.
+pub fn write_frame<W: Write + ?Sized>(writer: &mut W, opcode: u8, payload: &[u8], mask: bool) -> io::Result<()> {
+    let mut frame = vec![0x80 | opcode];
+    let mask_bit = if mask { 0x80 } else { 0 };
+    if payload.len() < 126 {
+        frame.push(mask_bit | payload.len() as u8);
+    } else if payload.len() <= 0xffff {
+        frame.push(mask_bit | 126);
+        frame.push((payload.len() >> 8) as u8);
+        frame.push(payload.len() as u8);
+    } else {
+        frame.push(mask_bit | 127);
+        for shift in (0..8).rev() {
+            frame.push(((payload.len() as u64) >> (shift * 8)) as u8);
+        }
+    }
+    if mask {
+        let key = randombytes::randombytes(4);
+        frame.extend_from_slice(&key);
+        frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ key[i % 4]));
+    } else {
+        frame.extend_from_slice(payload);
+    }
+    try!(writer.write_all(&frame));
+    writer.flush()
+}
+
+/// Reads a single frame, unmasking its payload if it was masked.
+pub fn read_frame<R: Read + ?Sized>(reader: &mut R) -> io::Result<Frame> {
+    let mut header = [0u8; 2];
+    try!(reader.read_exact(&mut header));
+    let fin = header[0] & 0x80 != 0;
+    let opcode = header[0] & 0x0f;
+    let masked = header[1] & 0x80 != 0;
+    let length = match header[1] & 0x7f {
+        126 => {
+            let mut extended = [0u8; 2];
+            try!(reader.read_exact(&mut extended));
+            ((extended[0] as u64) << 8) | extended[1] as u64
+        }
+        127 => {
+            let mut extended = [0u8; 8];
+            try!(reader.read_exact(&mut extended));
+            extended.iter().fold(0, |acc, &b| (acc << 8) | b as u64)
+        }
+        length => length as u64,
+    };
+    if length > MAX_PAYLOAD_LENGTH {
+        return Err(Error::new(ErrorKind::InvalidData,
+                              format!("websocket frame of {} bytes exceeds the limit of {} bytes",
+                                      length, MAX_PAYLOAD_LENGTH)));
+    }
+    let mut key = [0u8; 4];
+    if masked {
+        try!(reader.read_exact(&mut key));
+    }
+    let mut payload = vec![0u8; length as usize];
+    try!(reader.read_exact(&mut payload));
+    if masked {
+        for (i, byte) in payload.iter_mut().enumerate() {
+            *byte ^= key[i % 4];
+        }
+    }
+    Ok(Frame {
+        fin: fin,
+        opcode: opcode,
+        payload: payload,
+    })
+}
+
+/// Splits a ws:// or wss:// URL into (secure, host, port, resource).
+fn parse_url(url: &str) -> io::Result<(bool, String, u16, String)> {
+    let (secure, rest) = if url.starts_with("wss://") {
+        (true, &url[6..])
+    } else if url.starts_with("ws://") {
+        (false, &url[5..])
+    } else {
+        return Err(Error::new(ErrorKind::InvalidInput, format!("not a websocket URL: {}", url)));
+    };
+    let (authority, resource) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rfind(':') {
+        Some(i) => {
+            let port = try!(authority[i + 1..].parse().map_err(|e| Error::new(ErrorKind::InvalidInput, e)));
+            (&authority[..i], port)
+        }
+        None => (authority, if secure { 443 } else { 80 }),
+    };
+    Ok((secure, String::from(host), port, String::from(resource)))
+}
+
+/// Connects to the first address of the host which accepts within the timeout.
+fn connect_tcp(host: &str, port: u16, timeout: Duration) -> io::Result<TcpStream> {
+    let addresses: Vec<SocketAddr> = try!((host, port).to_socket_addrs()).collect();
+    let mut last_error = Error::new(ErrorKind::NotFound, format!("{} did not resolve to any address", host));
+    for address in addresses {
+        match TcpStream::connect_timeout(&address, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(error) => last_error = error,
+        }
+    }
+    Err(last_error)
+}
+
+/// Computes the Sec-WebSocket-Accept value a server must answer the given Sec-WebSocket-Key with.
+fn accept_key(key: &str) -> String {
+    base64_encode(&hash::hash(Type::SHA1, format!("{}{}", key, ACCEPT_GUID).as_bytes()))
+}
+
+/// Encodes bytes as padded base64, as required for the Sec-WebSocket-Key header.
+fn base64_encode(input: &[u8]) -> String {
+    let mut output = String::new();
+    for chunk in input.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let indices = [b[0] >> 2, ((b[0] & 0x03) << 4) | (b[1] >> 4), ((b[1] & 0x0f) << 2) | (b[2] >> 6), b[2] & 0x3f];
+        for (i, index) in indices.iter().enumerate() {
+            if i <= chunk.len() {
+                output.push(BASE64_CHARS[*index as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::{WebSocket, read_frame, write_frame, parse_url, base64_encode, accept_key, OPCODE_BINARY};
+    use std::io::{Cursor, ErrorKind, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    /// Serves one handshake on a local port, answering the request with the response the function
+    /// builds from the Sec-WebSocket-Key sent, and returns the ws:// URL of the server.
+    fn serve_handshake<F>(respond: F) -> String
+        where F: FnOnce(&str) -> String + Send + 'static
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut byte = [0u8; 1];
+            while !request.ends_with(b"\r\n\r\n") && stream.read(&mut byte).unwrap() == 1 {
+                request.push(byte[0]);
+            }
+            let request = String::from_utf8(request).unwrap();
+            let key = request.lines()
+                             .find(|line| line.starts_with("Sec-WebSocket-Key: "))
+                             .map(|line| &line["Sec-WebSocket-Key: ".len()..])
+                             .unwrap();
+            let _ = stream.write_all(respond(key).as_bytes());
+            // hold the connection open until the client is done with it
+            let _ = stream.read(&mut byte);
+        });
+        format!("ws://{}/", address)
+    }
+
+    #[test]
+    fn test_frame_round_trip() {
+        for length in [0, 125, 126, 65535, 65536].iter() {
+            let payload = vec![7u8; *length];
+            let mut bytes = Vec::new();
+            write_frame(&mut bytes, OPCODE_BINARY, &payload, true).unwrap();
+            let frame = read_frame(&mut Cursor::new(bytes)).unwrap();
+            assert!(frame.fin);
+            assert_eq!(OPCODE_BINARY, frame.opcode);
+            assert_eq!(payload, frame.payload);
+        }
+    }
+
+    #[test]
+    fn test_oversized_frame() {
+        // an unmasked binary frame claiming a 2^40 byte payload, with no payload following
+        let bytes = vec![0x80 | OPCODE_BINARY, 127, 0, 0, 1, 0, 0, 0, 0, 0];
+        let error = read_frame(&mut Cursor::new(bytes)).unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, error.kind());
+    }
+
+    #[test]
+    fn test_parse_url() {
+        assert_eq!((true, String::from("ssmmessages.us-west-2.amazonaws.com"), 443,
+                    String::from("/v1/data-channel/s-1?role=publish_subscribe")),
+                   parse_url("wss://ssmmessages.us-west-2.amazonaws.com/v1/data-channel/s-1?role=publish_subscribe").unwrap());
+        assert_eq!((false, String::from("127.0.0.1"), 8080, String::from("/")),
+                   parse_url("ws://127.0.0.1:8080").unwrap());
+        assert!(parse_url("https://example.com").is_err());
+    }
+
+    #[test]
+    fn test_accept_key() {
+        // the example from RFC 6455 section 1.3
+        assert_eq!("s3pPLMBiTxaQ9kYGzzhZRbK+xOo=", accept_key("dGhlIHNhbXBsZSBub25jZQ=="));
+    }
+
+    #[test]
+    fn test_handshake_accepted() {
+        let url = serve_handshake(|key| {
+            format!("HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+                     sec-websocket-accept: {}\r\n\r\n", accept_key(key))
+        });
+        assert!(WebSocket::connect(&url).is_ok());
+    }
+
+    #[test]
+    fn test_handshake_with_wrong_accept() {
+        let url = serve_handshake(|_| {
+            String::from("HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+                          Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=\r\n\r\n")
+        });
+        assert_eq!(ErrorKind::InvalidData, WebSocket::connect(&url).err().unwrap().kind());
+
+        let url = serve_handshake(|_| {
+            String::from("HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\n\r\n")
+        });
+        assert_eq!(ErrorKind::InvalidData, WebSocket::connect(&url).err().unwrap().kind());
+    }
+
+    #[test]
+    fn test_handshake_response_too_long() {
+        let url = serve_handshake(|_| {
+            format!("HTTP/1.1 101 Switching Protocols\r\nX-Padding: {}\r\n\r\n", "a".repeat(10000))
+        });
+        assert_eq!(ErrorKind::InvalidData, WebSocket::connect(&url).err().unwrap().kind());
+    }
+
+    #[test]
+    fn test_handshake_timeout() {
+        // the server accepts the connection but never answers the handshake
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("ws://{}/", listener.local_addr().unwrap());
+        let started = Instant::now();
+        assert!(WebSocket::connect_with_timeout(&url, Duration::from_millis(200)).is_err());
+        assert!(started.elapsed() < Duration::from_secs(5));
+        drop(listener);
+    }
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!("", base64_encode(b""));
+        assert_eq!("Zg==", base64_encode(b"f"));
+        assert_eq!("Zm8=", base64_encode(b"fo"));
+        assert_eq!("Zm9v", base64_encode(b"foo"));
+        assert_eq!("Zm9vYmFy", base64_encode(b"foobar"));
+    }
+}
@@ -1,18 +1,36 @@
 //! This module contains the ECSClient which can be used to make API requests to Amazon ECS.
 
 use region::Region;
+use builder::{self, ECSClientBuilder};
 use action::*;
-use custom_headers::{XAmzTarget, XAmzDate};
+use paginator::{Pages, Items};
+use waiter::Waiter;
+use retry::{self, RetryPolicy};
+use rate_limit::RateLimiter;
+use transport::{HttpTransport, HttpRequest, HttpResponse, HyperTransport};
+use response::{Response, ResponseMetadata};
+use endpoint::{Endpoint, EndpointResolver};
+use credentials::{Credentials, CredentialsProvider, DefaultCredentialsChain};
+use types::container_instance::ContainerInstance;
+use custom_headers::{XAmzTarget, XAmzDate, XAmzSecurityToken};
 use signature;
+use logging::{self, RequestContext};
+use interceptor::Interceptor;
+use metrics::{MetricsSink, OperationRecord};
 use error;
 
-use hyper;
-use hyper::header::{Headers, Host, AcceptEncoding, Encoding, qitem, ContentType, ContentLength, Authorization};
+use hyper::header::{Headers, AcceptEncoding, Encoding, qitem, ContentType, ContentLength, Authorization,
+                    UserAgent};
 use hyper::mime::{Mime, TopLevel, SubLevel};
 use serde_json;
+use log::LogLevel;
 use time;
+use std::io;
 use std::result;
-use std::io::Read;
+use std::usize;
+use std::thread;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// The service abbreviation string for Amazon ECS.
 const SERVICE_ABBREVIATION: &'static str = "ecs";
@@ -20,22 +38,83 @@ const SERVICE_ABBREVIATION: &'static str = "ecs";
 const AMZ_SUBLEVEL_CONTENT_TYPE: &'static str = "x-amz-json-1.1";
 /// The ECS API version this request is meant for.
 const ECS_API_VERSION: &'static str = "AmazonEC2ContainerServiceV20141113";
+/// The User-Agent header sent with every request, before any app ID.
+const USER_AGENT: &'static str = concat!("ecs_client/", env!("CARGO_PKG_VERSION"));
 
 /// A type alias to set the default error as an ECSError.
-type Result<T> = result::Result<T, error::ECSError>;
+pub type Result<T> = result::Result<T, error::ECSError>;
 
 #[derive(Debug)]
 pub struct ECSClient {
     region: Region,
-    client: hyper::Client
+    signing_region: Option<Region>,
+    endpoint_resolver: EndpointResolver,
+    credentials_provider: Box<CredentialsProvider>,
+    transport: Box<HttpTransport>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    user_agent: String,
+    operation_timeout: Option<Duration>,
+    interceptors: Vec<Box<Interceptor>>,
+    metrics_sink: Option<Arc<MetricsSink>>,
+}
+
+/// An operation in progress: the serialized request, the metadata recorded by its attempts so far
+/// and how many of them ECS throttled.  See ECSClient::start_operation().
+#[derive(Debug)]
+pub struct Operation {
+    action: ecs_action::ECSAction,
+    body: String,
+    metadata: ResponseMetadata,
+    start: Instant,
+    /// The number of attempts made so far.
+    attempt: u32,
+    throttles: u32,
 }
 
 impl ECSClient {
-    /// Creates a new ECSClient for the specified Region.
+    /// Creates a new ECSClient for the Region resolved from the environment, the shared config file
+    /// or instance metadata (see Region::from_env()), which signs its requests with credentials
+    /// from the DefaultCredentialsChain and connects through the proxies named in the environment
+    /// (see ProxyConfig::from_env()).  Fails if a proxy in the environment is invalid.
+    pub fn from_env() -> Result<ECSClient> {
+        ECSClient::builder().build()
+    }
+
+    /// Returns an ECSClientBuilder, for configuring timeouts, proxies, the User-Agent and the
+    /// other settings of a client in one place.
+    pub fn builder() -> ECSClientBuilder {
+        ECSClientBuilder::new()
+    }
+
+    /// Creates a new ECSClient for the specified Region, which signs its requests with credentials
+    /// from the DefaultCredentialsChain and connects through the proxies named in the environment.
+    /// If those are invalid, a warning is logged and the client connects directly.
     pub fn for_region(region: Region) -> ECSClient {
+        match ECSClient::builder().region(region.clone()).build() {
+            Ok(client) => client,
+            Err(error) => {
+                warn!("ignoring the proxy settings in the environment: {}", error);
+                ECSClient::with_transport(region, Box::new(HyperTransport::new()))
+            },
+        }
+    }
+
+    /// Creates a new ECSClient for the specified Region which sends its requests through the
+    /// given transport, such as a MockTransport in tests.
+    pub fn with_transport(region: Region, transport: Box<HttpTransport>) -> ECSClient {
         ECSClient {
             region: region,
-            client: hyper::Client::new(),
+            signing_region: None,
+            endpoint_resolver: EndpointResolver::new(),
+            credentials_provider: Box::new(DefaultCredentialsChain::new()),
+            transport: transport,
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: None,
+            user_agent: String::from(USER_AGENT),
+            operation_timeout: None,
+            interceptors: Vec::new(),
+            metrics_sink: None,
         }
     }
 
@@ -44,53 +123,400 @@ impl ECSClient {
         self.region = region;
     }
 
-    /// Lists all of your compute clusters on ECS.
-    pub fn list_clusters(&self, request: list_clusters::ListClustersRequest) -> list_clusters::ListClustersResponse {
-        let mut response = self.sign_and_send(ecs_action::ECSAction::ListClusters, request);
-        let mut response_body = String::new();
-        response.read_to_string(&mut response_body).unwrap();
-        println!("Response body...\n{}", response_body);
-        let list_clusters_response: list_clusters::ListClustersResponse = serde_json::from_str(&response_body).unwrap();
-        list_clusters_response
+    /// Signs requests for the given Region rather than the client's Region, e.g. when a custom
+    /// endpoint serves a Region other than the one it is named after.
+    pub fn set_signing_region(&mut self, signing_region: Region) {
+        self.signing_region = Some(signing_region);
+    }
+
+    /// Gets the Region for which requests are signed.
+    pub fn get_signing_region(&self) -> &Region {
+        self.signing_region.as_ref().unwrap_or(&self.region)
+    }
+
+    /// Sends requests to the given URL, such as "http://127.0.0.1:8080" for a local fake ECS
+    /// server or the DNS name of a VPC interface endpoint, instead of the endpoint resolved from
+    /// the environment or the client's Region.  Fails if the URL is not an http or https URL.
+    pub fn set_endpoint(&mut self, url: &str) -> result::Result<(), error::ValidationError> {
+        let endpoint = try!(Endpoint::parse(url));
+        self.endpoint_resolver.set_endpoint(endpoint);
+        Ok(())
+    }
+
+    /// Sets the EndpointResolver which chooses the endpoint of each request.
+    pub fn set_endpoint_resolver(&mut self, endpoint_resolver: EndpointResolver) {
+        self.endpoint_resolver = endpoint_resolver;
+    }
+
+    /// Sends requests to the FIPS endpoint of the client's Region, e.g.
+    /// ecs-fips.us-gov-west-1.amazonaws.com, unless an endpoint has been set.  Overrides the
+    /// AWS_USE_FIPS_ENDPOINT environment variable.
+    pub fn set_use_fips(&mut self, use_fips: bool) {
+        self.endpoint_resolver.set_use_fips(use_fips);
+    }
+
+    /// Sends requests to the dual-stack endpoint of the client's Region, which resolves to IPv6 as
+    /// well as IPv4 addresses, unless an endpoint has been set.  Overrides the
+    /// AWS_USE_DUALSTACK_ENDPOINT environment variable.
+    pub fn set_use_dual_stack(&mut self, use_dual_stack: bool) {
+        self.endpoint_resolver.set_use_dual_stack(use_dual_stack);
+    }
+
+    /// Sets the provider of the credentials requests are signed with.
+    pub fn set_credentials_provider(&mut self, credentials_provider: Box<CredentialsProvider>) {
+        self.credentials_provider = credentials_provider;
+    }
+
+    /// Sets the transport through which the client sends its requests.
+    pub fn set_transport(&mut self, transport: Box<HttpTransport>) {
+        self.transport = transport;
+    }
+
+    /// Sets the policy deciding whether and when failed requests are sent again.
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// Sets a RateLimiter which every request, including each retry, must acquire a token from
+    /// before it is sent.  The same RateLimiter may be shared by several clients.
+    pub fn set_rate_limiter(&mut self, rate_limiter: Arc<RateLimiter>) {
+        self.rate_limiter = Some(rate_limiter);
+    }
+
+    /// Appends "app/<app_id>" to the User-Agent header, identifying the application in CloudTrail
+    /// logs.  Fails unless the app ID is 1 to 50 visible ASCII characters.
+    pub fn set_app_id(&mut self, app_id: &str) -> result::Result<(), error::ValidationError> {
+        try!(builder::validate_app_id(app_id));
+        self.user_agent = format!("{} app/{}", USER_AGENT, app_id);
+        Ok(())
+    }
+
+    /// Bounds the total time an operation may take, including every retry.  Waiting for a token
+    /// from the RateLimiter fails at once if the token would not be available before the timeout,
+    /// no attempt is made once the timeout has passed, and no retry is made if its delay would
+    /// overrun the timeout.  An operation which runs out of time fails with an
+    /// io::ErrorKind::TimedOut error.  An attempt in flight is bounded by the read and write
+    /// timeouts of the transport, which ECSClientBuilder derives from the operation timeout
+    /// unless they are set.
+    pub fn set_operation_timeout(&mut self, operation_timeout: Duration) {
+        self.operation_timeout = Some(operation_timeout);
+    }
+
+    /// Registers an Interceptor whose hooks are called at each stage of every operation, after
+    /// those of the interceptors registered before it.
+    pub fn add_interceptor(&mut self, interceptor: Box<Interceptor>) {
+        self.interceptors.push(interceptor);
+    }
+
+    /// Sets a MetricsSink which a record of every operation is reported to, such as an
+    /// InMemoryMetrics.  The same sink may be shared by several clients.
+    pub fn set_metrics_sink(&mut self, metrics_sink: Arc<MetricsSink>) {
+        self.metrics_sink = Some(metrics_sink);
+    }
+
+    /// Walks every container instance registered with the given cluster and returns those whose
+    /// container agent is older than the given version, or which do not report an agent version
+    /// at all.  Pass each returned instance's ARN to update_container_agent() to update it.
+    pub fn find_outdated_agents(&self, cluster: String, latest_agent_version: &str)
+                                -> Result<Vec<ContainerInstance>> {
+        let request = list_container_instances::ListContainerInstancesRequest::for_cluster(cluster.clone());
+        let mut arns = self.list_container_instances_all(request);
+        arns.set_max_items(usize::MAX);
+        let arns = try!(arns.collect::<Result<Vec<String>>>());
+
+        let mut outdated = Vec::new();
+        for batch in arns.chunks(describe_container_instances::MAX_CONTAINER_INSTANCES_PER_REQUEST) {
+            // chunks() never yields an empty or oversized batch, so the request is always valid
+            let request = describe_container_instances::DescribeContainerInstancesRequest::new(
+                cluster.clone(), batch.to_vec()).unwrap();
+            let instances = try!(self.describe_container_instances(request)).into_container_instances();
+            outdated.extend(instances.into_iter()
+                                     .filter(|instance| instance.is_agent_older_than(latest_agent_version)));
+        }
+        Ok(outdated)
+    }
+
+    /// Polls DescribeServices with the given request until every service is stable, i.e. has
+    /// finished rolling out and is running its desired count of tasks.  Pass
+    /// waiter::services_stable() for the semantics of `aws ecs wait services-stable`.
+    pub fn wait_until_services_stable(&self,
+                                      request: describe_services::DescribeServicesRequest,
+                                      waiter: &Waiter<describe_services::DescribeServicesResponse>)
+                                      -> result::Result<describe_services::DescribeServicesResponse,
+                                                        error::WaiterError> {
+        waiter.wait(|| self.describe_services(request.clone()))
+    }
+
+    /// Polls DescribeTasks with the given request until every task is RUNNING.  Pass
+    /// waiter::tasks_running() for the semantics of `aws ecs wait tasks-running`.
+    pub fn wait_until_tasks_running(&self,
+                                    request: describe_tasks::DescribeTasksRequest,
+                                    waiter: &Waiter<describe_tasks::DescribeTasksResponse>)
+                                    -> result::Result<describe_tasks::DescribeTasksResponse,
+                                                      error::WaiterError> {
+        waiter.wait(|| self.describe_tasks(request.clone()))
+    }
+
+    /// Polls DescribeTasks with the given request until every task has STOPPED.  Pass
+    /// waiter::tasks_stopped() for the semantics of `aws ecs wait tasks-stopped`.
+    pub fn wait_until_tasks_stopped(&self,
+                                    request: describe_tasks::DescribeTasksRequest,
+                                    waiter: &Waiter<describe_tasks::DescribeTasksResponse>)
+                                    -> result::Result<describe_tasks::DescribeTasksResponse,
+                                                      error::WaiterError> {
+        waiter.wait(|| self.describe_tasks(request.clone()))
+    }
+
+    /// Sends the request for the given action like the per-action methods, e.g. list_clusters(),
+    /// but returns the ResponseMetadata of the operation along with the typed response, or along
+    /// with the error on failure.  U must be the response type of the action, otherwise the
+    /// response fails to deserialize.
+    pub fn send_with_metadata<T, U>(&self, action: ecs_action::ECSAction, request: T)
+                                    -> result::Result<Response<U>, error::OperationError>
+        where T: ecs_action::ECSRequest,
+              U: ecs_action::ECSResponse
+    {
+        let mut operation = try!(self.start_operation(action, &request));
+        loop {
+            if let Some(ref rate_limiter) = self.rate_limiter {
+                let acquired = match self.remaining_time(&operation) {
+                    Some(remaining) => rate_limiter.acquire_within(action, remaining),
+                    None => {
+                        rate_limiter.acquire(action);
+                        true
+                    },
+                };
+                if !acquired {
+                    let error = self.operation_timed_out(&operation);
+                    return self.finish_operation(operation, Err(error));
+                }
+            }
+            let error = match self.send_attempt(&mut operation) {
+                Ok(output) => return self.finish_operation(operation, Ok(output)),
+                Err(error) => error,
+            };
+            match self.retry_delay(&mut operation, &error) {
+                Some(delay) => thread::sleep(delay),
+                None => return self.finish_operation(operation, Err(error)),
+            }
+        }
+    }
+
+    /// Serializes the request and starts an Operation to send it with.  send_with_metadata()
+    /// drives the Operation itself; call this and the methods below instead to schedule its
+    /// attempts yourself, as the PooledECSClient does.  A request which cannot be serialized is
+    /// reported to the MetricsSink as a failed operation.
+    pub fn start_operation<T>(&self, action: ecs_action::ECSAction, request: &T)
+                              -> result::Result<Operation, error::OperationError>
+        where T: ecs_action::ECSRequest
+    {
+        match self.serialize(action, request) {
+            Ok(body) => Ok(Operation::new(action, body)),
+            Err(error) => {
+                let error = error::OperationError::new(error, ResponseMetadata::new());
+                self.record_operation(action, Err(&error), 0);
+                Err(error)
+            },
+        }
+    }
+
+    /// Makes the next attempt at the operation, recording its response in the operation's
+    /// metadata.  The attempt is not made at all if nothing remains of the operation timeout.  No
+    /// token is taken from the client's RateLimiter: the caller is expected to have acquired one.
+    pub fn send_attempt<U>(&self, operation: &mut Operation) -> Result<U>
+        where U: ecs_action::ECSResponse
+    {
+        if self.remaining_time(operation) == Some(Duration::new(0, 0)) {
+            return Err(self.operation_timed_out(operation));
+        }
+        operation.attempt += 1;
+        let context = RequestContext::new(operation.action, &self.region, operation.attempt);
+        let result = match self.sign_and_send(&context, &operation.body) {
+            Ok(response) => {
+                operation.metadata.record_response(Some(&response));
+                into_body(response)
+            },
+            Err(error) => {
+                operation.metadata.record_response(None);
+                Err(error)
+            },
+        };
+        operation.metadata.record_attempts(operation.attempt, operation.start.elapsed());
+        let response_body = try!(result);
+        Ok(try!(serde_json::from_str(&response_body)))
+    }
+
+    /// Decides whether the operation may be retried after its last attempt failed with the given
+    /// error, returning the delay to wait before the next attempt, or None if the RetryPolicy or
+    /// operation timeout does not allow another one.  The error is passed to the on_error() hook
+    /// of every interceptor.
+    pub fn retry_delay(&self, operation: &mut Operation, error: &error::ECSError) -> Option<Duration> {
+        if error.is_throttling() {
+            operation.throttles += 1;
+        }
+        let context = RequestContext::new(operation.action, &self.region, operation.attempt);
+        for interceptor in &self.interceptors {
+            interceptor.on_error(&context, error);
+        }
+        if !self.retry_policy.should_retry(error, operation.attempt) {
+            return None;
+        }
+        let delay = self.retry_policy.delay(operation.attempt);
+        if let Some(operation_timeout) = self.operation_timeout {
+            if operation.start.elapsed() + delay > operation_timeout {
+                return None;
+            }
+        }
+        warn!("{} failed, retrying in {}ms: {}", context, retry::to_millis(delay), error);
+        Some(delay)
+    }
+
+    /// Ends the operation with the result of its last attempt, attaching the operation's metadata
+    /// and reporting it to the client's MetricsSink.
+    pub fn finish_operation<U>(&self, operation: Operation, result: Result<U>)
+                               -> result::Result<Response<U>, error::OperationError>
+    {
+        let Operation { action, metadata, throttles, .. } = operation;
+        let result = match result {
+            Ok(output) => Ok(Response::new(output, metadata)),
+            Err(error) => Err(error::OperationError::new(error, metadata)),
+        };
+        self.record_operation(action, result.as_ref().map(|response| response.get_metadata()), throttles);
+        result
+    }
+
+    /// Gets the RateLimiter requests must acquire a token from, if one has been set.
+    pub fn get_rate_limiter(&self) -> Option<&Arc<RateLimiter>> {
+        self.rate_limiter.as_ref()
+    }
+
+    /// Gets what remains of the operation timeout for the given operation, or None if the client
+    /// has no operation timeout.
+    pub fn remaining_time(&self, operation: &Operation) -> Option<Duration> {
+        self.operation_timeout.map(|timeout| {
+            timeout.checked_sub(operation.start.elapsed()).unwrap_or(Duration::new(0, 0))
+        })
+    }
+
+    /// Builds the error an operation fails with once it has run out of time, e.g. while waiting
+    /// for a token from the RateLimiter.
+    pub fn operation_timed_out(&self, operation: &Operation) -> error::ECSError {
+        let message = format!("{} timed out after {}ms",
+                              operation.action,
+                              retry::to_millis(operation.start.elapsed()));
+        error::ECSError::Io(io::Error::new(io::ErrorKind::TimedOut, message))
+    }
+
+    /// Reports an operation to the client's MetricsSink, given the metadata of a successful
+    /// operation or the error of a failed one.
+    fn record_operation(&self,
+                        action: ecs_action::ECSAction,
+                        result: result::Result<&ResponseMetadata, &error::OperationError>,
+                        throttles: u32) {
+        if let Some(ref metrics_sink) = self.metrics_sink {
+            let record = match result {
+                Ok(metadata) => OperationRecord::new(action, metadata, throttles, None),
+                Err(error) => OperationRecord::new(action, error.get_metadata(), throttles, Some(error.get_error())),
+            };
+            metrics_sink.record(&record);
+        }
+    }
+
+    /// Serializes the request into the json body of an HTTP request, after passing it to the
+    /// before_serialization() hook of every interceptor.
+    fn serialize<T>(&self, action: ecs_action::ECSAction, request: &T) -> Result<String>
+        where T: ecs_action::ECSRequest
+    {
+        if self.interceptors.is_empty() {
+            return Ok(try!(serde_json::to_string(request)));
+        }
+        let context = RequestContext::new(action, &self.region, 1);
+        let mut input = serde_json::value::to_value(request);
+        for interceptor in &self.interceptors {
+            try!(interceptor.before_serialization(&context, &mut input));
+        }
+        Ok(try!(serde_json::to_string(&input)))
+    }
+
+    /// Signs and sends the request for the given action, then deserializes the body of the HTTP
+    /// response into the ECSResponse type expected for that action.  Failed attempts are retried
+    /// as the client's RetryPolicy and operation timeout allow.
+    fn send_request<T, U>(&self, action: ecs_action::ECSAction, request: T) -> Result<U>
+        where T: ecs_action::ECSRequest,
+              U: ecs_action::ECSResponse
+    {
+        self.send_with_metadata(action, request)
+            .map(Response::into_output)
+            .map_err(error::OperationError::into_error)
     }
 
     /// Creates an HTTP request to be sent to Amazon ECS.
-    /// Signs the request using Amazon's Signature Version 4 Signing Algorithm.
-    /// Serializes the service request to json format and sets it as the payload in the HTTP body.
-    /// Sends the request to ECS and returns the hyper::client::Response.
-    fn sign_and_send<T: ecs_action::ECSRequest>(&self,
-                                                action: ecs_action::ECSAction,
-                                                request: T) -> hyper::client::Response {
-        let body: String = serde_json::to_string(&request).unwrap();
-        let mut headers: Headers = self.build_headers(action, body.len() as u64);
-        let auth_header = signature::build_auth_header(&headers, &body, self.region, SERVICE_ABBREVIATION);
+    /// Signs the request using Amazon's Signature Version 4 Signing Algorithm.  The headers are
+    /// built anew on every call, so each attempt is signed with a fresh X-Amz-Date.
+    /// Sets the serialized service request as the payload in the HTTP body.
+    /// Sends the request through the client's transport and returns the response, whatever its
+    /// status code.  The hooks of every interceptor are called along the way.
+    fn sign_and_send(&self, context: &RequestContext, body: &str) -> Result<HttpResponse> {
+        let endpoint = try!(self.endpoint_resolver.resolve(&self.region));
+        let credentials = try!(self.credentials_provider.get_credentials());
+        let mut headers: Headers = self.build_headers(context.get_action(), &endpoint, &credentials, body.len() as u64);
+        for interceptor in &self.interceptors {
+            try!(interceptor.before_signing(context, &mut headers));
+        }
+        let auth_header = signature::build_auth_header_for_path(&headers,
+                                                                endpoint.get_path(),
+                                                                body,
+                                                                &credentials,
+                                                                self.get_signing_region(),
+                                                                SERVICE_ABBREVIATION);
         headers.set(Authorization(auth_header));
-        
-        let req_builder = self.client.post(&self.build_request_uri());
 
-        println!("Sending request...\n{}", headers);
-        println!("Request body...\n{}", body);
-        let response = req_builder.headers(headers).body(&body).send();
-        println!("Received response...\n{:?}", response);
-        response.unwrap()
-    }
+        let request = HttpRequest::new(endpoint.get_url(), headers, String::from(body));
+        for interceptor in &self.interceptors {
+            try!(interceptor.after_signing(context, &request));
+        }
+        debug!("{} sending request to {}", context, request.get_url());
+        if log_enabled!(LogLevel::Trace) {
+            trace!("{} request headers:\n{}", context, logging::redact_headers(request.get_headers()));
+            trace!("{} request body: {}", context, logging::redact_body(body));
+        }
+        for interceptor in &self.interceptors {
+            try!(interceptor.before_transmit(context, &request));
+        }
+        let sent = Instant::now();
+        let response = match self.transport.send(&request) {
+            Ok(response) => response,
+            Err(error) => {
+                debug!("{} no response after {}ms: {}", context, retry::to_millis(sent.elapsed()), error);
+                return Err(error);
+            },
+        };
+        debug!("{} received status={} request_id={} latency_ms={}",
+               context,
+               response.get_status_code(),
+               response.get_request_id().unwrap_or_else(|| String::from("-")),
+               retry::to_millis(sent.elapsed()));
+        if log_enabled!(LogLevel::Trace) {
+            trace!("{} response body: {}", context, logging::redact_body(response.get_body()));
+        }
+        for interceptor in &self.interceptors {
+            try!(interceptor.after_response(context, &response));
+        }
 
-    /// Builds the request URI based on the Region this client is currently configured to send
-    /// requests to.
-    fn build_request_uri(&self) -> String {
-        let mut uri = String::from("https://");
-        uri.push_str(&self.build_hostname());
-        uri
+        Ok(response)
     }
 
-    /// Builds a hyper::header::Headers with the Host, Accept-Encoding, X-Amz-Target, X-Amz-Date,
-    /// Content-Type, and Content-Length HTTP headers set.
-    fn build_headers(&self, action: ecs_action::ECSAction, content_length: u64) -> Headers {
+    /// Builds a hyper::header::Headers with the Host, User-Agent, Accept-Encoding, X-Amz-Target,
+    /// X-Amz-Date, Content-Type, and Content-Length HTTP headers set, plus X-Amz-Security-Token when signing
+    /// with temporary credentials.
+    fn build_headers(&self,
+                     action: ecs_action::ECSAction,
+                     endpoint: &Endpoint,
+                     credentials: &Credentials,
+                     content_length: u64) -> Headers {
         let mut headers: Headers = Headers::new();
-        headers.set(Host {
-            hostname: self.build_hostname(),
-            port: None,
-        });
+        headers.set(endpoint.host_header());
+        headers.set(UserAgent(self.user_agent.clone()));
         headers.set(AcceptEncoding(vec![qitem(Encoding::Identity)]));
         headers.set(XAmzTarget(self.build_x_amz_target(action)));
         headers.set(XAmzDate(time::strftime("%Y%m%dT%H%M%SZ", &time::now_utc()).unwrap()));
@@ -103,18 +529,12 @@ impl ECSClient {
             )
         );
         headers.set(ContentLength(content_length));
+        if let Some(ref session_token) = *credentials.get_session_token() {
+            headers.set(XAmzSecurityToken(session_token.clone()));
+        }
         headers
     }
 
-    /// Builds and returns the hostname String used in the Host header.
-    fn build_hostname(&self) -> String {
-        let mut hostname = String::from(SERVICE_ABBREVIATION);
-        hostname.push_str(".");
-        hostname.push_str(&self.region.to_string());
-        hostname.push_str(".amazonaws.com");
-        hostname
-    }
-
     /// Builds and returns the target String used in the X-Amz-Target header.
     fn build_x_amz_target(&self, action: ecs_action::ECSAction) -> String {
         let mut target = String::from(ECS_API_VERSION);
@@ -123,3 +543,91 @@ impl ECSClient {
         target
     }
 }
+
+/// Defines the per-action methods of ECSClient from the table of actions; see ecs_actions!.
+macro_rules! ecs_client_actions {
+    (actions {
+        $($(#[$doc:meta])*
+          $action:ident: $name:ident, $with_metadata:ident, $request:ty, $response:ty;)*
+    }
+    paginated {
+        $($(#[$all_doc:meta])* $pages:ident, $all:ident, $paged_request:ty, $paged_response:ty;)*
+    }) => {
+        impl ECSClient {
+            $(
+                $(#[$doc])*
+                pub fn $name(&self, request: $request) -> Result<$response> {
+                    self.send_request(ecs_action::ECSAction::$action, request)
+                }
+
+                /// Like the method of the same name without the _with_metadata suffix, but also
+                /// returns the ResponseMetadata of the operation along with the response, or the
+                /// OperationError carrying it on failure.
+                pub fn $with_metadata(&self, request: $request)
+                                      -> result::Result<Response<$response>,
+                                                        error::OperationError> {
+                    self.send_with_metadata(ecs_action::ECSAction::$action, request)
+                }
+            )*
+
+            $(
+                /// Returns an iterator over every page of output of the action, starting from the
+                /// given request and sending each follow-up request only as the previous page is
+                /// consumed.
+                pub fn $pages(&self, request: $paged_request) -> Pages<$paged_request> {
+                    Pages::new(self, request)
+                }
+
+                $(#[$all_doc])*
+                pub fn $all(&self, request: $paged_request)
+                            -> Items<Pages<$paged_request>, $paged_response> {
+                    Pages::new(self, request).items()
+                }
+            )*
+        }
+    }
+}
+
+ecs_actions!(ecs_client_actions);
+
+impl Operation {
+    /// Creates an Operation which has not made any attempt yet.
+    fn new(action: ecs_action::ECSAction, body: String) -> Operation {
+        Operation {
+            action: action,
+            body: body,
+            metadata: ResponseMetadata::new(),
+            start: Instant::now(),
+            attempt: 0,
+            throttles: 0,
+        }
+    }
+
+    /// Gets the action being requested.
+    pub fn get_action(&self) -> ecs_action::ECSAction {
+        self.action
+    }
+
+    /// Gets the metadata recorded by the attempts made so far.
+    pub fn get_metadata(&self) -> &ResponseMetadata {
+        &self.metadata
+    }
+
+    /// Gets the number of attempts made so far.
+    pub fn get_attempts(&self) -> u32 {
+        self.attempt
+    }
+}
+
+/// Returns the body of a successful response, or an ECSError built from an error response and
+/// naming its request ID.
+fn into_body(response: HttpResponse) -> Result<String> {
+    if !response.is_success() {
+        let mut error = error::ServiceError::from_response(response.get_status_code(), response.get_body());
+        if let Some(request_id) = response.get_request_id() {
+            error.set_request_id(request_id);
+        }
+        return Err(error::ECSError::Service(error));
+    }
+    Ok(response.get_body().clone())
+}
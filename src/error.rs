@@ -2,11 +2,390 @@
 //! Note that the ECS API reference defines 'Exception' types, but we map each Amazon ECS 
 //! Exception to a Rust Error.
 
-// note: might have to make this serde::de::Deserialize depending on how errors are returned.
-/// A general ECSError.
-pub struct ECSError {
+use response::ResponseMetadata;
+
+use hyper;
+use serde_json;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+
+/// An error returned when a request to Amazon ECS fails.
+#[derive(Debug)]
+pub enum ECSError {
+    /// ECS answered the request with an error response.
+    Service(ServiceError),
+    /// The request could not be sent, or the response could not be received.
+    Http(hyper::Error),
+    /// The body of the response could not be read.
+    Io(io::Error),
+    /// The request could not be serialized, or the response could not be deserialized.
+    Json(serde_json::Error),
+    /// The request could not be built, e.g. because the configured endpoint is not a valid URL.
+    Validation(ValidationError),
+    /// No credentials could be found to sign the request with.
+    Credentials(String),
+    /// No region was configured in the environment, the shared config file or instance metadata.
+    Region(String),
+}
+
+/// An error response returned by ECS, such as a ClientException or a ThrottlingException.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ServiceError {
     /// The status code that was returned with the HTTP response.
     status_code: u16,
+    /// The name of the exception ECS returned, e.g. ClientException, or an empty string if the
+    /// response did not name one.
+    error_type: String,
     /// The error message that was returned with the HTTP response.
     message: String,
+    /// The ID ECS assigned to the request, if the response named one.
+    request_id: Option<String>,
+}
+
+impl ECSError {
+    /// Returns true if the same request may succeed when sent again, i.e. the request was
+    /// throttled, ECS reported a server-side error, or the connection was dropped.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            &ECSError::Service(ref error) => error.is_retryable(),
+            &ECSError::Http(hyper::Error::Io(ref error)) | &ECSError::Io(ref error) => {
+                match error.kind() {
+                    io::ErrorKind::ConnectionReset | io::ErrorKind::ConnectionAborted |
+                    io::ErrorKind::BrokenPipe | io::ErrorKind::TimedOut => true,
+                    _ => false,
+                }
+            },
+            _ => false,
+        }
+    }
+
+    /// Returns true if ECS throttled the request.
+    pub fn is_throttling(&self) -> bool {
+        match self {
+            &ECSError::Service(ref error) => error.is_throttling(),
+            _ => false,
+        }
+    }
+
+    /// Gets a short name for the kind of error: the exception named by ECS for a service error,
+    /// e.g. "ClientException", or the name of the variant otherwise, e.g. "Http".
+    pub fn get_error_type(&self) -> &str {
+        match self {
+            &ECSError::Service(ref error) if !error.get_error_type().is_empty() => error.get_error_type(),
+            &ECSError::Service(_) => "UnknownServiceError",
+            &ECSError::Http(_) => "Http",
+            &ECSError::Io(_) => "Io",
+            &ECSError::Json(_) => "Json",
+            &ECSError::Validation(_) => "Validation",
+            &ECSError::Credentials(_) => "Credentials",
+            &ECSError::Region(_) => "Region",
+        }
+    }
+}
+
+impl ServiceError {
+    /// Creates a ServiceError from the status code and body of an error response.  ECS names the
+    /// exception in the __type field of the body, optionally prefixed with its namespace and a
+    /// '#'.
+    pub fn from_response(status_code: u16, body: &str) -> ServiceError {
+        let (error_type, message) = match serde_json::from_str::<serde_json::Value>(body) {
+            Ok(value) => {
+                let error_type = value.find("__type")
+                                      .and_then(|error_type| error_type.as_string())
+                                      .map(|error_type| error_type.rsplit('#').next().unwrap_or(""))
+                                      .unwrap_or("");
+                let message = value.find("message")
+                                   .or(value.find("Message"))
+                                   .and_then(|message| message.as_string())
+                                   .unwrap_or("");
+                (String::from(error_type), String::from(message))
+            },
+            Err(_) => (String::new(), String::from(body)),
+        };
+        ServiceError {
+            status_code: status_code,
+            error_type: error_type,
+            message: message,
+            request_id: None,
+        }
+    }
+
+    /// Sets the ID ECS assigned to the request, from the x-amzn-RequestId header.
+    pub fn set_request_id(&mut self, request_id: String) {
+        self.request_id = Some(request_id);
+    }
+
+    /// Gets the ID ECS assigned to the request, to cite when contacting AWS support.
+    pub fn get_request_id(&self) -> Option<&String> {
+        self.request_id.as_ref()
+    }
+
+    /// Gets the status code that was returned with the HTTP response.
+    pub fn get_status_code(&self) -> u16 {
+        self.status_code
+    }
+
+    /// Gets the name of the exception ECS returned.
+    pub fn get_error_type(&self) -> &String {
+        &self.error_type
+    }
+
+    /// Gets the error message that was returned with the HTTP response.
+    pub fn get_message(&self) -> &String {
+        &self.message
+    }
+
+    /// Returns true if ECS throttled the request.
+    pub fn is_throttling(&self) -> bool {
+        self.error_type == "ThrottlingException" || self.status_code == 429
+    }
+
+    /// Returns true if the request was throttled or failed on the server side.
+    pub fn is_retryable(&self) -> bool {
+        match &self.error_type[..] {
+            "ThrottlingException" | "ServerException" => true,
+            _ => self.status_code == 429 || self.status_code >= 500,
+        }
+    }
+}
+
+impl Display for ECSError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            &ECSError::Service(ref error) => write!(f, "{}", error),
+            &ECSError::Http(ref error) => write!(f, "http error: {}", error),
+            &ECSError::Io(ref error) => write!(f, "io error: {}", error),
+            &ECSError::Json(ref error) => write!(f, "json error: {}", error),
+            &ECSError::Validation(ref error) => write!(f, "{}", error),
+            &ECSError::Credentials(ref message) => write!(f, "credentials error: {}", message),
+            &ECSError::Region(ref message) => write!(f, "region error: {}", message),
+        }
+    }
+}
+
+impl Error for ECSError {
+    fn description(&self) -> &str {
+        match self {
+            &ECSError::Service(ref error) => error.description(),
+            &ECSError::Http(ref error) => error.description(),
+            &ECSError::Io(ref error) => error.description(),
+            &ECSError::Json(ref error) => error.description(),
+            &ECSError::Validation(ref error) => error.description(),
+            &ECSError::Credentials(ref message) | &ECSError::Region(ref message) => message,
+        }
+    }
+}
+
+impl From<hyper::Error> for ECSError {
+    fn from(error: hyper::Error) -> ECSError {
+        ECSError::Http(error)
+    }
+}
+
+impl From<io::Error> for ECSError {
+    fn from(error: io::Error) -> ECSError {
+        ECSError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for ECSError {
+    fn from(error: serde_json::Error) -> ECSError {
+        ECSError::Json(error)
+    }
+}
+
+impl From<ValidationError> for ECSError {
+    fn from(error: ValidationError) -> ECSError {
+        ECSError::Validation(error)
+    }
+}
+
+impl Display for ServiceError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        try!(write!(f, "{} ({}): {}", self.error_type, self.status_code, self.message));
+        match self.request_id {
+            Some(ref request_id) => write!(f, " (request id {})", request_id),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Error for ServiceError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
+
+/// An ECSError along with the ResponseMetadata of the operation which failed, as returned by the
+/// *_with_metadata() methods of the ECSClient, e.g. list_clusters_with_metadata().
+#[derive(Debug)]
+pub struct OperationError {
+    error: ECSError,
+    metadata: ResponseMetadata,
+}
+
+impl OperationError {
+    /// Creates an OperationError.
+    pub fn new(error: ECSError, metadata: ResponseMetadata) -> OperationError {
+        OperationError {
+            error: error,
+            metadata: metadata,
+        }
+    }
+
+    /// Gets the error the operation failed with.
+    pub fn get_error(&self) -> &ECSError {
+        &self.error
+    }
+
+    /// Gets the metadata of the operation.
+    pub fn get_metadata(&self) -> &ResponseMetadata {
+        &self.metadata
+    }
+
+    /// Consumes the OperationError, returning the error the operation failed with.
+    pub fn into_error(self) -> ECSError {
+        self.error
+    }
+}
+
+impl Display for OperationError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        try!(write!(f, "{} after {} attempt(s)", self.error, self.metadata.get_attempts()));
+        match (self.metadata.get_request_id(), &self.error) {
+            // a ServiceError already names its request ID
+            (Some(request_id), error) if !is_service_error(error) => write!(f, " (request id {})", request_id),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Error for OperationError {
+    fn description(&self) -> &str {
+        self.error.description()
+    }
+}
+
+impl From<OperationError> for ECSError {
+    fn from(error: OperationError) -> ECSError {
+        error.error
+    }
+}
+
+/// Returns true if the error is an ECSError::Service.
+fn is_service_error(error: &ECSError) -> bool {
+    match *error {
+        ECSError::Service(_) => true,
+        _ => false,
+    }
+}
+
+/// An error returned when a request parameter violates one of the constraints documented in the
+/// ECS API reference.  These are caught on the client before a request is ever sent to ECS.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationError {
+    /// The name of the parameter which failed validation.
+    parameter: String,
+    /// A description of the constraint which was violated.
+    message: String,
+}
+
+impl ValidationError {
+    /// Creates a ValidationError for the named parameter.
+    pub fn new(parameter: &str, message: String) -> ValidationError {
+        ValidationError {
+            parameter: String::from(parameter),
+            message: message,
+        }
+    }
+
+    /// Gets the name of the parameter which failed validation.
+    pub fn get_parameter(&self) -> &String {
+        &self.parameter
+    }
+
+    /// Gets a description of the constraint which was violated.
+    pub fn get_message(&self) -> &String {
+        &self.message
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "invalid {}: {}", self.parameter, self.message)
+    }
+}
+
+impl Error for ValidationError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
+
+/// An error returned when a waiter gives up before the resources it polls reach the desired
+/// state.
+#[derive(Debug)]
+pub enum WaiterError {
+    /// A failure matcher matched the polled response, so the desired state can no longer be
+    /// reached.  Holds a description of the state which was matched.
+    FailureState(String),
+    /// The desired state was not reached within the given number of attempts.
+    MaxAttemptsExceeded(u32),
+    /// A request polling the state of the resources failed.
+    Request(ECSError),
+}
+
+impl Display for WaiterError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            &WaiterError::FailureState(ref state) => write!(f, "waiter reached a failure state: {}", state),
+            &WaiterError::MaxAttemptsExceeded(attempts) =>
+                write!(f, "waiter gave up after {} attempts", attempts),
+            &WaiterError::Request(ref error) => write!(f, "waiter request failed: {}", error),
+        }
+    }
+}
+
+impl Error for WaiterError {
+    fn description(&self) -> &str {
+        match self {
+            &WaiterError::FailureState(_) => "waiter reached a failure state",
+            &WaiterError::MaxAttemptsExceeded(_) => "waiter exceeded its max attempts",
+            &WaiterError::Request(ref error) => error.description(),
+        }
+    }
+}
+
+impl From<ECSError> for WaiterError {
+    fn from(error: ECSError) -> WaiterError {
+        WaiterError::Request(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ECSError, ServiceError};
+    use std::io;
+
+    #[test]
+    fn test_service_error_from_response() {
+        let error = ServiceError::from_response(400,
+            "{\"__type\":\"com.amazonaws.ecs#ThrottlingException\",\"message\":\"Rate exceeded\"}");
+        assert_eq!("ThrottlingException", *error.get_error_type());
+        assert_eq!("Rate exceeded", *error.get_message());
+        assert!(error.is_retryable());
+
+        let error = ServiceError::from_response(400, "{\"__type\":\"ClientException\",\"Message\":\"bad\"}");
+        assert_eq!("ClientException", *error.get_error_type());
+        assert_eq!("bad", *error.get_message());
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn test_retryable_classification() {
+        assert!(ECSError::Service(ServiceError::from_response(503, "Service Unavailable")).is_retryable());
+        assert!(ECSError::Io(io::Error::new(io::ErrorKind::ConnectionReset, "reset")).is_retryable());
+        assert!(!ECSError::Io(io::Error::new(io::ErrorKind::PermissionDenied, "denied")).is_retryable());
+    }
 }
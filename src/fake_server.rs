@@ -0,0 +1,1565 @@
+//! This module contains FakeECS, a local HTTP server which speaks the x-amz-json-1.1 protocol of
+//! Amazon ECS and keeps its resources in memory, so that integration tests can exercise an
+//! ECSClient without a network or an AWS account.  It is only built with the fake-server
+//! feature:
+//!
+//! ```text
+//! [dev-dependencies]
+//! ecs_client = { version = "*", features = ["fake-server"] }
+//! ```
+//!
+//! Every request must be signed with the credentials in the environment of the current process;
+//! the server recomputes the Signature Version 4 signature with the crate's own signer and
+//! rejects requests whose signature does not match.  Every action of the crate is modelled:
+//! clusters, services and their task sets, task definitions, tasks, container instances and their
+//! attributes, capacity providers, account settings and tags can be created and changed through
+//! the API, and also seeded through the methods of FakeECS.  Each request the server handles
+//! advances the simulated state by one step: PENDING tasks start RUNNING, stopping tasks reach
+//! STOPPED, services and task sets move one task closer to their desired count, and pending
+//! container agent updates complete.  Requests are treated as coming from the root user of
+//! ACCOUNT_ID.
+//!
+//! ```no_run
+//! # use ecs_client::ecs_client::ECSClient;
+//! # use ecs_client::fake_server::FakeECS;
+//! # use ecs_client::region::Region;
+//! let fake = FakeECS::start(Region::USWest2).unwrap();
+//! fake.add_cluster("default");
+//! let mut client = ECSClient::for_region(Region::USWest2);
+//! client.set_endpoint(&fake.endpoint()).unwrap();
+//! ```
+
+use error::ECSError;
+use custom_headers::{XAmzTarget, XAmzDate};
+use custom_ser;
+use region::Region;
+use credentials::{CredentialsProvider, EnvironmentProvider};
+use signature;
+
+use hyper::header::{Headers, Host, AcceptEncoding, ContentType, ContentLength, Authorization};
+use hyper::mime::{Mime, TopLevel, SubLevel};
+use hyper::server::{Server, Handler, Request, Response, Listening};
+use hyper::uri::RequestUri;
+use hyper::status::StatusCode;
+use serde::ser::Serialize;
+use serde_json;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+
+/// The account ID used in the ARNs of fake resources.
+pub const ACCOUNT_ID: &'static str = "012345678910";
+/// The prefix of the X-Amz-Target header of every ECS action.
+const TARGET_PREFIX: &'static str = "AmazonEC2ContainerServiceV20141113.";
+/// The number of results in a page of a listing when the request sets no maxResults.
+const DEFAULT_MAX_RESULTS: usize = 100;
+
+/// A running fake ECS server.  The server stops listening when this is dropped.
+pub struct FakeECS {
+    listening: Listening,
+    state: Arc<Mutex<State>>,
+}
+
+/// The in-memory resources of a FakeECS.
+#[derive(Debug)]
+struct State {
+    region: Region,
+    clusters: Vec<FakeCluster>,
+    services: Vec<FakeService>,
+    task_definitions: Vec<FakeTaskDefinition>,
+    tasks: Vec<FakeTask>,
+    container_instances: Vec<FakeContainerInstance>,
+    capacity_providers: Vec<FakeCapacityProvider>,
+    account_settings: Vec<FakeSetting>,
+    /// The tags of every resource, keyed by ARN.
+    tags: BTreeMap<String, Vec<FakeTag>>,
+    /// A counter used to generate unique IDs.
+    next_id: u64,
+}
+
+/// Handles each HTTP request received by a FakeECS.
+struct FakeHandler {
+    state: Arc<Mutex<State>>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Clone, Debug)]
+struct FakeCluster {
+    clusterName: String,
+    clusterArn: String,
+    status: String,
+    settings: Vec<Value>,
+    configuration: Option<Value>,
+    capacityProviders: Vec<String>,
+    defaultCapacityProviderStrategy: Vec<Value>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Clone, Debug)]
+struct FakeService {
+    serviceName: String,
+    serviceArn: String,
+    clusterArn: String,
+    status: String,
+    taskDefinition: String,
+    launchType: Option<String>,
+    desiredCount: i32,
+    runningCount: i32,
+    pendingCount: i32,
+    deployments: Vec<FakeDeployment>,
+    taskSets: Vec<FakeTaskSet>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Clone, Debug)]
+struct FakeDeployment {
+    id: String,
+    status: String,
+    taskDefinition: String,
+    desiredCount: i32,
+    runningCount: i32,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Clone, Debug)]
+struct FakeTaskSet {
+    id: String,
+    taskSetArn: String,
+    serviceArn: String,
+    clusterArn: String,
+    externalId: Option<String>,
+    status: String,
+    taskDefinition: String,
+    launchType: Option<String>,
+    computedDesiredCount: i32,
+    pendingCount: i32,
+    runningCount: i32,
+    scale: Value,
+    stabilityStatus: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Clone, Debug)]
+struct FakeTaskDefinition {
+    taskDefinitionArn: String,
+    family: String,
+    revision: i32,
+    status: String,
+    containerDefinitions: Vec<Value>,
+    networkMode: Option<String>,
+    taskRoleArn: Option<String>,
+    executionRoleArn: Option<String>,
+    cpu: Option<String>,
+    memory: Option<String>,
+    requiresCompatibilities: Vec<String>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Clone, Debug)]
+struct FakeTask {
+    taskArn: String,
+    clusterArn: String,
+    taskDefinitionArn: String,
+    lastStatus: String,
+    desiredStatus: String,
+    launchType: Option<String>,
+    startedBy: Option<String>,
+    stoppedReason: Option<String>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Clone, Debug)]
+struct FakeContainerInstance {
+    containerInstanceArn: String,
+    clusterArn: String,
+    ec2InstanceId: String,
+    status: String,
+    agentConnected: bool,
+    agentUpdateStatus: Option<String>,
+    versionInfo: FakeVersionInfo,
+    registeredResources: Vec<Value>,
+    remainingResources: Vec<Value>,
+    attributes: Vec<FakeAttribute>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Clone, Debug)]
+struct FakeVersionInfo {
+    agentVersion: Option<String>,
+    agentHash: Option<String>,
+    dockerVersion: Option<String>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Clone, Debug)]
+struct FakeAttribute {
+    name: String,
+    value: Option<String>,
+    targetType: String,
+    targetId: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Clone, Debug)]
+struct FakeCapacityProvider {
+    capacityProviderArn: String,
+    name: String,
+    status: String,
+    autoScalingGroupProvider: Value,
+    updateStatus: Option<String>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Clone, Debug)]
+struct FakeSetting {
+    name: String,
+    value: String,
+    principalArn: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+struct FakeTag {
+    key: String,
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    value: Option<String>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+struct FakeSession {
+    sessionId: String,
+    streamUrl: String,
+    tokenValue: String,
+}
+
+#[derive(Serialize, Debug)]
+struct FakeFailure {
+    arn: String,
+    reason: String,
+}
+
+/// An error response: a status code and a json body.
+type Reply = (StatusCode, String);
+
+/// The outcome of handling a request: the json body of a successful response, or an error
+/// response.
+type Outcome = Result<String, Reply>;
+
+impl FakeECS {
+    /// Starts a FakeECS listening on an ephemeral port of the loopback interface, which verifies
+    /// request signatures for the given Region.
+    pub fn start(region: Region) -> Result<FakeECS, ECSError> {
+        let state = Arc::new(Mutex::new(State {
+            region: region,
+            clusters: Vec::new(),
+            services: Vec::new(),
+            task_definitions: Vec::new(),
+            tasks: Vec::new(),
+            container_instances: Vec::new(),
+            capacity_providers: Vec::new(),
+            account_settings: Vec::new(),
+            tags: BTreeMap::new(),
+            next_id: 1,
+        }));
+        let handler = FakeHandler {
+            state: state.clone(),
+        };
+        let listening = try!(try!(Server::http("127.0.0.1:0")).handle(handler));
+        Ok(FakeECS {
+            listening: listening,
+            state: state,
+        })
+    }
+
+    /// Gets the URL to pass to ECSClient::set_endpoint() to send requests to this server.
+    pub fn endpoint(&self) -> String {
+        format!("http://{}", self.listening.socket)
+    }
+
+    /// Adds an ACTIVE cluster and returns its ARN.  An existing ACTIVE cluster with the same name
+    /// is kept as it is.
+    pub fn add_cluster(&self, name: &str) -> String {
+        let mut state = self.state.lock().unwrap();
+        let index = state.add_cluster(name);
+        state.clusters[index].clusterArn.clone()
+    }
+
+    /// Adds an ACTIVE service running no tasks yet to the given cluster, and returns its ARN.  The
+    /// service starts one task per request handled until it reaches its desired count.
+    pub fn add_service(&self, cluster: &str, name: &str, task_definition: &str, desired_count: i32) -> String {
+        let mut state = self.state.lock().unwrap();
+        let cluster_arn = state.cluster_arn(cluster);
+        let index = state.add_service(cluster_arn, name, task_definition, desired_count);
+        state.services[index].serviceArn.clone()
+    }
+
+    /// Starts rolling out a new task definition to a service.  The old deployment is kept until
+    /// the next request has been handled.
+    pub fn update_service(&self, service: &str, task_definition: &str) {
+        let mut state = self.state.lock().unwrap();
+        let deployment = state.deployment(task_definition, 0);
+        if let Some(service) = state.services.iter_mut().find(|s| matches(&s.serviceArn, &s.serviceName, service)) {
+            let mut deployment = deployment;
+            deployment.desiredCount = service.desiredCount;
+            for old in service.deployments.iter_mut() {
+                old.status = String::from("ACTIVE");
+            }
+            service.deployments.insert(0, deployment);
+            service.taskDefinition = String::from(task_definition);
+        }
+    }
+
+    /// Sets the status of a service, e.g. to DRAINING or INACTIVE.
+    pub fn set_service_status(&self, service: &str, status: &str) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(service) = state.services.iter_mut().find(|s| matches(&s.serviceArn, &s.serviceName, service)) {
+            service.status = String::from(status);
+        }
+    }
+
+    /// Starts a PENDING task in the given cluster and returns its ARN.  The task is RUNNING once
+    /// the next request has been handled.  The task definition need not have been registered.
+    pub fn run_task(&self, cluster: &str, task_definition: &str) -> String {
+        let mut state = self.state.lock().unwrap();
+        let cluster_arn = state.cluster_arn(cluster);
+        let task_definition_arn = match state.find_task_definition(task_definition) {
+            Ok(index) => state.task_definitions[index].taskDefinitionArn.clone(),
+            Err(_) => state.arn(&format!("task-definition/{}", task_definition)),
+        };
+        let index = state.start_task(cluster_arn, task_definition_arn);
+        state.tasks[index].taskArn.clone()
+    }
+
+    /// Asks a task to stop.  The task is STOPPED once the next request has been handled.
+    pub fn stop_task(&self, task: &str, reason: &str) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(task_state) = state.tasks.iter_mut().find(|t| matches(&t.taskArn, &t.taskArn, task)) {
+            task_state.desiredStatus = String::from("STOPPED");
+            task_state.stoppedReason = Some(String::from(reason));
+        }
+    }
+
+    /// Registers a container instance running the given agent version with a cluster, and
+    /// returns its ARN.
+    pub fn add_container_instance(&self, cluster: &str, agent_version: &str) -> String {
+        let mut state = self.state.lock().unwrap();
+        let cluster_arn = state.cluster_arn(cluster);
+        let version_info = FakeVersionInfo {
+            agentVersion: Some(String::from(agent_version)),
+            agentHash: None,
+            dockerVersion: None,
+        };
+        let index = state.add_container_instance(cluster_arn, None, version_info);
+        state.container_instances[index].containerInstanceArn.clone()
+    }
+}
+
+impl Drop for FakeECS {
+    fn drop(&mut self) {
+        let _ = self.listening.close();
+    }
+}
+
+impl Handler for FakeHandler {
+    fn handle<'a, 'k>(&'a self, mut request: Request<'a, 'k>, mut response: Response<'a>) {
+        let path = match request.uri {
+            RequestUri::AbsolutePath(ref path) => path.split('?').next().unwrap_or("/").to_owned(),
+            _ => String::from("/"),
+        };
+        let mut body = String::new();
+        let (status, reply) = match request.read_to_string(&mut body) {
+            Ok(_) => self.reply(&request.headers, &path, &body),
+            Err(error) => client_error("SerializationException", &error.to_string()),
+        };
+        *response.status_mut() = status;
+        response.headers_mut().set(ContentType(Mime(TopLevel::Application,
+                                                    SubLevel::Ext(String::from("x-amz-json-1.1")),
+                                                    vec![])));
+        let _ = response.send(reply.as_bytes());
+    }
+}
+
+impl FakeHandler {
+    /// Verifies the signature of a request and dispatches it to the handler of its action.
+    fn reply(&self, headers: &Headers, path: &str, body: &str) -> Reply {
+        let mut state = self.state.lock().unwrap();
+        if let Err(reply) = verify_signature(headers, path, body, &state.region) {
+            return reply;
+        }
+        let target = headers.get::<XAmzTarget>().unwrap().0.clone();
+        if !target.starts_with(TARGET_PREFIX) {
+            return client_error("UnknownOperationException", &target);
+        }
+        let params: Value = match serde_json::from_str(body) {
+            Ok(params) => params,
+            Err(error) => return client_error("SerializationException", &error.to_string()),
+        };
+        state.tick();
+        let outcome = match &target[TARGET_PREFIX.len()..] {
+            "CreateCluster" => state.create_cluster(&params),
+            "ListClusters" => state.list_clusters(&params),
+            "UpdateCluster" => state.update_cluster(&params),
+            "UpdateClusterSettings" => state.update_cluster(&params),
+            "PutClusterCapacityProviders" => state.put_cluster_capacity_providers(&params),
+            "RegisterTaskDefinition" => state.register_task_definition(&params),
+            "CreateService" => state.create_service(&params),
+            "DescribeServices" => state.describe_services(&params),
+            "RunTask" => state.run_task(&params),
+            "DescribeTasks" => state.describe_tasks(&params),
+            "SubmitTaskStateChange" => state.submit_task_state_change(&params),
+            "SubmitContainerStateChange" => state.submit_container_state_change(&params),
+            "ExecuteCommand" => state.execute_command(&params),
+            "RegisterContainerInstance" => state.register_container_instance(&params),
+            "ListContainerInstances" => state.list_container_instances(&params),
+            "DescribeContainerInstances" => state.describe_container_instances(&params),
+            "UpdateContainerAgent" => state.update_container_agent(&params),
+            "DiscoverPollEndpoint" => state.discover_poll_endpoint(&params),
+            "PutAttributes" => state.put_attributes(&params),
+            "DeleteAttributes" => state.delete_attributes(&params),
+            "ListAttributes" => state.list_attributes(&params),
+            "TagResource" => state.tag_resource(&params),
+            "UntagResource" => state.untag_resource(&params),
+            "ListTagsForResource" => state.list_tags_for_resource(&params),
+            "CreateCapacityProvider" => state.create_capacity_provider(&params),
+            "UpdateCapacityProvider" => state.update_capacity_provider(&params),
+            "DeleteCapacityProvider" => state.delete_capacity_provider(&params),
+            "DescribeCapacityProviders" => state.describe_capacity_providers(&params),
+            "PutAccountSetting" => state.put_account_setting(&params),
+            "PutAccountSettingDefault" => state.put_account_setting_default(&params),
+            "ListAccountSettings" => state.list_account_settings(&params),
+            "DeleteAccountSetting" => state.delete_account_setting(&params),
+            "CreateTaskSet" => state.create_task_set(&params),
+            "UpdateTaskSet" => state.update_task_set(&params),
+            "DeleteTaskSet" => state.delete_task_set(&params),
+            "DescribeTaskSets" => state.describe_task_sets(&params),
+            "UpdateServicePrimaryTaskSet" => state.update_service_primary_task_set(&params),
+            action => Err(client_error("UnknownOperationException", action)),
+        };
+        match outcome {
+            Ok(body) => (StatusCode::Ok, body),
+            Err(reply) => reply,
+        }
+    }
+}
+
+impl State {
+    /// Builds the ARN of a resource in this server's region and account.
+    fn arn(&self, resource: &str) -> String {
+        format!("arn:{}:ecs:{}:{}:{}", self.region.get_partition(), self.region, ACCOUNT_ID, resource)
+    }
+
+    /// Gets the ARN of the root user every request is treated as coming from.
+    fn root_arn(&self) -> String {
+        format!("arn:{}:iam::{}:root", self.region.get_partition(), ACCOUNT_ID)
+    }
+
+    /// Returns a new unique ID.
+    fn next_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    /// Resolves a cluster name or ARN to an ARN, defaulting to the default cluster.
+    fn cluster_arn(&self, cluster: &str) -> String {
+        match self.clusters.iter().find(|c| matches(&c.clusterArn, &c.clusterName, cluster)) {
+            Some(cluster) => cluster.clusterArn.clone(),
+            None => self.arn(&format!("cluster/{}", short_name(cluster))),
+        }
+    }
+
+    /// Finds the ACTIVE cluster named by the cluster parameter of a request, which defaults to
+    /// the default cluster, and returns its ARN.
+    fn find_cluster(&self, params: &Value) -> Result<String, Reply> {
+        let cluster = string_param(params, "cluster").unwrap_or(String::from("default"));
+        match self.clusters.iter().find(|c| c.status == "ACTIVE" && matches(&c.clusterArn, &c.clusterName, &cluster)) {
+            Some(cluster) => Ok(cluster.clusterArn.clone()),
+            None => Err(client_error("ClusterNotFoundException", "Cluster not found.")),
+        }
+    }
+
+    /// Finds the service named by the cluster and service parameters of a request.
+    fn find_service(&self, params: &Value) -> Result<usize, Reply> {
+        let cluster_arn = try!(self.find_cluster(params));
+        let service = try!(required_string_param(params, "service"));
+        match self.services.iter().position(|s| s.clusterArn == cluster_arn && s.status != "INACTIVE" &&
+                                                 matches(&s.serviceArn, &s.serviceName, &service)) {
+            Some(index) => Ok(index),
+            None => Err(client_error("ServiceNotFoundException", "Service not found.")),
+        }
+    }
+
+    /// Finds a task definition by family and revision, family alone for its latest ACTIVE
+    /// revision, or ARN.
+    fn find_task_definition(&self, task_definition: &str) -> Result<usize, Reply> {
+        let found = if task_definition.contains(':') {
+            self.task_definitions.iter().position(|d| {
+                matches(&d.taskDefinitionArn, &format!("{}:{}", d.family, d.revision), task_definition)
+            })
+        } else {
+            self.task_definitions.iter()
+                                 .enumerate()
+                                 .filter(|&(_, d)| d.family == task_definition && d.status == "ACTIVE")
+                                 .max_by_key(|&(_, d)| d.revision)
+                                 .map(|(index, _)| index)
+        };
+        found.ok_or_else(|| client_error("ClientException", "Unable to describe task definition."))
+    }
+
+    /// Finds a task in a cluster by ID or ARN.
+    fn find_task(&self, cluster_arn: &str, task: &str) -> Result<usize, Reply> {
+        match self.tasks.iter().position(|t| t.clusterArn == cluster_arn && matches(&t.taskArn, &t.taskArn, task)) {
+            Some(index) => Ok(index),
+            None => Err(client_error("InvalidParameterException", "The referenced task was not found.")),
+        }
+    }
+
+    /// Finds a container instance in a cluster by ID or ARN.
+    fn find_container_instance(&self, cluster_arn: &str, container_instance: &str) -> Option<usize> {
+        self.container_instances.iter().position(|i| {
+            i.clusterArn == cluster_arn &&
+            matches(&i.containerInstanceArn, &i.containerInstanceArn, container_instance)
+        })
+    }
+
+    /// Finds an ACTIVE capacity provider by name or ARN.
+    fn find_capacity_provider(&self, capacity_provider: &str) -> Result<usize, Reply> {
+        match self.capacity_providers.iter().position(|p| {
+            p.status == "ACTIVE" && matches(&p.capacityProviderArn, &p.name, capacity_provider)
+        }) {
+            Some(index) => Ok(index),
+            None => Err(client_error("ClientException", "The specified capacity provider does not exist.")),
+        }
+    }
+
+    /// Finds a task set of the given service by ID or ARN.
+    fn find_task_set(&self, service: usize, task_set: &str) -> Result<usize, Reply> {
+        match self.services[service].taskSets.iter().position(|t| matches(&t.taskSetArn, &t.id, task_set)) {
+            Some(index) => Ok(index),
+            None => Err(client_error("TaskSetNotFoundException", "The specified task set does not exist.")),
+        }
+    }
+
+    /// Checks that the resourceArn parameter of a request is the ARN of a taggable resource, and
+    /// returns it.
+    fn find_resource(&self, params: &Value) -> Result<String, Reply> {
+        let arn = try!(required_string_param(params, "resourceArn"));
+        let known = self.clusters.iter().any(|c| c.clusterArn == arn) ||
+                    self.services.iter().any(|s| s.serviceArn == arn) ||
+                    self.services.iter().flat_map(|s| s.taskSets.iter()).any(|t| t.taskSetArn == arn) ||
+                    self.task_definitions.iter().any(|d| d.taskDefinitionArn == arn) ||
+                    self.tasks.iter().any(|t| t.taskArn == arn) ||
+                    self.container_instances.iter().any(|i| i.containerInstanceArn == arn) ||
+                    self.capacity_providers.iter().any(|p| p.capacityProviderArn == arn);
+        if known {
+            Ok(arn)
+        } else {
+            Err(client_error("InvalidParameterException", "The specified resource was not found."))
+        }
+    }
+
+    /// Adds an ACTIVE cluster, unless one with the same name already exists, and returns its
+    /// index.
+    fn add_cluster(&mut self, name: &str) -> usize {
+        if let Some(index) = self.clusters.iter().position(|c| c.clusterName == name && c.status == "ACTIVE") {
+            return index;
+        }
+        let arn = self.arn(&format!("cluster/{}", name));
+        self.clusters.push(FakeCluster {
+            clusterName: String::from(name),
+            clusterArn: arn,
+            status: String::from("ACTIVE"),
+            settings: Vec::new(),
+            configuration: None,
+            capacityProviders: Vec::new(),
+            defaultCapacityProviderStrategy: Vec::new(),
+        });
+        self.clusters.len() - 1
+    }
+
+    /// Adds an ACTIVE service running no tasks yet, and returns its index.
+    fn add_service(&mut self, cluster_arn: String, name: &str, task_definition: &str, desired_count: i32) -> usize {
+        let arn = self.arn(&format!("service/{}/{}", short_name(&cluster_arn), name));
+        let deployment = self.deployment(task_definition, desired_count);
+        self.services.push(FakeService {
+            serviceName: String::from(name),
+            serviceArn: arn,
+            clusterArn: cluster_arn,
+            status: String::from("ACTIVE"),
+            taskDefinition: String::from(task_definition),
+            launchType: None,
+            desiredCount: desired_count,
+            runningCount: 0,
+            pendingCount: 0,
+            deployments: vec![deployment],
+            taskSets: Vec::new(),
+        });
+        self.services.len() - 1
+    }
+
+    /// Creates a PRIMARY deployment of the given task definition.
+    fn deployment(&mut self, task_definition: &str, desired_count: i32) -> FakeDeployment {
+        FakeDeployment {
+            id: format!("ecs-svc/{}", self.next_id()),
+            status: String::from("PRIMARY"),
+            taskDefinition: String::from(task_definition),
+            desiredCount: desired_count,
+            runningCount: 0,
+        }
+    }
+
+    /// Registers the next revision of a task definition family, and returns its index.
+    fn add_task_definition(&mut self, family: &str, container_definitions: Vec<Value>) -> usize {
+        let revision = self.task_definitions.iter()
+                                            .filter(|d| d.family == family)
+                                            .map(|d| d.revision)
+                                            .max()
+                                            .unwrap_or(0) + 1;
+        let arn = self.arn(&format!("task-definition/{}:{}", family, revision));
+        self.task_definitions.push(FakeTaskDefinition {
+            taskDefinitionArn: arn,
+            family: String::from(family),
+            revision: revision,
+            status: String::from("ACTIVE"),
+            containerDefinitions: container_definitions,
+            networkMode: None,
+            taskRoleArn: None,
+            executionRoleArn: None,
+            cpu: None,
+            memory: None,
+            requiresCompatibilities: Vec::new(),
+        });
+        self.task_definitions.len() - 1
+    }
+
+    /// Starts a PENDING task, and returns its index.
+    fn start_task(&mut self, cluster_arn: String, task_definition_arn: String) -> usize {
+        let id = self.next_id();
+        let arn = self.arn(&format!("task/{}/{:032x}", short_name(&cluster_arn), id));
+        self.tasks.push(FakeTask {
+            taskArn: arn,
+            clusterArn: cluster_arn,
+            taskDefinitionArn: task_definition_arn,
+            lastStatus: String::from("PENDING"),
+            desiredStatus: String::from("RUNNING"),
+            launchType: None,
+            startedBy: None,
+            stoppedReason: None,
+        });
+        self.tasks.len() - 1
+    }
+
+    /// Registers an ACTIVE container instance, and returns its index.
+    fn add_container_instance(&mut self,
+                              cluster_arn: String,
+                              ec2_instance_id: Option<String>,
+                              version_info: FakeVersionInfo) -> usize {
+        let id = self.next_id();
+        let arn = self.arn(&format!("container-instance/{}/{:032x}", short_name(&cluster_arn), id));
+        self.container_instances.push(FakeContainerInstance {
+            containerInstanceArn: arn,
+            clusterArn: cluster_arn,
+            ec2InstanceId: ec2_instance_id.unwrap_or_else(|| format!("i-{:017x}", id)),
+            status: String::from("ACTIVE"),
+            agentConnected: true,
+            agentUpdateStatus: None,
+            versionInfo: version_info,
+            registeredResources: Vec::new(),
+            remainingResources: Vec::new(),
+            attributes: Vec::new(),
+        });
+        self.container_instances.len() - 1
+    }
+
+    /// Gets the tags of a resource.
+    fn tags_of(&self, arn: &str) -> Vec<FakeTag> {
+        self.tags.get(arn).cloned().unwrap_or(Vec::new())
+    }
+
+    /// Adds tags to a resource, replacing any existing tags with the same keys.
+    fn add_tags(&mut self, arn: &str, tags: Vec<FakeTag>) {
+        if tags.is_empty() {
+            return;
+        }
+        let existing = self.tags.entry(String::from(arn)).or_insert(Vec::new());
+        for tag in tags {
+            existing.retain(|t| t.key != tag.key);
+            existing.push(tag);
+        }
+    }
+
+    /// Advances every simulated state transition by one step.
+    fn tick(&mut self) {
+        for task in self.tasks.iter_mut() {
+            if task.desiredStatus == "STOPPED" {
+                task.lastStatus = String::from("STOPPED");
+            } else if task.lastStatus == "PENDING" {
+                task.lastStatus = String::from("RUNNING");
+            }
+        }
+        for service in self.services.iter_mut() {
+            if service.deployments.len() > 1 {
+                service.deployments.truncate(1);
+                service.deployments[0].runningCount = service.runningCount;
+            } else if service.runningCount != service.desiredCount {
+                service.runningCount += if service.runningCount < service.desiredCount { 1 } else { -1 };
+                service.deployments[0].runningCount = service.runningCount;
+            }
+            for task_set in service.taskSets.iter_mut() {
+                if task_set.runningCount != task_set.computedDesiredCount {
+                    task_set.runningCount += if task_set.runningCount < task_set.computedDesiredCount { 1 } else { -1 };
+                }
+                task_set.stabilityStatus = String::from(if task_set.runningCount == task_set.computedDesiredCount {
+                    "STEADY_STATE"
+                } else {
+                    "STABILIZING"
+                });
+            }
+        }
+        for instance in self.container_instances.iter_mut() {
+            if instance.agentUpdateStatus.as_ref().map_or(false, |status| status == "PENDING") {
+                instance.agentUpdateStatus = Some(String::from("UPDATED"));
+            }
+        }
+    }
+
+    fn create_cluster(&mut self, params: &Value) -> Outcome {
+        let name = string_param(params, "clusterName").unwrap_or(String::from("default"));
+        let index = self.add_cluster(&name);
+        {
+            let cluster = &mut self.clusters[index];
+            merge_settings(&mut cluster.settings, value_list_param(params, "settings"));
+            if let Some(configuration) = params.find("configuration") {
+                cluster.configuration = Some(configuration.clone());
+            }
+            let capacity_providers = string_list_param(params, "capacityProviders");
+            if !capacity_providers.is_empty() {
+                cluster.capacityProviders = capacity_providers;
+            }
+            let strategy = value_list_param(params, "defaultCapacityProviderStrategy");
+            if !strategy.is_empty() {
+                cluster.defaultCapacityProviderStrategy = strategy;
+            }
+        }
+        let arn = self.clusters[index].clusterArn.clone();
+        self.add_tags(&arn, tag_params(params));
+        ok(&ClusterReply { cluster: self.clusters[index].clone() })
+    }
+
+    fn list_clusters(&self, params: &Value) -> Outcome {
+        let arns: Vec<String> = self.clusters.iter()
+                                    .filter(|cluster| cluster.status == "ACTIVE")
+                                    .map(|cluster| cluster.clusterArn.clone())
+                                    .collect();
+        let (arns, next_token) = page(arns, params);
+        ok(&ListClustersReply { clusterArns: arns, nextToken: next_token })
+    }
+
+    /// Handles both UpdateCluster and UpdateClusterSettings, whose parameters only differ in
+    /// that the latter cannot change the configuration.
+    fn update_cluster(&mut self, params: &Value) -> Outcome {
+        let cluster_arn = try!(self.find_cluster(params));
+        let cluster = self.clusters.iter_mut().find(|c| c.clusterArn == cluster_arn).unwrap();
+        merge_settings(&mut cluster.settings, value_list_param(params, "settings"));
+        if let Some(configuration) = params.find("configuration") {
+            cluster.configuration = Some(configuration.clone());
+        }
+        ok(&ClusterReply { cluster: cluster.clone() })
+    }
+
+    fn put_cluster_capacity_providers(&mut self, params: &Value) -> Outcome {
+        let cluster_arn = try!(self.find_cluster(params));
+        let capacity_providers = string_list_param(params, "capacityProviders");
+        for name in &capacity_providers {
+            if !name.starts_with("FARGATE") && self.find_capacity_provider(name).is_err() {
+                return Err(client_error("InvalidParameterException",
+                                        &format!("The specified capacity provider {} was not found.", name)));
+            }
+        }
+        let cluster = self.clusters.iter_mut().find(|c| c.clusterArn == cluster_arn).unwrap();
+        cluster.capacityProviders = capacity_providers;
+        cluster.defaultCapacityProviderStrategy = value_list_param(params, "defaultCapacityProviderStrategy");
+        ok(&ClusterReply { cluster: cluster.clone() })
+    }
+
+    fn register_task_definition(&mut self, params: &Value) -> Outcome {
+        let family = try!(required_string_param(params, "family"));
+        let container_definitions = value_list_param(params, "containerDefinitions");
+        if container_definitions.is_empty() {
+            return Err(client_error("ClientException", "Container list cannot be empty."));
+        }
+        let index = self.add_task_definition(&family, container_definitions);
+        {
+            let definition = &mut self.task_definitions[index];
+            definition.networkMode = string_param(params, "networkMode");
+            definition.taskRoleArn = string_param(params, "taskRoleArn");
+            definition.executionRoleArn = string_param(params, "executionRoleArn");
+            definition.cpu = string_param(params, "cpu");
+            definition.memory = string_param(params, "memory");
+            definition.requiresCompatibilities = string_list_param(params, "requiresCompatibilities");
+        }
+        let arn = self.task_definitions[index].taskDefinitionArn.clone();
+        self.add_tags(&arn, tag_params(params));
+        ok(&TaskDefinitionReply {
+            taskDefinition: self.task_definitions[index].clone(),
+            tags: self.tags_of(&arn),
+        })
+    }
+
+    fn create_service(&mut self, params: &Value) -> Outcome {
+        let cluster_arn = try!(self.find_cluster(params));
+        let name = try!(required_string_param(params, "serviceName"));
+        let definition = try!(self.find_task_definition(&try!(required_string_param(params, "taskDefinition"))));
+        if self.services.iter().any(|s| s.clusterArn == cluster_arn && s.serviceName == name &&
+                                        s.status != "INACTIVE") {
+            return Err(client_error("InvalidParameterException", "Creation of service was not idempotent."));
+        }
+        let task_definition = self.task_definitions[definition].taskDefinitionArn.clone();
+        let desired_count = params.find("desiredCount").and_then(|count| count.as_i64()).unwrap_or(0) as i32;
+        let index = self.add_service(cluster_arn, &name, &task_definition, desired_count);
+        self.services[index].launchType = string_param(params, "launchType");
+        let arn = self.services[index].serviceArn.clone();
+        self.add_tags(&arn, tag_params(params));
+        ok(&ServiceReply { service: self.services[index].clone() })
+    }
+
+    fn describe_services(&self, params: &Value) -> Outcome {
+        let cluster_arn = self.cluster_arn(&string_param(params, "cluster").unwrap_or(String::from("default")));
+        let mut services = Vec::new();
+        let mut failures = Vec::new();
+        for name in string_list_param(params, "services") {
+            match self.services.iter().find(|s| s.clusterArn == cluster_arn &&
+                                                matches(&s.serviceArn, &s.serviceName, &name)) {
+                Some(service) => services.push(service.clone()),
+                None => failures.push(missing(self.arn(&format!("service/{}", name)))),
+            }
+        }
+        ok(&DescribeServicesReply { services: services, failures: failures })
+    }
+
+    fn run_task(&mut self, params: &Value) -> Outcome {
+        let cluster_arn = try!(self.find_cluster(params));
+        let definition = try!(self.find_task_definition(&try!(required_string_param(params, "taskDefinition"))));
+        let task_definition_arn = self.task_definitions[definition].taskDefinitionArn.clone();
+        let count = params.find("count").and_then(|count| count.as_u64()).unwrap_or(1);
+        let tags = tag_params(params);
+        let mut tasks = Vec::new();
+        for _ in 0..count {
+            let index = self.start_task(cluster_arn.clone(), task_definition_arn.clone());
+            self.tasks[index].launchType = string_param(params, "launchType");
+            self.tasks[index].startedBy = string_param(params, "startedBy");
+            let arn = self.tasks[index].taskArn.clone();
+            self.add_tags(&arn, tags.clone());
+            tasks.push(self.tasks[index].clone());
+        }
+        ok(&TasksReply { tasks: tasks, failures: Vec::new() })
+    }
+
+    fn describe_tasks(&self, params: &Value) -> Outcome {
+        let mut tasks = Vec::new();
+        let mut failures = Vec::new();
+        for id in string_list_param(params, "tasks") {
+            match self.tasks.iter().find(|t| matches(&t.taskArn, &t.taskArn, &id)) {
+                Some(task) => tasks.push(task.clone()),
+                None => failures.push(missing(id)),
+            }
+        }
+        ok(&TasksReply { tasks: tasks, failures: failures })
+    }
+
+    fn submit_task_state_change(&mut self, params: &Value) -> Outcome {
+        let cluster_arn = try!(self.find_cluster(params));
+        let index = try!(self.find_task(&cluster_arn, &try!(required_string_param(params, "task"))));
+        let task = &mut self.tasks[index];
+        if let Some(status) = string_param(params, "status") {
+            if status == "STOPPED" {
+                task.desiredStatus = status.clone();
+            }
+            task.lastStatus = status;
+        }
+        if let Some(reason) = string_param(params, "reason") {
+            task.stoppedReason = Some(reason);
+        }
+        ok(&AcknowledgmentReply { acknowledgment: String::from("true") })
+    }
+
+    fn submit_container_state_change(&self, params: &Value) -> Outcome {
+        let cluster_arn = try!(self.find_cluster(params));
+        try!(self.find_task(&cluster_arn, &try!(required_string_param(params, "task"))));
+        ok(&AcknowledgmentReply { acknowledgment: String::from("true") })
+    }
+
+    fn execute_command(&mut self, params: &Value) -> Outcome {
+        let cluster_arn = try!(self.find_cluster(params));
+        let index = try!(self.find_task(&cluster_arn, &try!(required_string_param(params, "task"))));
+        if self.tasks[index].lastStatus != "RUNNING" {
+            return Err(client_error("InvalidParameterException", "The task is not RUNNING."));
+        }
+        let session_id = format!("ecs-execute-command-{:017x}", self.next_id());
+        ok(&ExecuteCommandReply {
+            clusterArn: cluster_arn,
+            containerName: string_param(params, "container"),
+            interactive: params.find("interactive").and_then(|interactive| interactive.as_boolean()).unwrap_or(false),
+            session: FakeSession {
+                streamUrl: format!("wss://ssmmessages.{}.amazonaws.com/v1/data-channel/{}?role=publish_subscribe",
+                                   self.region, session_id),
+                tokenValue: format!("token-{}", session_id),
+                sessionId: session_id,
+            },
+            taskArn: self.tasks[index].taskArn.clone(),
+        })
+    }
+
+    fn register_container_instance(&mut self, params: &Value) -> Outcome {
+        let cluster_arn = try!(self.find_cluster(params));
+        let version_info = version_info_param(params);
+        let existing = string_param(params, "containerInstanceArn")
+            .and_then(|arn| self.find_container_instance(&cluster_arn, &arn));
+        let index = match existing {
+            Some(index) => {
+                self.container_instances[index].versionInfo = version_info;
+                self.container_instances[index].status = String::from("ACTIVE");
+                index
+            },
+            None => {
+                let ec2_instance_id = string_param(params, "instanceIdentityDocument")
+                    .and_then(|document| serde_json::from_str::<Value>(&document).ok())
+                    .and_then(|document| string_param(&document, "instanceId"));
+                self.add_container_instance(cluster_arn, ec2_instance_id, version_info)
+            },
+        };
+        let arn = self.container_instances[index].containerInstanceArn.clone();
+        {
+            let instance = &mut self.container_instances[index];
+            let resources = value_list_param(params, "totalResources");
+            instance.registeredResources = resources.clone();
+            instance.remainingResources = resources;
+            for attribute in value_list_param(params, "attributes") {
+                if let Some(name) = string_param(&attribute, "name") {
+                    put_attribute(&mut instance.attributes, FakeAttribute {
+                        name: name,
+                        value: string_param(&attribute, "value"),
+                        targetType: String::from("container-instance"),
+                        targetId: arn.clone(),
+                    });
+                }
+            }
+        }
+        self.add_tags(&arn, tag_params(params));
+        ok(&ContainerInstanceReply { containerInstance: self.container_instances[index].clone() })
+    }
+
+    fn list_container_instances(&self, params: &Value) -> Outcome {
+        let cluster_arn = self.cluster_arn(&string_param(params, "cluster").unwrap_or(String::from("default")));
+        let status = string_param(params, "status");
+        let arns: Vec<String> = self.container_instances.iter()
+                                    .filter(|instance| instance.clusterArn == cluster_arn)
+                                    .filter(|instance| status.as_ref().map_or(true, |status| instance.status == *status))
+                                    .map(|instance| instance.containerInstanceArn.clone())
+                                    .collect();
+        let (arns, next_token) = page(arns, params);
+        ok(&ListContainerInstancesReply { containerInstanceArns: arns, nextToken: next_token })
+    }
+
+    fn describe_container_instances(&self, params: &Value) -> Outcome {
+        let mut instances = Vec::new();
+        let mut failures = Vec::new();
+        for id in string_list_param(params, "containerInstances") {
+            match self.container_instances.iter().find(|i| matches(&i.containerInstanceArn,
+                                                                   &i.containerInstanceArn,
+                                                                   &id)) {
+                Some(instance) => instances.push(instance.clone()),
+                None => failures.push(missing(id)),
+            }
+        }
+        ok(&DescribeContainerInstancesReply { containerInstances: instances, failures: failures })
+    }
+
+    fn update_container_agent(&mut self, params: &Value) -> Outcome {
+        let cluster_arn = try!(self.find_cluster(params));
+        let container_instance = try!(required_string_param(params, "containerInstance"));
+        let index = match self.find_container_instance(&cluster_arn, &container_instance) {
+            Some(index) => index,
+            None => return Err(client_error("InvalidParameterException",
+                                            "The referenced container instance was not found.")),
+        };
+        let instance = &mut self.container_instances[index];
+        if instance.agentUpdateStatus.as_ref().map_or(false, |status| status == "PENDING") {
+            return Err(client_error("UpdateInProgressException", "An agent update is already in progress."));
+        }
+        instance.agentUpdateStatus = Some(String::from("PENDING"));
+        ok(&ContainerInstanceReply { containerInstance: instance.clone() })
+    }
+
+    fn discover_poll_endpoint(&self, _params: &Value) -> Outcome {
+        ok(&DiscoverPollEndpointReply {
+            endpoint: format!("https://ecs-a-1.{}.amazonaws.com/", self.region),
+            telemetryEndpoint: format!("https://ecs-t-1.{}.amazonaws.com/", self.region),
+        })
+    }
+
+    fn put_attributes(&mut self, params: &Value) -> Outcome {
+        let cluster_arn = try!(self.find_cluster(params));
+        let attributes = try!(self.attribute_params(&cluster_arn, params));
+        for &(index, ref attribute) in &attributes {
+            put_attribute(&mut self.container_instances[index].attributes, attribute.clone());
+        }
+        ok(&AttributesReply { attributes: attributes.into_iter().map(|(_, attribute)| attribute).collect() })
+    }
+
+    fn delete_attributes(&mut self, params: &Value) -> Outcome {
+        let cluster_arn = try!(self.find_cluster(params));
+        let attributes = try!(self.attribute_params(&cluster_arn, params));
+        for &(index, ref attribute) in &attributes {
+            self.container_instances[index].attributes.retain(|existing| existing.name != attribute.name);
+        }
+        ok(&AttributesReply { attributes: attributes.into_iter().map(|(_, attribute)| attribute).collect() })
+    }
+
+    /// Reads the attributes parameter of a PutAttributes or DeleteAttributes request, resolving
+    /// the container instance each attribute targets.
+    fn attribute_params(&self, cluster_arn: &str, params: &Value) -> Result<Vec<(usize, FakeAttribute)>, Reply> {
+        let mut attributes = Vec::new();
+        for attribute in value_list_param(params, "attributes") {
+            let name = try!(required_string_param(&attribute, "name"));
+            let target = try!(required_string_param(&attribute, "targetId"));
+            let index = match self.find_container_instance(cluster_arn, &target) {
+                Some(index) => index,
+                None => return Err(client_error("TargetNotFoundException", &format!("Could not find {}.", target))),
+            };
+            attributes.push((index, FakeAttribute {
+                name: name,
+                value: string_param(&attribute, "value"),
+                targetType: String::from("container-instance"),
+                targetId: self.container_instances[index].containerInstanceArn.clone(),
+            }));
+        }
+        Ok(attributes)
+    }
+
+    fn list_attributes(&self, params: &Value) -> Outcome {
+        let cluster_arn = try!(self.find_cluster(params));
+        let target_type = try!(required_string_param(params, "targetType"));
+        if target_type != "container-instance" {
+            return Err(client_error("InvalidParameterException", &format!("Unknown target type {}.", target_type)));
+        }
+        let name = string_param(params, "attributeName");
+        let value = string_param(params, "attributeValue");
+        let attributes: Vec<FakeAttribute> = self.container_instances.iter()
+            .filter(|instance| instance.clusterArn == cluster_arn)
+            .flat_map(|instance| instance.attributes.iter())
+            .filter(|attribute| name.as_ref().map_or(true, |name| attribute.name == *name))
+            .filter(|attribute| value.is_none() || attribute.value == value)
+            .cloned()
+            .collect();
+        let (attributes, next_token) = page(attributes, params);
+        ok(&ListAttributesReply { attributes: attributes, nextToken: next_token })
+    }
+
+    fn tag_resource(&mut self, params: &Value) -> Outcome {
+        let arn = try!(self.find_resource(params));
+        self.add_tags(&arn, tag_params(params));
+        Ok(String::from("{}"))
+    }
+
+    fn untag_resource(&mut self, params: &Value) -> Outcome {
+        let arn = try!(self.find_resource(params));
+        let keys = string_list_param(params, "tagKeys");
+        if let Some(tags) = self.tags.get_mut(&arn) {
+            tags.retain(|tag| !keys.contains(&tag.key));
+        }
+        Ok(String::from("{}"))
+    }
+
+    fn list_tags_for_resource(&self, params: &Value) -> Outcome {
+        let arn = try!(self.find_resource(params));
+        ok(&TagsReply { tags: self.tags_of(&arn) })
+    }
+
+    fn create_capacity_provider(&mut self, params: &Value) -> Outcome {
+        let name = try!(required_string_param(params, "name"));
+        let provider = match params.find("autoScalingGroupProvider") {
+            Some(provider) => provider.clone(),
+            None => return Err(missing_param("autoScalingGroupProvider")),
+        };
+        if self.find_capacity_provider(&name).is_ok() {
+            return Err(client_error("ClientException", &format!("The capacity provider {} already exists.", name)));
+        }
+        let arn = self.arn(&format!("capacity-provider/{}", name));
+        self.capacity_providers.push(FakeCapacityProvider {
+            capacityProviderArn: arn.clone(),
+            name: name,
+            status: String::from("ACTIVE"),
+            autoScalingGroupProvider: provider,
+            updateStatus: None,
+        });
+        self.add_tags(&arn, tag_params(params));
+        ok(&CapacityProviderReply { capacityProvider: self.capacity_providers.last().unwrap().clone() })
+    }
+
+    fn update_capacity_provider(&mut self, params: &Value) -> Outcome {
+        let index = try!(self.find_capacity_provider(&try!(required_string_param(params, "name"))));
+        let provider = &mut self.capacity_providers[index];
+        if let Some(&Value::Object(ref update)) = params.find("autoScalingGroupProvider") {
+            if let Value::Object(ref mut existing) = provider.autoScalingGroupProvider {
+                for (key, value) in update {
+                    existing.insert(key.clone(), value.clone());
+                }
+            }
+        }
+        provider.updateStatus = Some(String::from("UPDATE_COMPLETE"));
+        ok(&CapacityProviderReply { capacityProvider: provider.clone() })
+    }
+
+    fn delete_capacity_provider(&mut self, params: &Value) -> Outcome {
+        let index = try!(self.find_capacity_provider(&try!(required_string_param(params, "capacityProvider"))));
+        let name = self.capacity_providers[index].name.clone();
+        if self.clusters.iter().any(|c| c.status == "ACTIVE" && c.capacityProviders.contains(&name)) {
+            return Err(client_error("ResourceInUseException",
+                                    "The specified capacity provider is in use and cannot be removed."));
+        }
+        let provider = &mut self.capacity_providers[index];
+        provider.status = String::from("INACTIVE");
+        provider.updateStatus = Some(String::from("DELETE_COMPLETE"));
+        ok(&CapacityProviderReply { capacityProvider: provider.clone() })
+    }
+
+    fn describe_capacity_providers(&self, params: &Value) -> Outcome {
+        let names = string_list_param(params, "capacityProviders");
+        let mut capacity_providers = Vec::new();
+        let mut failures = Vec::new();
+        if names.is_empty() {
+            capacity_providers = self.capacity_providers.iter()
+                                                        .filter(|provider| provider.status == "ACTIVE")
+                                                        .cloned()
+                                                        .collect();
+        }
+        for name in names {
+            match self.capacity_providers.iter().rev().find(|p| matches(&p.capacityProviderArn, &p.name, &name)) {
+                Some(provider) => capacity_providers.push(provider.clone()),
+                None => failures.push(missing(self.arn(&format!("capacity-provider/{}", name)))),
+            }
+        }
+        let (capacity_providers, next_token) = page(capacity_providers, params);
+        ok(&DescribeCapacityProvidersReply {
+            capacityProviders: capacity_providers,
+            failures: failures,
+            nextToken: next_token,
+        })
+    }
+
+    fn put_account_setting(&mut self, params: &Value) -> Outcome {
+        let principal_arn = string_param(params, "principalArn").unwrap_or_else(|| self.root_arn());
+        self.put_setting(params, principal_arn)
+    }
+
+    /// Every request comes from the root user, so the account default is the root user's own
+    /// setting.
+    fn put_account_setting_default(&mut self, params: &Value) -> Outcome {
+        let principal_arn = self.root_arn();
+        self.put_setting(params, principal_arn)
+    }
+
+    /// Sets the account setting named by a request for the given principal.
+    fn put_setting(&mut self, params: &Value, principal_arn: String) -> Outcome {
+        let name = try!(required_string_param(params, "name"));
+        let value = try!(required_string_param(params, "value"));
+        self.account_settings.retain(|s| !(s.name == name && s.principalArn == principal_arn));
+        let setting = FakeSetting {
+            name: name,
+            value: value,
+            principalArn: principal_arn,
+        };
+        self.account_settings.push(setting.clone());
+        ok(&SettingReply { setting: setting })
+    }
+
+    fn list_account_settings(&self, params: &Value) -> Outcome {
+        let principal_arn = string_param(params, "principalArn").unwrap_or_else(|| self.root_arn());
+        let name = string_param(params, "name");
+        let value = string_param(params, "value");
+        let settings: Vec<FakeSetting> = self.account_settings.iter()
+            .filter(|setting| setting.principalArn == principal_arn)
+            .filter(|setting| name.as_ref().map_or(true, |name| setting.name == *name))
+            .filter(|setting| value.as_ref().map_or(true, |value| setting.value == *value))
+            .cloned()
+            .collect();
+        let (settings, next_token) = page(settings, params);
+        ok(&ListAccountSettingsReply { settings: settings, nextToken: next_token })
+    }
+
+    fn delete_account_setting(&mut self, params: &Value) -> Outcome {
+        let name = try!(required_string_param(params, "name"));
+        let principal_arn = string_param(params, "principalArn").unwrap_or_else(|| self.root_arn());
+        let index = self.account_settings.iter().position(|s| s.name == name && s.principalArn == principal_arn);
+        match index {
+            Some(index) => ok(&SettingReply { setting: self.account_settings.remove(index) }),
+            None => Err(client_error("InvalidParameterException", "The specified setting was not found.")),
+        }
+    }
+
+    fn create_task_set(&mut self, params: &Value) -> Outcome {
+        let service = try!(self.find_service(params));
+        let definition = try!(self.find_task_definition(&try!(required_string_param(params, "taskDefinition"))));
+        let id = format!("ecs-svc/{}", self.next_id());
+        let arn = self.arn(&format!("task-set/{}/{}/{}",
+                                    short_name(&self.services[service].clusterArn),
+                                    self.services[service].serviceName,
+                                    id));
+        let scale = params.find("scale")
+                          .cloned()
+                          .unwrap_or_else(|| serde_json::from_str("{\"value\":100.0,\"unit\":\"PERCENT\"}").unwrap());
+        let task_set = FakeTaskSet {
+            id: id,
+            taskSetArn: arn.clone(),
+            serviceArn: self.services[service].serviceArn.clone(),
+            clusterArn: self.services[service].clusterArn.clone(),
+            externalId: string_param(params, "externalId"),
+            status: String::from("ACTIVE"),
+            taskDefinition: self.task_definitions[definition].taskDefinitionArn.clone(),
+            launchType: string_param(params, "launchType"),
+            computedDesiredCount: computed_desired_count(self.services[service].desiredCount, &scale),
+            pendingCount: 0,
+            runningCount: 0,
+            scale: scale,
+            stabilityStatus: String::from("STABILIZING"),
+        };
+        self.services[service].taskSets.push(task_set.clone());
+        self.add_tags(&arn, tag_params(params));
+        ok(&TaskSetReply { taskSet: task_set })
+    }
+
+    fn update_task_set(&mut self, params: &Value) -> Outcome {
+        let service = try!(self.find_service(params));
+        let index = try!(self.find_task_set(service, &try!(required_string_param(params, "taskSet"))));
+        let scale = match params.find("scale") {
+            Some(scale) => scale.clone(),
+            None => return Err(missing_param("scale")),
+        };
+        let desired_count = self.services[service].desiredCount;
+        let task_set = &mut self.services[service].taskSets[index];
+        task_set.computedDesiredCount = computed_desired_count(desired_count, &scale);
+        task_set.scale = scale;
+        if task_set.runningCount != task_set.computedDesiredCount {
+            task_set.stabilityStatus = String::from("STABILIZING");
+        }
+        ok(&TaskSetReply { taskSet: task_set.clone() })
+    }
+
+    fn delete_task_set(&mut self, params: &Value) -> Outcome {
+        let service = try!(self.find_service(params));
+        let index = try!(self.find_task_set(service, &try!(required_string_param(params, "taskSet"))));
+        let force = params.find("force").and_then(|force| force.as_boolean()).unwrap_or(false);
+        if self.services[service].taskSets[index].status == "PRIMARY" && !force {
+            return Err(client_error("InvalidParameterException",
+                                    "The primary task set of a service cannot be deleted unless forced."));
+        }
+        let mut task_set = self.services[service].taskSets.remove(index);
+        task_set.status = String::from("DRAINING");
+        ok(&TaskSetReply { taskSet: task_set })
+    }
+
+    fn describe_task_sets(&self, params: &Value) -> Outcome {
+        let service = try!(self.find_service(params));
+        let ids = string_list_param(params, "taskSets");
+        let mut task_sets = Vec::new();
+        let mut failures = Vec::new();
+        if ids.is_empty() {
+            task_sets = self.services[service].taskSets.clone();
+        }
+        for id in ids {
+            match self.find_task_set(service, &id) {
+                Ok(index) => task_sets.push(self.services[service].taskSets[index].clone()),
+                Err(_) => failures.push(missing(id)),
+            }
+        }
+        ok(&DescribeTaskSetsReply { taskSets: task_sets, failures: failures })
+    }
+
+    fn update_service_primary_task_set(&mut self, params: &Value) -> Outcome {
+        let service = try!(self.find_service(params));
+        let index = try!(self.find_task_set(service, &try!(required_string_param(params, "primaryTaskSet"))));
+        for task_set in self.services[service].taskSets.iter_mut() {
+            if task_set.status == "PRIMARY" {
+                task_set.status = String::from("ACTIVE");
+            }
+        }
+        let task_set = &mut self.services[service].taskSets[index];
+        task_set.status = String::from("PRIMARY");
+        ok(&TaskSetReply { taskSet: task_set.clone() })
+    }
+}
+
+#[derive(Serialize)]
+struct ClusterReply {
+    cluster: FakeCluster,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize)]
+struct ListClustersReply {
+    clusterArns: Vec<String>,
+    nextToken: Option<String>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize)]
+struct TaskDefinitionReply {
+    taskDefinition: FakeTaskDefinition,
+    tags: Vec<FakeTag>,
+}
+
+#[derive(Serialize)]
+struct ServiceReply {
+    service: FakeService,
+}
+
+#[derive(Serialize)]
+struct DescribeServicesReply {
+    services: Vec<FakeService>,
+    failures: Vec<FakeFailure>,
+}
+
+#[derive(Serialize)]
+struct TasksReply {
+    tasks: Vec<FakeTask>,
+    failures: Vec<FakeFailure>,
+}
+
+#[derive(Serialize)]
+struct AcknowledgmentReply {
+    acknowledgment: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize)]
+struct ExecuteCommandReply {
+    clusterArn: String,
+    containerName: Option<String>,
+    interactive: bool,
+    session: FakeSession,
+    taskArn: String,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize)]
+struct ContainerInstanceReply {
+    containerInstance: FakeContainerInstance,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize)]
+struct ListContainerInstancesReply {
+    containerInstanceArns: Vec<String>,
+    nextToken: Option<String>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize)]
+struct DescribeContainerInstancesReply {
+    containerInstances: Vec<FakeContainerInstance>,
+    failures: Vec<FakeFailure>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize)]
+struct DiscoverPollEndpointReply {
+    endpoint: String,
+    telemetryEndpoint: String,
+}
+
+#[derive(Serialize)]
+struct AttributesReply {
+    attributes: Vec<FakeAttribute>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize)]
+struct ListAttributesReply {
+    attributes: Vec<FakeAttribute>,
+    nextToken: Option<String>,
+}
+
+#[derive(Serialize)]
+struct TagsReply {
+    tags: Vec<FakeTag>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize)]
+struct CapacityProviderReply {
+    capacityProvider: FakeCapacityProvider,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize)]
+struct DescribeCapacityProvidersReply {
+    capacityProviders: Vec<FakeCapacityProvider>,
+    failures: Vec<FakeFailure>,
+    nextToken: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SettingReply {
+    setting: FakeSetting,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize)]
+struct ListAccountSettingsReply {
+    settings: Vec<FakeSetting>,
+    nextToken: Option<String>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize)]
+struct TaskSetReply {
+    taskSet: FakeTaskSet,
+}
+
+#[allow(non_snake_case)]
+#[derive(Serialize)]
+struct DescribeTaskSetsReply {
+    taskSets: Vec<FakeTaskSet>,
+    failures: Vec<FakeFailure>,
+}
+
+/// Checks that a request carries every signed header and an Authorization header matching the
+/// signature computed for its path from the credentials in the environment.
+fn verify_signature(headers: &Headers, path: &str, body: &str, region: &Region) -> Result<(), Reply> {
+    if !(headers.has::<AcceptEncoding>() && headers.has::<ContentLength>() && headers.has::<ContentType>() &&
+         headers.has::<Host>() && headers.has::<XAmzDate>() && headers.has::<XAmzTarget>()) {
+        return Err(client_error("IncompleteSignatureException",
+                                "Accept-Encoding, Content-Length, Content-Type, Host, X-Amz-Date and \
+                                 X-Amz-Target must be present and signed"));
+    }
+    let credentials = match EnvironmentProvider.get_credentials() {
+        Ok(credentials) => credentials,
+        Err(error) => return Err((StatusCode::Forbidden,
+                                  error_body("UnrecognizedClientException", &error.to_string()))),
+    };
+    let authorization = match headers.get::<Authorization<String>>() {
+        Some(authorization) => authorization.0.clone(),
+        None => return Err((StatusCode::Forbidden,
+                            error_body("MissingAuthenticationTokenException", "Missing Authentication Token"))),
+    };
+    if authorization != signature::build_auth_header_for_path(headers, path, body, &credentials, region, "ecs") {
+        return Err((StatusCode::Forbidden,
+                    error_body("InvalidSignatureException",
+                               "The request signature we calculated does not match the signature you provided.")));
+    }
+    Ok(())
+}
+
+/// Returns true if the given name or ARN refers to the resource with the given ARN and name.
+fn matches(arn: &str, name: &str, name_or_arn: &str) -> bool {
+    arn == name_or_arn || name == name_or_arn || arn.ends_with(&format!("/{}", name_or_arn))
+}
+
+/// Returns the last component of a name or ARN.
+fn short_name(name_or_arn: &str) -> String {
+    String::from(name_or_arn.rsplit('/').next().unwrap_or(name_or_arn))
+}
+
+/// Selects the page of items requested by the maxResults and nextToken of a request, returning
+/// it along with the nextToken of the following page.
+fn page<T>(items: Vec<T>, params: &Value) -> (Vec<T>, Option<String>) {
+    let start = string_param(params, "nextToken").and_then(|token| token.parse::<usize>().ok()).unwrap_or(0);
+    let max_results = params.find("maxResults")
+                            .and_then(|max| max.as_u64())
+                            .map(|max| max as usize)
+                            .unwrap_or(DEFAULT_MAX_RESULTS);
+    let total = items.len();
+    let page: Vec<T> = items.into_iter().skip(start).take(max_results).collect();
+    let next_token = if start + page.len() < total {
+        Some((start + page.len()).to_string())
+    } else {
+        None
+    };
+    (page, next_token)
+}
+
+/// Replaces the settings with the same names as the given ones, and adds the others.
+fn merge_settings(settings: &mut Vec<Value>, updates: Vec<Value>) {
+    for update in updates {
+        let name = string_param(&update, "name");
+        settings.retain(|setting| string_param(setting, "name") != name);
+        settings.push(update);
+    }
+}
+
+/// Replaces the attribute with the same name as the given one, or adds it.
+fn put_attribute(attributes: &mut Vec<FakeAttribute>, attribute: FakeAttribute) {
+    attributes.retain(|existing| existing.name != attribute.name);
+    attributes.push(attribute);
+}
+
+/// Computes the number of tasks a task set should run from the desired count of its service.
+fn computed_desired_count(desired_count: i32, scale: &Value) -> i32 {
+    let percent = scale.find("value").and_then(|value| value.as_f64()).unwrap_or(100.0);
+    (desired_count as f64 * percent / 100.0).ceil() as i32
+}
+
+fn version_info_param(params: &Value) -> FakeVersionInfo {
+    let version_info = params.find("versionInfo").cloned().unwrap_or(Value::Null);
+    FakeVersionInfo {
+        agentVersion: string_param(&version_info, "agentVersion"),
+        agentHash: string_param(&version_info, "agentHash"),
+        dockerVersion: string_param(&version_info, "dockerVersion"),
+    }
+}
+
+fn tag_params(params: &Value) -> Vec<FakeTag> {
+    value_list_param(params, "tags").iter()
+                                    .filter_map(|tag| {
+                                        string_param(tag, "key").map(|key| {
+                                            FakeTag { key: key, value: string_param(tag, "value") }
+                                        })
+                                    })
+                                    .collect()
+}
+
+fn string_param(params: &Value, name: &str) -> Option<String> {
+    params.find(name).and_then(|value| value.as_string()).map(String::from)
+}
+
+fn required_string_param(params: &Value, name: &str) -> Result<String, Reply> {
+    string_param(params, name).ok_or_else(|| missing_param(name))
+}
+
+fn string_list_param(params: &Value, name: &str) -> Vec<String> {
+    match params.find(name).and_then(|value| value.as_array()) {
+        Some(values) => values.iter().filter_map(|value| value.as_string()).map(String::from).collect(),
+        None => Vec::new(),
+    }
+}
+
+fn value_list_param(params: &Value, name: &str) -> Vec<Value> {
+    params.find(name).and_then(|value| value.as_array()).cloned().unwrap_or(Vec::new())
+}
+
+fn missing(arn: String) -> FakeFailure {
+    FakeFailure {
+        arn: arn,
+        reason: String::from("MISSING"),
+    }
+}
+
+fn ok<T: Serialize>(reply: &T) -> Outcome {
+    Ok(serde_json::to_string(reply).unwrap())
+}
+
+fn missing_param(name: &str) -> Reply {
+    client_error("InvalidParameterException", &format!("{} is required.", name))
+}
+
+fn client_error(error_type: &str, message: &str) -> Reply {
+    (StatusCode::BadRequest, error_body(error_type, message))
+}
+
+fn error_body(error_type: &str, message: &str) -> String {
+    let mut body = BTreeMap::new();
+    body.insert(String::from("__type"), Value::String(String::from(error_type)));
+    body.insert(String::from("message"), Value::String(String::from(message)));
+    serde_json::to_string(&Value::Object(body)).unwrap()
+}
@@ -0,0 +1,82 @@
+//! This module contains the Interceptor trait, whose hooks the ECSClient calls at each stage of
+//! an operation, so that custom headers, audit logging, metrics or fault injection can be added
+//! without changing the client.  Register interceptors with ECSClient::add_interceptor(); each
+//! hook is called on every interceptor in the order they were registered.
+//!
+//! Every hook but on_error() may fail the current attempt by returning an Err, which is then
+//! handled like any other failed attempt: it is passed to on_error() and retried if the
+//! RetryPolicy allows, so returning a retryable error, such as a ThrottlingException, simulates a
+//! transient fault.
+
+use error::ECSError;
+use logging::RequestContext;
+use transport::{HttpRequest, HttpResponse};
+
+use hyper::header::Headers;
+use serde_json::Value;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Hooks into the stages of an ECSClient operation.  Every hook does nothing by default.
+pub trait Interceptor : Debug + Send + Sync {
+    /// Called once per operation, with the request as a json value before it is serialized into
+    /// the body of the HTTP request.  The context names attempt 1.
+    fn before_serialization(&self, _context: &RequestContext, _input: &mut Value) -> Result<(), ECSError> {
+        Ok(())
+    }
+
+    /// Called on every attempt with the headers of the request before it is signed.  Headers
+    /// added here are sent, but not covered by the signature.
+    fn before_signing(&self, _context: &RequestContext, _headers: &mut Headers) -> Result<(), ECSError> {
+        Ok(())
+    }
+
+    /// Called on every attempt with the signed request, which can no longer be changed without
+    /// invalidating its signature.
+    fn after_signing(&self, _context: &RequestContext, _request: &HttpRequest) -> Result<(), ECSError> {
+        Ok(())
+    }
+
+    /// Called on every attempt just before the request is handed to the transport.  Returning an
+    /// Err fails the attempt without sending anything.
+    fn before_transmit(&self, _context: &RequestContext, _request: &HttpRequest) -> Result<(), ECSError> {
+        Ok(())
+    }
+
+    /// Called on every attempt which receives a response, whatever its status code, before the
+    /// response is interpreted.
+    fn after_response(&self, _context: &RequestContext, _response: &HttpResponse) -> Result<(), ECSError> {
+        Ok(())
+    }
+
+    /// Called on every failed attempt with the error it failed with, before deciding whether to
+    /// retry.
+    fn on_error(&self, _context: &RequestContext, _error: &ECSError) {}
+}
+
+/// Used so that an interceptor can be shared between a client and the code inspecting it.
+impl<T: Interceptor> Interceptor for Arc<T> {
+    fn before_serialization(&self, context: &RequestContext, input: &mut Value) -> Result<(), ECSError> {
+        (**self).before_serialization(context, input)
+    }
+
+    fn before_signing(&self, context: &RequestContext, headers: &mut Headers) -> Result<(), ECSError> {
+        (**self).before_signing(context, headers)
+    }
+
+    fn after_signing(&self, context: &RequestContext, request: &HttpRequest) -> Result<(), ECSError> {
+        (**self).after_signing(context, request)
+    }
+
+    fn before_transmit(&self, context: &RequestContext, request: &HttpRequest) -> Result<(), ECSError> {
+        (**self).before_transmit(context, request)
+    }
+
+    fn after_response(&self, context: &RequestContext, response: &HttpResponse) -> Result<(), ECSError> {
+        (**self).after_response(context, response)
+    }
+
+    fn on_error(&self, context: &RequestContext, error: &ECSError) {
+        (**self).on_error(context, error)
+    }
+}
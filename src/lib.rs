@@ -13,6 +13,26 @@
 //! $ export AWS_SECRET_ACCESS_KEY="your_secret_access_key"
 //! ```
 //!
+//! If they are not set in the environment, credentials are read from the active profile of
+//! ~/.aws/credentials, the role of the ECS task, or the role of the EC2 instance, in that order;
+//! see the credentials module.  ECSClient::from_env() likewise resolves the Region from
+//! AWS_REGION, AWS_DEFAULT_REGION, ~/.aws/config or instance metadata.
+//! Use ECSClient::builder() to also set timeouts, a proxy or an app ID for the User-Agent header;
+//! see the builder module.
+//!
+//! The client logs each request through the `log` crate, with credentials redacted; see the
+//! logging module.
+//! The *_with_metadata() variant of each action, e.g. ECSClient::list_clusters_with_metadata(),
+//! also returns the request ID, status, attempts and latency of an operation; see the response
+//! module.
+//! Register an Interceptor with ECSClient::add_interceptor() to hook into every stage of an
+//! operation; see the interceptor module.
+//! Per-action request, error, retry and throttle counts and latencies can be collected with
+//! ECSClient::set_metrics_sink() and rendered for Prometheus; see the metrics module.
+//! PooledECSClient sends requests on a bounded pool of worker threads and returns futures of
+//! their responses.  It is not a non-blocking client: each request still holds a thread while it
+//! is on the wire; see the pooled_client module.
+//!
 //! With these credentials created and stored appropriately, the ECSClient should be able to make
 //! requests to Amazon ECS on your behalf.  Simply create an ECSClient, build a request, and call
 //! the relevent API action on the client.  For example (note that the code below should panic if
@@ -28,7 +48,7 @@
 //! fn main() {
 //!     let ecs_client = ECSClient::for_region(Region::USWest2);
 //!     let request = list_clusters::ListClustersRequest::new();
-//!     let response: list_clusters::ListClustersResponse = ecs_client.list_clusters(request);
+//!     let response: list_clusters::ListClustersResponse = ecs_client.list_clusters(request).unwrap();
 //!     for cluster_arn in response.get_cluster_arns() {
 //!         println!("{}", cluster_arn);
 //!     }
@@ -41,8 +61,12 @@
 extern crate serde;
 extern crate serde_json;
 #[macro_use] extern crate hyper;
+#[macro_use] extern crate log;
 extern crate time;
 extern crate sodiumoxide; // for bindings to a crypto library
+extern crate openssl;
+extern crate futures;
+extern crate futures_cpupool;
 
 #[cfg(feature = "serde_macros")]
 include!("lib.rs.in");
@@ -0,0 +1,162 @@
+//! This module contains the helpers the ECSClient uses to log its requests through the `log`
+//! facade without leaking credentials.  Nothing is printed unless the application installs a
+//! logger, such as env_logger, and every line about a request starts with the RequestContext,
+//! e.g. "action=ListClusters region=us-west-2 attempt=2", so lines can be filtered per request.
+//!
+//! At debug level each attempt and its status, request ID and latency are logged, and at trace
+//! level its headers and bodies too.  Authorization and X-Amz-Security-Token header values, and
+//! json fields which hold secrets or tokens, are replaced with "<redacted>".
+
+use action::ecs_action::ECSAction;
+use region::Region;
+
+use hyper::header::Headers;
+use serde_json::{self, Value};
+use std::fmt::{self, Display, Formatter};
+
+/// The text which replaces a redacted value.
+pub const REDACTED: &'static str = "<redacted>";
+/// The lowercase names of the headers whose values are redacted.
+const SENSITIVE_HEADERS: &'static [&'static str] = &["authorization", "x-amz-security-token"];
+/// Lowercase fragments of the names of json fields whose values are redacted.
+const SENSITIVE_FIELDS: &'static [&'static str] = &["secret", "password", "sessiontoken", "tokenvalue", "credential"];
+
+/// Identifies one attempt at an ECS request in log lines, and to interceptors.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RequestContext<'a> {
+    action: ECSAction,
+    region: &'a Region,
+    attempt: u32,
+}
+
+impl<'a> RequestContext<'a> {
+    /// Creates the context of the given attempt (counting from 1) at an action in a Region.
+    pub fn new(action: ECSAction, region: &'a Region, attempt: u32) -> Self {
+        RequestContext {
+            action: action,
+            region: region,
+            attempt: attempt,
+        }
+    }
+
+    /// Gets the action being requested.
+    pub fn get_action(&self) -> ECSAction {
+        self.action
+    }
+
+    /// Gets the Region the request is sent to.
+    pub fn get_region(&self) -> &Region {
+        self.region
+    }
+
+    /// Gets the attempt, counting from 1.
+    pub fn get_attempt(&self) -> u32 {
+        self.attempt
+    }
+}
+
+impl<'a> Display for RequestContext<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "action={} region={} attempt={}", self.action, self.region, self.attempt)
+    }
+}
+
+/// Formats the headers one per line, as "Name: value", with the values of the Authorization and
+/// X-Amz-Security-Token headers redacted.
+pub fn redact_headers(headers: &Headers) -> String {
+    let mut lines = Vec::new();
+    for header in headers.iter() {
+        let name = header.name();
+        if SENSITIVE_HEADERS.contains(&&name.to_lowercase()[..]) {
+            lines.push(format!("{}: {}", name, REDACTED));
+        } else {
+            lines.push(format!("{}: {}", name, header.value_string()));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Returns the json body with the values of fields such as "tokenValue" or "secretAccessKey"
+/// redacted, at any depth.  A body which is not json is redacted entirely, since it cannot be
+/// inspected.
+pub fn redact_body(body: &str) -> String {
+    if body.is_empty() {
+        return String::new();
+    }
+    match serde_json::from_str::<Value>(body) {
+        Ok(mut value) => {
+            redact_value(&mut value);
+            serde_json::to_string(&value).unwrap_or_else(|_| String::from(REDACTED))
+        },
+        Err(_) => String::from(REDACTED),
+    }
+}
+
+/// Redacts the sensitive fields of a json value in place.
+fn redact_value(value: &mut Value) {
+    match *value {
+        Value::Object(ref mut fields) => {
+            for (key, field) in fields.iter_mut() {
+                if is_sensitive_field(key) {
+                    *field = Value::String(String::from(REDACTED));
+                } else {
+                    redact_value(field);
+                }
+            }
+        },
+        Value::Array(ref mut values) => {
+            for value in values.iter_mut() {
+                redact_value(value);
+            }
+        },
+        _ => (),
+    }
+}
+
+/// Returns true if a json field of the given name holds a secret.
+fn is_sensitive_field(key: &str) -> bool {
+    let key = key.to_lowercase();
+    SENSITIVE_FIELDS.iter().any(|fragment| key.contains(fragment))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{redact_body, redact_headers, RequestContext, REDACTED};
+    use action::ecs_action::ECSAction;
+    use custom_headers::{XAmzSecurityToken, XAmzTarget};
+    use region::Region;
+    use hyper::header::{Authorization, Headers};
+
+    #[test]
+    fn test_redact_headers() {
+        let mut headers = Headers::new();
+        headers.set(Authorization(String::from("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/..., Signature=abc")));
+        headers.set(XAmzSecurityToken(String::from("session-token")));
+        headers.set(XAmzTarget(String::from("AmazonEC2ContainerServiceV20141113.ListClusters")));
+        let redacted = redact_headers(&headers);
+        assert!(redacted.contains(&format!("Authorization: {}", REDACTED)));
+        assert!(redacted.contains(&format!("X-Amz-Security-Token: {}", REDACTED)));
+        assert!(redacted.contains("X-Amz-Target: AmazonEC2ContainerServiceV20141113.ListClusters"));
+        assert!(!redacted.contains("AKIDEXAMPLE"));
+        assert!(!redacted.contains("session-token"));
+    }
+
+    #[test]
+    fn test_redact_body() {
+        let body = "{\"session\":{\"sessionId\":\"s-1\",\"streamUrl\":\"wss://x\",\"tokenValue\":\"secret\"},\
+                    \"clusterArns\":[\"a\"]}";
+        let redacted = redact_body(body);
+        assert!(!redacted.contains("\"secret\""));
+        assert!(redacted.contains("\"sessionId\":\"s-1\""));
+        assert!(redacted.contains("\"clusterArns\":[\"a\"]"));
+        assert_eq!(REDACTED, redact_body("<html>not json</html>"));
+        assert_eq!("", redact_body(""));
+    }
+
+    #[test]
+    fn test_request_context() {
+        let region = Region::USWest2;
+        let context = RequestContext::new(ECSAction::ListClusters, &region, 2);
+        assert_eq!("action=ListClusters region=us-west-2 attempt=2", context.to_string());
+    }
+}
@@ -0,0 +1,203 @@
+//! This module contains clients for the link-local metadata services of EC2 and ECS, which supply
+//! the region and temporary credentials of the instance or task the process is running on.
+//!
+//! Off EC2 the metadata addresses usually do not answer at all, and hyper 0.8 cannot bound the
+//! time spent connecting, so requests are written by hand over a TcpStream with connect and read
+//! timeouts.  Both services answer with a Content-Length, so chunked responses are not supported.
+
+use error::ECSError;
+
+use std::env;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+/// The address of the EC2 instance metadata service.
+pub const INSTANCE_METADATA_ADDRESS: &'static str = "169.254.169.254:80";
+/// The address of the ECS task metadata service which serves container credentials.
+pub const CONTAINER_METADATA_ADDRESS: &'static str = "169.254.170.2:80";
+/// The name of the environment variable which disables the instance metadata service when true.
+pub const AWS_EC2_METADATA_DISABLED: &'static str = "AWS_EC2_METADATA_DISABLED";
+/// The time allowed to connect to, and then read from, a metadata service.
+const DEFAULT_TIMEOUT_MS: u64 = 1000;
+/// The lifetime requested for instance metadata session tokens, in seconds.
+const TOKEN_TTL_SECONDS: &'static str = "21600";
+
+/// A response from a metadata service.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetadataResponse {
+    status_code: u16,
+    body: String,
+}
+
+/// A client for the EC2 instance metadata service, using session tokens (IMDSv2).
+#[derive(Clone, Debug)]
+pub struct InstanceMetadataClient {
+    address: String,
+    timeout: Duration,
+}
+
+impl MetadataResponse {
+    /// Gets the status code of the response.
+    pub fn get_status_code(&self) -> u16 {
+        self.status_code
+    }
+
+    /// Gets the body of the response.
+    pub fn get_body(&self) -> &String {
+        &self.body
+    }
+
+    /// Returns the body of a 2xx response, or an error naming the path otherwise.
+    pub fn into_body(self, path: &str) -> Result<String, ECSError> {
+        if self.status_code >= 200 && self.status_code < 300 {
+            Ok(self.body)
+        } else {
+            Err(ECSError::Io(io::Error::new(io::ErrorKind::Other,
+                                            format!("metadata request for {} failed with status {}",
+                                                    path, self.status_code))))
+        }
+    }
+}
+
+impl InstanceMetadataClient {
+    /// Creates a client of the instance metadata service at its link-local address.
+    pub fn new() -> Self {
+        InstanceMetadataClient::with_address(INSTANCE_METADATA_ADDRESS)
+    }
+
+    /// Creates a client of an instance metadata service at the given host:port address.
+    pub fn with_address(address: &str) -> Self {
+        InstanceMetadataClient {
+            address: String::from(address),
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MS),
+        }
+    }
+
+    /// Sets the time allowed to connect to, and then read from, the service.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Returns true unless AWS_EC2_METADATA_DISABLED is set to true.
+    pub fn is_enabled() -> bool {
+        env::var(AWS_EC2_METADATA_DISABLED).map(|disabled| disabled.to_lowercase() != "true").unwrap_or(true)
+    }
+
+    /// Gets the body of a metadata path, such as "/latest/meta-data/placement/region", after
+    /// fetching a session token.
+    pub fn get(&self, path: &str) -> Result<String, ECSError> {
+        if !InstanceMetadataClient::is_enabled() {
+            return Err(ECSError::Io(io::Error::new(io::ErrorKind::Other,
+                                                   format!("{} is true", AWS_EC2_METADATA_DISABLED))));
+        }
+        let token_path = "/latest/api/token";
+        let token = try!(try!(send(&self.address, "PUT", token_path,
+                                   &[("X-aws-ec2-metadata-token-ttl-seconds", TOKEN_TTL_SECONDS)],
+                                   self.timeout)).into_body(token_path));
+        let response = try!(send(&self.address, "GET", path,
+                                 &[("X-aws-ec2-metadata-token", token.trim())], self.timeout));
+        response.into_body(path)
+    }
+}
+
+/// Sends a request without a body to a metadata service and reads the whole response.  The
+/// address is host:port, and the headers are (name, value) pairs.
+pub fn send(address: &str,
+            method: &str,
+            path: &str,
+            headers: &[(&str, &str)],
+            timeout: Duration) -> Result<MetadataResponse, ECSError> {
+    let socket_address = try!(resolve(address));
+    let mut stream = try!(TcpStream::connect_timeout(&socket_address, timeout));
+    try!(stream.set_read_timeout(Some(timeout)));
+    try!(stream.set_write_timeout(Some(timeout)));
+
+    let mut request = format!("{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\nContent-Length: 0\r\n",
+                              method, path, address);
+    for &(name, value) in headers {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    request.push_str("\r\n");
+    try!(stream.write_all(request.as_bytes()));
+
+    let mut raw = String::new();
+    try!(stream.read_to_string(&mut raw));
+    parse_response(&raw)
+}
+
+/// Resolves a host:port address to its first socket address.
+fn resolve(address: &str) -> Result<SocketAddr, ECSError> {
+    match try!(address.to_socket_addrs()).next() {
+        Some(socket_address) => Ok(socket_address),
+        None => Err(ECSError::Io(io::Error::new(io::ErrorKind::NotFound,
+                                                format!("{} did not resolve to any address", address)))),
+    }
+}
+
+/// Parses the status code and body out of a raw HTTP/1.x response, honouring Content-Length.
+fn parse_response(raw: &str) -> Result<MetadataResponse, ECSError> {
+    let malformed = || ECSError::Io(io::Error::new(io::ErrorKind::InvalidData, "malformed metadata response"));
+    let header_end = try!(raw.find("\r\n\r\n").ok_or_else(&malformed));
+    let head = &raw[..header_end];
+    let mut body = &raw[header_end + 4..];
+    let status_code = try!(head.split_whitespace()
+                               .nth(1)
+                               .and_then(|status| status.parse().ok())
+                               .ok_or_else(&malformed));
+    for line in head.lines().skip(1) {
+        if let Some(index) = line.find(':') {
+            if line[..index].trim().to_lowercase() == "content-length" {
+                let length: usize = try!(line[index + 1..].trim().parse().map_err(|_| malformed()));
+                if length <= body.len() {
+                    body = &body[..length];
+                }
+            }
+        }
+    }
+    Ok(MetadataResponse {
+        status_code: status_code,
+        body: String::from(body),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_response, InstanceMetadataClient};
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[test]
+    fn test_parse_response() {
+        let response = parse_response("HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\n\
+                                       Content-Length: 9\r\n\r\nus-west-2trailing").unwrap();
+        assert_eq!(200, response.get_status_code());
+        assert_eq!("us-west-2", *response.get_body());
+        assert!(parse_response("garbage").is_err());
+    }
+
+    #[test]
+    fn test_get_with_session_token() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let server = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for reply in &["token-123", "eu-central-1"] {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buffer = [0; 1024];
+                let read = stream.read(&mut buffer).unwrap();
+                requests.push(String::from_utf8_lossy(&buffer[..read]).into_owned());
+                write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}", reply.len(), reply).unwrap();
+            }
+            requests
+        });
+
+        let client = InstanceMetadataClient::with_address(&address);
+        assert_eq!("eu-central-1", client.get("/latest/meta-data/placement/region").unwrap());
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("PUT /latest/api/token HTTP/1.1"));
+        assert!(requests[1].starts_with("GET /latest/meta-data/placement/region HTTP/1.1"));
+        assert!(requests[1].contains("X-aws-ec2-metadata-token: token-123"));
+    }
+}
@@ -0,0 +1,362 @@
+//! This module contains the MetricsSink trait, which the ECSClient reports every operation to,
+//! and InMemoryMetrics, a sink which aggregates per-action counters and latency histograms in
+//! process and renders them in the Prometheus text exposition format.
+//!
+//! ```
+//! # use ecs_client::ecs_client::ECSClient;
+//! # use ecs_client::metrics::InMemoryMetrics;
+//! # use ecs_client::region::Region;
+//! # use std::sync::Arc;
+//! let metrics = Arc::new(InMemoryMetrics::new());
+//! let mut client = ECSClient::for_region(Region::USWest2);
+//! client.set_metrics_sink(metrics.clone());
+//! // ... send requests, then serve this from a /metrics endpoint
+//! let exposition = metrics.render_prometheus();
+//! ```
+//!
+//! Other backends, such as an OpenTelemetry meter or statsd, can be plugged in by implementing
+//! MetricsSink.
+
+use action::ecs_action::ECSAction;
+use error::ECSError;
+use response::ResponseMetadata;
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// The upper bounds, in seconds, of the buckets of the latency histograms of InMemoryMetrics.
+pub const DEFAULT_LATENCY_BUCKETS: &'static [f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5,
+                                                       1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+/// What happened during one operation, i.e. one call of an ECSClient action method including
+/// its retries.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OperationRecord {
+    action: ECSAction,
+    duration: Duration,
+    attempts: u32,
+    throttles: u32,
+    error_type: Option<String>,
+}
+
+/// Receives a record of every operation an ECSClient completes, successful or not.
+pub trait MetricsSink : Debug + Send + Sync {
+    /// Records a completed operation.  Called on the thread which sent the request, so this
+    /// should not block.
+    fn record(&self, record: &OperationRecord);
+}
+
+/// A MetricsSink which keeps per-action counters and latency histograms in memory.  Share it
+/// between clients with an Arc to aggregate their operations.
+#[derive(Debug)]
+pub struct InMemoryMetrics {
+    buckets: Vec<f64>,
+    /// The metrics of each action, keyed by action name so that they render in a stable order.
+    actions: Mutex<BTreeMap<String, ActionMetrics>>,
+}
+
+/// The metrics of a single action.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActionMetrics {
+    requests: u64,
+    /// The number of failed operations, keyed by error type, e.g. "ClientException".
+    errors: BTreeMap<String, u64>,
+    retries: u64,
+    throttles: u64,
+    latency: Histogram,
+}
+
+/// A histogram of durations, in seconds, with fixed buckets.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    /// The upper bound of each bucket, in increasing order.
+    bounds: Vec<f64>,
+    /// The number of observations in each bucket, which are not cumulative, plus one for those
+    /// above the last bound.
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl OperationRecord {
+    /// Creates the record of an operation from its metadata, the number of its attempts which ECS
+    /// throttled, and the error it failed with, if any.
+    pub fn new(action: ECSAction, metadata: &ResponseMetadata, throttles: u32, error: Option<&ECSError>) -> Self {
+        OperationRecord {
+            action: action,
+            duration: metadata.get_elapsed(),
+            attempts: metadata.get_attempts(),
+            throttles: throttles,
+            error_type: error.map(|error| String::from(error.get_error_type())),
+        }
+    }
+
+    /// Gets the action of the operation.
+    pub fn get_action(&self) -> ECSAction {
+        self.action
+    }
+
+    /// Gets the time the operation took, including the delays between retries.
+    pub fn get_duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Gets the number of attempts made, which is 0 if the request could not be serialized.
+    pub fn get_attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Gets the number of attempts made after the first.
+    pub fn get_retries(&self) -> u32 {
+        self.attempts.saturating_sub(1)
+    }
+
+    /// Gets the number of attempts which ECS throttled.
+    pub fn get_throttles(&self) -> u32 {
+        self.throttles
+    }
+
+    /// Gets the type of the error the operation failed with, or None if it succeeded.
+    pub fn get_error_type(&self) -> Option<&String> {
+        self.error_type.as_ref()
+    }
+}
+
+impl InMemoryMetrics {
+    /// Creates an InMemoryMetrics with the DEFAULT_LATENCY_BUCKETS.
+    pub fn new() -> Self {
+        InMemoryMetrics::with_buckets(DEFAULT_LATENCY_BUCKETS.to_vec())
+    }
+
+    /// Creates an InMemoryMetrics whose latency histograms have buckets with the given upper
+    /// bounds, in seconds.  The bounds are sorted, and a bucket for larger values is implied.
+    pub fn with_buckets(mut buckets: Vec<f64>) -> Self {
+        buckets.sort_by(|a, b| a.partial_cmp(b).unwrap_or(::std::cmp::Ordering::Equal));
+        buckets.dedup();
+        InMemoryMetrics {
+            buckets: buckets,
+            actions: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Gets a copy of the metrics of the given action, or None if no operation was recorded for it.
+    pub fn get(&self, action: ECSAction) -> Option<ActionMetrics> {
+        self.actions.lock().unwrap().get(&action.to_string()).cloned()
+    }
+
+    /// Renders every metric in the Prometheus text exposition format, version 0.0.4.
+    pub fn render_prometheus(&self) -> String {
+        let actions = self.actions.lock().unwrap();
+        let mut out = String::new();
+
+        write_header(&mut out, "ecs_client_requests_total", "counter", "Operations sent to Amazon ECS.");
+        for (action, metrics) in actions.iter() {
+            out.push_str(&format!("ecs_client_requests_total{{action=\"{}\"}} {}\n", escape(action), metrics.requests));
+        }
+
+        write_header(&mut out, "ecs_client_errors_total", "counter", "Operations which failed, by error type.");
+        for (action, metrics) in actions.iter() {
+            for (error_type, count) in &metrics.errors {
+                out.push_str(&format!("ecs_client_errors_total{{action=\"{}\",error_type=\"{}\"}} {}\n",
+                                      escape(action), escape(error_type), count));
+            }
+        }
+
+        write_header(&mut out, "ecs_client_retries_total", "counter", "Attempts made after the first.");
+        for (action, metrics) in actions.iter() {
+            out.push_str(&format!("ecs_client_retries_total{{action=\"{}\"}} {}\n", escape(action), metrics.retries));
+        }
+
+        write_header(&mut out, "ecs_client_throttles_total", "counter", "Attempts throttled by Amazon ECS.");
+        for (action, metrics) in actions.iter() {
+            out.push_str(&format!("ecs_client_throttles_total{{action=\"{}\"}} {}\n", escape(action), metrics.throttles));
+        }
+
+        write_header(&mut out, "ecs_client_request_duration_seconds", "histogram",
+                     "Time taken by operations, including retries.");
+        for (action, metrics) in actions.iter() {
+            let histogram = &metrics.latency;
+            let mut cumulative = 0;
+            for (bound, count) in histogram.bounds.iter().zip(&histogram.counts) {
+                cumulative += *count;
+                out.push_str(&format!("ecs_client_request_duration_seconds_bucket{{action=\"{}\",le=\"{}\"}} {}\n",
+                                      escape(action), bound, cumulative));
+            }
+            out.push_str(&format!("ecs_client_request_duration_seconds_bucket{{action=\"{}\",le=\"+Inf\"}} {}\n",
+                                  escape(action), histogram.count));
+            out.push_str(&format!("ecs_client_request_duration_seconds_sum{{action=\"{}\"}} {}\n",
+                                  escape(action), histogram.sum));
+            out.push_str(&format!("ecs_client_request_duration_seconds_count{{action=\"{}\"}} {}\n",
+                                  escape(action), histogram.count));
+        }
+        out
+    }
+}
+
+impl Default for InMemoryMetrics {
+    fn default() -> Self {
+        InMemoryMetrics::new()
+    }
+}
+
+impl MetricsSink for InMemoryMetrics {
+    fn record(&self, record: &OperationRecord) {
+        let mut actions = self.actions.lock().unwrap();
+        let buckets = &self.buckets;
+        let metrics = actions.entry(record.action.to_string())
+                             .or_insert_with(|| ActionMetrics::new(buckets.clone()));
+        metrics.requests += 1;
+        if let Some(ref error_type) = record.error_type {
+            *metrics.errors.entry(error_type.clone()).or_insert(0) += 1;
+        }
+        metrics.retries += record.get_retries() as u64;
+        metrics.throttles += record.throttles as u64;
+        metrics.latency.observe(record.duration);
+    }
+}
+
+impl ActionMetrics {
+    /// Creates empty metrics whose latency histogram has the given bucket bounds.
+    fn new(buckets: Vec<f64>) -> Self {
+        ActionMetrics {
+            requests: 0,
+            errors: BTreeMap::new(),
+            retries: 0,
+            throttles: 0,
+            latency: Histogram::new(buckets),
+        }
+    }
+
+    /// Gets the number of operations.
+    pub fn get_requests(&self) -> u64 {
+        self.requests
+    }
+
+    /// Gets the number of failed operations, keyed by error type.
+    pub fn get_errors(&self) -> &BTreeMap<String, u64> {
+        &self.errors
+    }
+
+    /// Gets the number of attempts made after the first.
+    pub fn get_retries(&self) -> u64 {
+        self.retries
+    }
+
+    /// Gets the number of attempts throttled by ECS.
+    pub fn get_throttles(&self) -> u64 {
+        self.throttles
+    }
+
+    /// Gets the histogram of the time taken by operations.
+    pub fn get_latency(&self) -> &Histogram {
+        &self.latency
+    }
+}
+
+impl Histogram {
+    /// Creates an empty Histogram with buckets with the given upper bounds, in increasing order.
+    pub fn new(bounds: Vec<f64>) -> Self {
+        let buckets = bounds.len() + 1;
+        Histogram {
+            bounds: bounds,
+            counts: vec![0; buckets],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    /// Adds a duration to the histogram.
+    pub fn observe(&mut self, duration: Duration) {
+        let seconds = duration.as_secs() as f64 + duration.subsec_nanos() as f64 / 1e9;
+        let bucket = self.bounds.iter().position(|bound| seconds <= *bound).unwrap_or(self.bounds.len());
+        self.counts[bucket] += 1;
+        self.sum += seconds;
+        self.count += 1;
+    }
+
+    /// Gets the number of observations at or below the given bucket bound, or None if there is no
+    /// bucket with that bound.
+    pub fn get_cumulative_count(&self, bound: f64) -> Option<u64> {
+        self.bounds.iter()
+                   .position(|candidate| *candidate == bound)
+                   .map(|index| self.counts[..index + 1].iter().fold(0, |total, count| total + count))
+    }
+
+    /// Gets the sum of every observation, in seconds.
+    pub fn get_sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Gets the number of observations.
+    pub fn get_count(&self) -> u64 {
+        self.count
+    }
+}
+
+/// Appends the HELP and TYPE lines of a metric.
+fn write_header(out: &mut String, name: &str, metric_type: &str, help: &str) {
+    out.push_str(&format!("# HELP {} {}\n# TYPE {} {}\n", name, help, name, metric_type));
+}
+
+/// Escapes a label value for the Prometheus text format.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{InMemoryMetrics, MetricsSink, OperationRecord};
+    use action::ecs_action::ECSAction;
+    use error::{ECSError, ServiceError};
+    use response::ResponseMetadata;
+    use std::time::Duration;
+
+    fn record(action: ECSAction, millis: u64, attempts: u32, throttles: u32, error: Option<&ECSError>) -> OperationRecord {
+        let mut metadata = ResponseMetadata::new();
+        metadata.record_attempts(attempts, Duration::from_millis(millis));
+        OperationRecord::new(action, &metadata, throttles, error)
+    }
+
+    #[test]
+    fn test_record() {
+        let metrics = InMemoryMetrics::with_buckets(vec![1.0, 0.1]);
+        let throttled = ECSError::Service(ServiceError::from_response(
+            400, "{\"__type\":\"ThrottlingException\",\"message\":\"Rate exceeded\"}"));
+        metrics.record(&record(ECSAction::ListClusters, 50, 1, 0, None));
+        metrics.record(&record(ECSAction::ListClusters, 500, 3, 3, Some(&throttled)));
+        metrics.record(&record(ECSAction::ListClusters, 1500, 2, 1, None));
+
+        let list_clusters = metrics.get(ECSAction::ListClusters).unwrap();
+        assert_eq!(3, list_clusters.get_requests());
+        assert_eq!(Some(&1), list_clusters.get_errors().get("ThrottlingException"));
+        assert_eq!(3, list_clusters.get_retries());
+        assert_eq!(4, list_clusters.get_throttles());
+        assert_eq!(Some(1), list_clusters.get_latency().get_cumulative_count(0.1));
+        assert_eq!(Some(2), list_clusters.get_latency().get_cumulative_count(1.0));
+        assert_eq!(3, list_clusters.get_latency().get_count());
+        assert!(metrics.get(ECSAction::DescribeTasks).is_none());
+    }
+
+    #[test]
+    fn test_render_prometheus() {
+        let metrics = InMemoryMetrics::with_buckets(vec![0.1, 1.0]);
+        let missing = ECSError::Service(ServiceError::from_response(
+            400, "{\"__type\":\"ClusterNotFoundException\",\"message\":\"Cluster not found.\"}"));
+        metrics.record(&record(ECSAction::DescribeTasks, 250, 2, 0, Some(&missing)));
+        metrics.record(&record(ECSAction::ListClusters, 50, 1, 0, None));
+
+        let text = metrics.render_prometheus();
+        assert!(text.contains("# TYPE ecs_client_requests_total counter\n\
+                               ecs_client_requests_total{action=\"DescribeTasks\"} 1\n\
+                               ecs_client_requests_total{action=\"ListClusters\"} 1\n"));
+        assert!(text.contains("ecs_client_errors_total{action=\"DescribeTasks\",error_type=\"ClusterNotFoundException\"} 1\n"));
+        assert!(text.contains("ecs_client_retries_total{action=\"DescribeTasks\"} 1\n"));
+        assert!(text.contains("ecs_client_request_duration_seconds_bucket{action=\"DescribeTasks\",le=\"0.1\"} 0\n\
+                               ecs_client_request_duration_seconds_bucket{action=\"DescribeTasks\",le=\"1\"} 1\n\
+                               ecs_client_request_duration_seconds_bucket{action=\"DescribeTasks\",le=\"+Inf\"} 1\n\
+                               ecs_client_request_duration_seconds_sum{action=\"DescribeTasks\"} 0.25\n\
+                               ecs_client_request_duration_seconds_count{action=\"DescribeTasks\"} 1\n"));
+    }
+}
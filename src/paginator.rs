@@ -0,0 +1,176 @@
+//! This module contains iterators which follow the nextToken of paginated ECS responses, issuing
+//! follow-up requests lazily so that callers do not have to loop over pages by hand.
+
+use action::ecs_action::{ECSAction, ECSRequest, ECSResponse};
+use ecs_client::{self, ECSClient};
+use std::vec;
+
+/// The default cap on the total number of items yielded by an Items iterator.  This keeps a
+/// misbehaving or unexpectedly large listing from issuing requests forever.
+pub const DEFAULT_MAX_ITEMS: usize = 10000;
+
+/// An ECSRequest for an action whose output is split into pages linked by a nextToken.
+pub trait PaginatedRequest : ECSRequest + Clone {
+    /// The type of response returned for each page.
+    type Response: PaginatedResponse;
+
+    /// Gets the action each page is requested with.
+    fn action(&self) -> ECSAction;
+
+    /// Sets the nextToken returned by the previous page.
+    fn set_page_token(&mut self, next_token: String);
+
+    /// Sends the request for a single page using the given client.
+    fn send_page(self, client: &ECSClient) -> ecs_client::Result<Self::Response>;
+}
+
+/// An ECSResponse holding a single page of paginated output.
+pub trait PaginatedResponse : ECSResponse {
+    /// The type of item listed in each page.
+    type Item;
+
+    /// Gets the nextToken to use for the next page, or None if this is the last page.
+    fn page_token(&self) -> Option<String>;
+
+    /// Consumes the page, returning the items it holds.
+    fn into_items(self) -> Vec<Self::Item>;
+}
+
+/// An iterator over the pages of a paginated action.  The first request is sent on the first
+/// call to next(), and each following request only once the previous page has been consumed.
+/// Iteration ends after the first page which fails to be fetched.
+pub struct Pages<'a, R: PaginatedRequest> {
+    client: &'a ECSClient,
+    /// The request for the next page, or None once the last page has been returned.
+    request: Option<R>,
+}
+
+/// An iterator over the items of every page of a paginated action, stopping once the pages run
+/// out or max_items items have been yielded, whichever comes first.  A page which fails to be
+/// fetched is yielded as a single Err item.
+pub struct Items<P, T> where P: Iterator<Item=ecs_client::Result<T>>, T: PaginatedResponse {
+    pages: P,
+    /// The items of the current page which have not been yielded yet.
+    buffer: vec::IntoIter<T::Item>,
+    /// The number of items which may still be yielded before the cap is reached.
+    remaining: usize,
+}
+
+impl<'a, R: PaginatedRequest> Pages<'a, R> {
+    /// Creates a Pages iterator which starts from the given request.  If the request already
+    /// holds a nextToken, iteration resumes from that page.
+    pub fn new(client: &'a ECSClient, request: R) -> Self {
+        Pages {
+            client: client,
+            request: Some(request),
+        }
+    }
+
+    /// Flattens the pages into an iterator over their items, capped at DEFAULT_MAX_ITEMS.
+    pub fn items(self) -> Items<Self, R::Response> {
+        Items::new(self)
+    }
+}
+
+impl<'a, R: PaginatedRequest> Iterator for Pages<'a, R> {
+    type Item = ecs_client::Result<R::Response>;
+
+    fn next(&mut self) -> Option<ecs_client::Result<R::Response>> {
+        let request = match self.request.take() {
+            Some(request) => request,
+            None => return None,
+        };
+        let mut next_request = request.clone();
+        let response = request.send_page(self.client);
+        if let Ok(ref response) = response {
+            if let Some(token) = response.page_token() {
+                next_request.set_page_token(token);
+                self.request = Some(next_request);
+            }
+        }
+        Some(response)
+    }
+}
+
+impl<P, T> Items<P, T> where P: Iterator<Item=ecs_client::Result<T>>, T: PaginatedResponse {
+    /// Creates an Items iterator over the given pages, capped at DEFAULT_MAX_ITEMS.
+    pub fn new(pages: P) -> Self {
+        Items {
+            pages: pages,
+            buffer: Vec::new().into_iter(),
+            remaining: DEFAULT_MAX_ITEMS,
+        }
+    }
+
+    /// Sets the max number of items to yield.  No further pages are requested once the cap has
+    /// been reached.
+    pub fn set_max_items(&mut self, max_items: usize) {
+        self.remaining = max_items;
+    }
+}
+
+impl<P, T> Iterator for Items<P, T> where P: Iterator<Item=ecs_client::Result<T>>, T: PaginatedResponse {
+    type Item = ecs_client::Result<T::Item>;
+
+    fn next(&mut self) -> Option<ecs_client::Result<T::Item>> {
+        if self.remaining == 0 {
+            return None;
+        }
+        loop {
+            if let Some(item) = self.buffer.next() {
+                self.remaining -= 1;
+                return Some(Ok(item));
+            }
+            match self.pages.next() {
+                Some(Ok(page)) => self.buffer = page.into_items().into_iter(),
+                Some(Err(error)) => {
+                    self.remaining = 0;
+                    return Some(Err(error));
+                },
+                None => return None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Items;
+    use action::list_clusters::ListClustersResponse;
+    use ecs_client;
+    use error::{ECSError, ServiceError};
+    use serde_json;
+    use std::cell::Cell;
+
+    fn pages() -> Vec<ecs_client::Result<ListClustersResponse>> {
+        vec![Ok(serde_json::from_str("{\"clusterArns\":[\"a\",\"b\"],\"nextToken\":\"1\"}").unwrap()),
+             Ok(serde_json::from_str("{\"clusterArns\":[],\"nextToken\":\"2\"}").unwrap()),
+             Ok(serde_json::from_str("{\"clusterArns\":[\"c\"]}").unwrap())]
+    }
+
+    #[test]
+    fn test_items_span_pages() {
+        let items: Vec<String> = Items::new(pages().into_iter()).collect::<ecs_client::Result<_>>().unwrap();
+        assert_eq!(vec!["a", "b", "c"], items);
+    }
+
+    #[test]
+    fn test_max_items() {
+        let pages_fetched = Cell::new(0);
+        let mut items = Items::new(pages().into_iter().inspect(|_| pages_fetched.set(pages_fetched.get() + 1)));
+        items.set_max_items(2);
+        assert_eq!(vec!["a", "b"], items.collect::<ecs_client::Result<Vec<String>>>().unwrap());
+        assert_eq!(1, pages_fetched.get());
+    }
+
+    #[test]
+    fn test_error_ends_items() {
+        let mut pages = pages();
+        pages.insert(1, Err(ECSError::Service(ServiceError::from_response(500, "{}"))));
+        let mut items = Items::new(pages.into_iter());
+        assert_eq!("a", items.next().unwrap().unwrap());
+        assert_eq!("b", items.next().unwrap().unwrap());
+        assert!(items.next().unwrap().is_err());
+        assert!(items.next().is_none());
+    }
+}
@@ -0,0 +1,294 @@
+//! This module contains the PooledECSClient, whose actions return futures of their responses
+//! instead of blocking the calling thread.
+//!
+//! It is not an asynchronous HTTP client.  hyper 0.8 only provides a blocking client, so each
+//! attempt at a request is sent by one of a fixed pool of worker threads, which is held until
+//! the response has been read.  Everything else happens off the pool: requests are serialized on
+//! the calling thread, a token is taken from the client's RateLimiter with
+//! RateLimiter::acquire_async() before an attempt is handed to a worker, and the delay before a
+//! retry is waited out on the shared timer.  The size of the pool therefore caps the number of
+//! requests on the wire, while any number of further operations may be waiting for a token or a
+//! retry without holding a thread.
+//!
+//! The futures do not depend on a particular executor, and can simply be wait()ed on.  Sending
+//! requests without a thread each needs a non-blocking HTTP stack, i.e. a hyper upgrade and a
+//! TLS library which can be linked alongside the openssl 0.7 used by hyper 0.8, and is left to a
+//! separate change.
+
+use action::*;
+use ecs_client::{self, ECSClient, Operation};
+use error::{ECSError, OperationError};
+use paginator::{PaginatedRequest, PaginatedResponse, DEFAULT_MAX_ITEMS};
+use rate_limit::RateLimiter;
+use region::Region;
+use response::Response;
+use timer;
+
+use futures::{Future, Poll, Async, Stream};
+use futures::future::{self, Loop};
+use futures::stream;
+use futures_cpupool::CpuPool;
+use std::result;
+use std::sync::Arc;
+
+/// The default max number of requests a PooledECSClient has on the wire at once.
+pub const DEFAULT_MAX_CONCURRENCY: usize = 16;
+
+/// A future of the response to a request sent by a PooledECSClient.
+pub type ResponseFuture<T> = Box<Future<Item=T, Error=ECSError> + Send>;
+
+/// A future of the response to a request sent by a PooledECSClient, along with the metadata of
+/// the operation.  See PooledECSClient::send_with_metadata().
+pub type MetadataFuture<T> = Box<Future<Item=Response<T>, Error=OperationError> + Send>;
+
+/// A future of the outcome of a single attempt at an operation: either the end of the operation,
+/// or the operation to attempt again.
+type AttemptFuture<T> = Box<Future<Item=Loop<Response<T>, Operation>, Error=OperationError> + Send>;
+
+/// An ECS client whose actions return futures instead of blocking.  Cloning a PooledECSClient is
+/// cheap, and clones share the same connections, worker pool and rate limiter.
+#[derive(Clone)]
+pub struct PooledECSClient {
+    client: Arc<ECSClient>,
+    pool: CpuPool,
+}
+
+/// A stream of the pages of a paginated action.  Each request is sent once the previous page has
+/// been received, and the stream ends after the last page or the first error.
+pub struct PageStream<R: PaginatedRequest> {
+    client: PooledECSClient,
+    /// The request for the next page, or None once the last page has been requested.
+    request: Option<R>,
+    /// The page currently being fetched, along with a copy of its request to carry the nextToken
+    /// of the page to the request for the following one.
+    in_flight: Option<(ResponseFuture<R::Response>, R)>,
+}
+
+impl PooledECSClient {
+    /// Creates a new PooledECSClient for the specified Region, with DEFAULT_MAX_CONCURRENCY
+    /// worker threads.
+    pub fn for_region(region: Region) -> PooledECSClient {
+        PooledECSClient::new(ECSClient::for_region(region), DEFAULT_MAX_CONCURRENCY)
+    }
+
+    /// Creates a PooledECSClient which sends requests with the given ECSClient, keeping its
+    /// retry policy, rate limiter and operation timeout, and has at most max_concurrency
+    /// requests on the wire at once.
+    pub fn new(client: ECSClient, max_concurrency: usize) -> PooledECSClient {
+        PooledECSClient {
+            client: Arc::new(client),
+            pool: CpuPool::new(max_concurrency),
+        }
+    }
+
+    /// Runs the given call against the underlying ECSClient on the worker pool, returning a
+    /// future of its result.  The call holds a worker until it returns, including any rate limit
+    /// waits and retries, so prefer the per-action methods where one exists.
+    pub fn call<F, T>(&self, call: F) -> ResponseFuture<T>
+        where F: FnOnce(&ECSClient) -> ecs_client::Result<T> + Send + 'static,
+              T: Send + 'static
+    {
+        let client = self.client.clone();
+        Box::new(self.pool.spawn_fn(move || call(&client)))
+    }
+
+    /// Sends the request for the given action, resolving to its response along with the
+    /// ResponseMetadata of the operation, or to an OperationError carrying the metadata of the
+    /// failed attempts.  Attempts are retried as the ECSClient's RetryPolicy and operation
+    /// timeout allow.
+    pub fn send_with_metadata<T, U>(&self, action: ecs_action::ECSAction, request: T) -> MetadataFuture<U>
+        where T: ecs_action::ECSRequest,
+              U: ecs_action::ECSResponse + Send + 'static
+    {
+        let operation = match self.client.start_operation(action, &request) {
+            Ok(operation) => operation,
+            Err(error) => return Box::new(future::err::<Response<U>, OperationError>(error)),
+        };
+        let client = self.client.clone();
+        let pool = self.pool.clone();
+        Box::new(future::loop_fn(operation, move |operation| {
+            attempt::<U>(client.clone(), pool.clone(), operation)
+        }))
+    }
+
+    /// Sends the request for the given action, resolving to its response.
+    pub fn send<T, U>(&self, action: ecs_action::ECSAction, request: T) -> ResponseFuture<U>
+        where T: ecs_action::ECSRequest,
+              U: ecs_action::ECSResponse + Send + 'static
+    {
+        Box::new(self.send_with_metadata(action, request)
+                     .map(Response::into_output)
+                     .map_err(OperationError::into_error))
+    }
+
+    /// Returns a stream of every page of output of a paginated action, starting from the given
+    /// request.
+    pub fn pages<R>(&self, request: R) -> PageStream<R>
+        where R: PaginatedRequest,
+              R::Response: Send + 'static
+    {
+        PageStream {
+            client: self.clone(),
+            request: Some(request),
+            in_flight: None,
+        }
+    }
+}
+
+/// Defines the per-action methods of PooledECSClient from the table of actions; see
+/// ecs_actions!.  Paginated actions are read with pages() instead of per-action methods.
+macro_rules! pooled_client_actions {
+    (actions {
+        $($(#[$doc:meta])*
+          $action:ident: $name:ident, $with_metadata:ident, $request:ty, $response:ty;)*
+    }
+    paginated { $($paginated:tt)* }) => {
+        impl PooledECSClient {
+            $(
+                $(#[$doc])*
+                ///
+                /// Returns a future of the response instead of blocking.
+                pub fn $name(&self, request: $request) -> ResponseFuture<$response> {
+                    self.send(ecs_action::ECSAction::$action, request)
+                }
+
+                /// Like the method of the same name without the _with_metadata suffix, but
+                /// resolves to the response along with the ResponseMetadata of the operation, or
+                /// fails with the OperationError carrying it.
+                pub fn $with_metadata(&self, request: $request) -> MetadataFuture<$response> {
+                    self.send_with_metadata(ecs_action::ECSAction::$action, request)
+                }
+            )*
+        }
+    }
+}
+
+ecs_actions!(pooled_client_actions);
+
+/// Makes the next attempt at an operation.  A token is taken from the client's RateLimiter
+/// without holding a worker, the request is sent on a worker, and then the operation either ends
+/// or waits out its retry delay on the shared timer.  The operation ends at once if no token will
+/// be available before its timeout runs out.
+fn attempt<U>(client: Arc<ECSClient>, pool: CpuPool, operation: Operation) -> AttemptFuture<U>
+    where U: ecs_action::ECSResponse + Send + 'static
+{
+    let acquired: Box<Future<Item=(), Error=()> + Send> = match client.get_rate_limiter() {
+        Some(rate_limiter) => {
+            let rate_limiter = rate_limiter.clone();
+            match client.remaining_time(&operation) {
+                Some(remaining) => {
+                    Box::new(RateLimiter::acquire_async_within(rate_limiter, operation.get_action(), remaining))
+                },
+                None => Box::new(RateLimiter::acquire_async(rate_limiter, operation.get_action())),
+            }
+        },
+        None => Box::new(future::ok::<(), ()>(())),
+    };
+    Box::new(acquired.then(move |acquired| -> AttemptFuture<U> {
+        if acquired.is_err() {
+            let error = client.operation_timed_out(&operation);
+            return finish(&client, operation, Err(error));
+        }
+        let worker = client.clone();
+        let sent = pool.spawn_fn(move || {
+            let mut operation = operation;
+            let result = worker.send_attempt::<U>(&mut operation);
+            Ok::<_, OperationError>((operation, result))
+        });
+        Box::new(sent.and_then(move |(mut operation, result)| -> AttemptFuture<U> {
+            let error = match result {
+                Ok(output) => return finish(&client, operation, Ok(output)),
+                Err(error) => error,
+            };
+            let delay = match client.retry_delay(&mut operation, &error) {
+                Some(delay) => delay,
+                None => return finish(&client, operation, Err(error)),
+            };
+            Box::new(timer::sleep(delay).then(move |_| {
+                let next: Loop<Response<U>, Operation> = Loop::Continue(operation);
+                Ok::<_, OperationError>(next)
+            }))
+        }))
+    }))
+}
+
+/// Ends an operation with the result of its last attempt.
+fn finish<U>(client: &ECSClient, operation: Operation, result: ecs_client::Result<U>) -> AttemptFuture<U>
+    where U: Send + 'static
+{
+    let finished: result::Result<Loop<Response<U>, Operation>, OperationError> =
+        client.finish_operation(operation, result).map(Loop::Break);
+    Box::new(future::result(finished))
+}
+
+impl<R> PageStream<R>
+    where R: PaginatedRequest + 'static,
+          R::Response: Send + 'static,
+          <R::Response as PaginatedResponse>::Item: 'static
+{
+    /// Flattens the pages into a stream of their items, capped at DEFAULT_MAX_ITEMS.
+    pub fn items(self) -> Box<Stream<Item=<R::Response as PaginatedResponse>::Item, Error=ECSError>> {
+        self.items_capped(DEFAULT_MAX_ITEMS)
+    }
+
+    /// Flattens the pages into a stream of their items, ending once max_items items have been
+    /// yielded.  No further pages are requested once the cap has been reached.
+    pub fn items_capped(self, max_items: usize)
+                        -> Box<Stream<Item=<R::Response as PaginatedResponse>::Item, Error=ECSError>> {
+        Box::new(self.map(|page| stream::iter_ok(page.into_items()))
+                     .flatten()
+                     .take(max_items as u64))
+    }
+}
+
+impl<R> Stream for PageStream<R>
+    where R: PaginatedRequest,
+          R::Response: Send + 'static
+{
+    type Item = R::Response;
+    type Error = ECSError;
+
+    fn poll(&mut self) -> Poll<Option<R::Response>, ECSError> {
+        if self.in_flight.is_none() {
+            let request = match self.request.take() {
+                Some(request) => request,
+                None => return Ok(Async::Ready(None)),
+            };
+            let following = request.clone();
+            let page = self.client.send(request.action(), request);
+            self.in_flight = Some((page, following));
+        }
+        let polled = self.in_flight.as_mut().unwrap().0.poll();
+        let page = match polled {
+            Ok(Async::Ready(page)) => page,
+            Ok(Async::NotReady) => return Ok(Async::NotReady),
+            Err(error) => {
+                self.in_flight = None;
+                return Err(error);
+            },
+        };
+        let (_, mut following) = self.in_flight.take().unwrap();
+        if let Some(token) = page.page_token() {
+            following.set_page_token(token);
+            self.request = Some(following);
+        }
+        Ok(Async::Ready(Some(page)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PooledECSClient;
+    use ecs_client::ECSClient;
+    use region::Region;
+    use futures::Future;
+    use futures::future;
+
+    #[test]
+    fn test_calls_run_concurrently() {
+        let client = PooledECSClient::new(ECSClient::for_region(Region::USWest2), 4);
+        let calls: Vec<_> = (0..8).map(|i| client.call(move |_| Ok(i * 2))).collect();
+        let results = future::join_all(calls).wait().unwrap();
+        assert_eq!(vec![0, 2, 4, 6, 8, 10, 12, 14], results);
+    }
+}
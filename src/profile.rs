@@ -0,0 +1,152 @@
+//! This module reads the named profiles of the shared AWS config and credentials files, by default
+//! ~/.aws/config and ~/.aws/credentials, as written by `aws configure`.
+
+use std::collections::HashMap;
+use std::env;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+/// The name of the environment variable which selects the active profile.
+pub const AWS_PROFILE: &'static str = "AWS_PROFILE";
+/// The name of the environment variable which overrides the path of the config file.
+pub const AWS_CONFIG_FILE: &'static str = "AWS_CONFIG_FILE";
+/// The name of the environment variable which overrides the path of the credentials file.
+pub const AWS_SHARED_CREDENTIALS_FILE: &'static str = "AWS_SHARED_CREDENTIALS_FILE";
+/// The profile used when AWS_PROFILE is not set.
+pub const DEFAULT_PROFILE: &'static str = "default";
+
+/// The settings of every profile in a shared config or credentials file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProfileFile {
+    profiles: HashMap<String, HashMap<String, String>>,
+}
+
+impl ProfileFile {
+    /// Parses the contents of a config file, in which every profile but the default one is
+    /// declared as "[profile name]".
+    pub fn parse_config(contents: &str) -> ProfileFile {
+        ProfileFile::parse(contents, true)
+    }
+
+    /// Parses the contents of a credentials file, in which profiles are declared as "[name]".
+    pub fn parse_credentials(contents: &str) -> ProfileFile {
+        ProfileFile::parse(contents, false)
+    }
+
+    /// Loads the config file named by AWS_CONFIG_FILE, or ~/.aws/config.  A missing file has no
+    /// profiles.
+    pub fn load_config() -> io::Result<ProfileFile> {
+        let contents = try!(read_if_exists(file_path(AWS_CONFIG_FILE, "config")));
+        Ok(ProfileFile::parse_config(&contents))
+    }
+
+    /// Loads the credentials file named by AWS_SHARED_CREDENTIALS_FILE, or ~/.aws/credentials.  A
+    /// missing file has no profiles.
+    pub fn load_credentials() -> io::Result<ProfileFile> {
+        let contents = try!(read_if_exists(file_path(AWS_SHARED_CREDENTIALS_FILE, "credentials")));
+        Ok(ProfileFile::parse_credentials(&contents))
+    }
+
+    /// Gets the value of a setting of the given profile.
+    pub fn get(&self, profile: &str, key: &str) -> Option<&String> {
+        self.profiles.get(profile).and_then(|settings| settings.get(key))
+    }
+
+    /// Parses "key = value" lines grouped under "[profile]" headers, skipping blank lines, comments
+    /// starting with '#' or ';', and lines outside of any profile.
+    fn parse(contents: &str, is_config: bool) -> ProfileFile {
+        let mut profiles: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let mut current: Option<String> = None;
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
+                let name = line[1..line.len() - 1].trim();
+                current = if !is_config || name == DEFAULT_PROFILE {
+                    Some(String::from(name))
+                } else if name.starts_with("profile ") {
+                    Some(String::from(name["profile ".len()..].trim()))
+                } else {
+                    None
+                };
+                continue;
+            }
+            if let (&Some(ref profile), Some(index)) = (&current, line.find('=')) {
+                let key = line[..index].trim();
+                let value = line[index + 1..].trim();
+                profiles.entry(profile.clone())
+                        .or_insert_with(HashMap::new)
+                        .insert(String::from(key), String::from(value));
+            }
+        }
+        ProfileFile {
+            profiles: profiles,
+        }
+    }
+}
+
+/// Gets the name of the active profile: AWS_PROFILE, or "default".
+pub fn active_profile() -> String {
+    match env::var(AWS_PROFILE) {
+        Ok(ref profile) if !profile.is_empty() => profile.clone(),
+        _ => String::from(DEFAULT_PROFILE),
+    }
+}
+
+/// Gets the path named by the given environment variable, or ~/.aws/<file_name>.
+fn file_path(variable: &str, file_name: &str) -> Option<PathBuf> {
+    match env::var(variable) {
+        Ok(ref path) if !path.is_empty() => Some(PathBuf::from(path)),
+        _ => env::home_dir().map(|home| home.join(".aws").join(file_name)),
+    }
+}
+
+/// Reads the file at the given path, treating a missing file or home directory as empty.
+fn read_if_exists(path: Option<PathBuf>) -> io::Result<String> {
+    let mut contents = String::new();
+    if let Some(path) = path {
+        match File::open(&path) {
+            Ok(mut file) => {
+                try!(file.read_to_string(&mut contents));
+            },
+            Err(ref error) if error.kind() == io::ErrorKind::NotFound => (),
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(contents)
+}
+
+#[cfg(test)]
+mod test {
+    use super::ProfileFile;
+
+    #[test]
+    fn test_parse_config() {
+        let config = ProfileFile::parse_config("# managed by bootstrap\n\
+                                                [default]\n\
+                                                region = us-west-2\n\
+                                                \n\
+                                                [profile ci]\n\
+                                                region=eu-west-1\n\
+                                                ; output = json\n\
+                                                [sso-session corp]\n\
+                                                region = us-east-1\n");
+        assert_eq!(Some(&String::from("us-west-2")), config.get("default", "region"));
+        assert_eq!(Some(&String::from("eu-west-1")), config.get("ci", "region"));
+        assert_eq!(None, config.get("ci", "output"));
+        assert_eq!(None, config.get("corp", "region"));
+    }
+
+    #[test]
+    fn test_parse_credentials() {
+        let credentials = ProfileFile::parse_credentials("[ci]\n\
+                                                          aws_access_key_id = AKIDEXAMPLE\n\
+                                                          aws_secret_access_key = secret=with=equals\n");
+        assert_eq!(Some(&String::from("AKIDEXAMPLE")), credentials.get("ci", "aws_access_key_id"));
+        assert_eq!(Some(&String::from("secret=with=equals")), credentials.get("ci", "aws_secret_access_key"));
+        assert_eq!(None, credentials.get("default", "aws_access_key_id"));
+    }
+}
@@ -0,0 +1,288 @@
+//! This module contains a client-side RateLimiter which spaces out requests to Amazon ECS with a
+//! token bucket per ECSAction, so that bursts of requests are smoothed out before ECS throttles
+//! them.  ECS itself throttles each account with token buckets per action, and the defaults here
+//! roughly follow the quotas documented in "Request throttling for the Amazon ECS API".
+
+use action::ecs_action::ECSAction;
+use error::ValidationError;
+use timer;
+use futures::{Future, Poll, Async};
+use futures::task;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use std::u32;
+
+/// A bucket holding up to capacity tokens which refills at a steady rate.  Each request takes
+/// one token.
+#[derive(Clone, Debug)]
+pub struct TokenBucket {
+    /// The max number of tokens the bucket holds, i.e. the largest burst of requests allowed.
+    capacity: f64,
+    /// The number of tokens added to the bucket every second.
+    refill_per_second: f64,
+    /// The number of tokens currently in the bucket.
+    tokens: f64,
+    /// The last time tokens were added to the bucket.
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a full TokenBucket with the given capacity and refill rate.
+    pub fn new(capacity: u32, refill_per_second: f64) -> TokenBucket {
+        TokenBucket {
+            capacity: capacity as f64,
+            refill_per_second: refill_per_second,
+            tokens: capacity as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Takes a token from the bucket if one is available at the given time.  Otherwise returns
+    /// how long to wait until one will be.
+    pub fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+        if now > self.last_refill {
+            let elapsed = now.duration_since(self.last_refill);
+            let elapsed = elapsed.as_secs() as f64 + elapsed.subsec_nanos() as f64 / 1e9;
+            self.tokens = (self.tokens + elapsed * self.refill_per_second).min(self.capacity);
+            self.last_refill = now;
+        }
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+        let wait = ((1.0 - self.tokens) / self.refill_per_second).min(u32::MAX as f64);
+        Err(Duration::new(wait as u64, (wait.fract() * 1e9) as u32))
+    }
+}
+
+/// Limits the rate of requests per ECSAction.  Share one RateLimiter between clients with an
+/// Arc to limit their combined traffic.
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// The bucket of each action which has been acquired at least once.
+    buckets: Mutex<HashMap<ECSAction, TokenBucket>>,
+    /// The capacity and refill rate of actions whose limits differ from the defaults.
+    limits: HashMap<ECSAction, (u32, f64)>,
+}
+
+impl RateLimiter {
+    /// Creates a RateLimiter using the default limits for every action.
+    pub fn new() -> RateLimiter {
+        RateLimiter {
+            buckets: Mutex::new(HashMap::new()),
+            limits: HashMap::new(),
+        }
+    }
+
+    /// Sets the capacity and refill rate, in tokens per second, of the given action's bucket.
+    /// The bucket starts out full.  Returns a ValidationError if the capacity is zero or the
+    /// refill rate is not a positive number, since no request could ever be sent.
+    pub fn set_limit(&mut self, action: ECSAction, capacity: u32, refill_per_second: f64)
+                     -> Result<(), ValidationError> {
+        if capacity == 0 {
+            return Err(ValidationError::new("capacity", String::from("must be at least 1")));
+        }
+        if !(refill_per_second > 0.0 && refill_per_second.is_finite()) {
+            return Err(ValidationError::new("refill_per_second",
+                                            format!("must be a positive number, got {}", refill_per_second)));
+        }
+        self.limits.insert(action, (capacity, refill_per_second));
+        self.buckets.lock().unwrap().remove(&action);
+        Ok(())
+    }
+
+    /// Takes a token for the given action if one is available.  Otherwise returns how long to
+    /// wait before trying again, without blocking, so that callers which cannot block a thread
+    /// can schedule the retry themselves.
+    pub fn try_acquire(&self, action: ECSAction) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap();
+        let (capacity, refill_per_second) = self.limit(action);
+        let acquired = buckets.entry(action)
+                              .or_insert_with(|| TokenBucket::new(capacity, refill_per_second))
+                              .try_take(Instant::now());
+        acquired
+    }
+
+    /// Blocks the current thread until a token for the given action is available, then takes it.
+    pub fn acquire(&self, action: ECSAction) {
+        while let Err(wait) = self.try_acquire(action) {
+            thread::sleep(wait);
+        }
+    }
+
+    /// Blocks the current thread until a token for the given action is available, then takes it
+    /// and returns true, unless no token will be available within the timeout, in which case
+    /// returns false without waiting any further.
+    pub fn acquire_within(&self, action: ECSAction, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        loop {
+            match self.try_acquire(action) {
+                Ok(()) => return true,
+                Err(wait) if Instant::now() + wait > deadline => return false,
+                Err(wait) => thread::sleep(wait),
+            }
+        }
+    }
+
+    /// Returns a future which completes once a token for the given action has been taken,
+    /// without blocking the task polling it.
+    pub fn acquire_async(limiter: Arc<RateLimiter>, action: ECSAction) -> Acquire {
+        Acquire {
+            limiter: limiter,
+            action: action,
+            deadline: None,
+        }
+    }
+
+    /// Returns a future which completes once a token for the given action has been taken, or
+    /// fails as soon as no token will be available within the timeout, without blocking the task
+    /// polling it.
+    pub fn acquire_async_within(limiter: Arc<RateLimiter>, action: ECSAction, timeout: Duration) -> Acquire {
+        Acquire {
+            limiter: limiter,
+            action: action,
+            deadline: Some(Instant::now() + timeout),
+        }
+    }
+
+    /// Gets the capacity and refill rate of the given action's bucket.
+    pub fn limit(&self, action: ECSAction) -> (u32, f64) {
+        match self.limits.get(&action) {
+            Some(&limit) => limit,
+            None => default_limit(action),
+        }
+    }
+}
+
+/// A future which completes once a token has been taken from a RateLimiter, or fails if it has a
+/// deadline and no token will be available by then.  While no token is available, the shared
+/// timer thread wakes the polling task once one is expected to be, so the future works with any
+/// executor.
+pub struct Acquire {
+    limiter: Arc<RateLimiter>,
+    action: ECSAction,
+    deadline: Option<Instant>,
+}
+
+impl Future for Acquire {
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<(), ()> {
+        match self.limiter.try_acquire(self.action) {
+            Ok(()) => Ok(Async::Ready(())),
+            Err(wait) => {
+                let ready = Instant::now() + wait;
+                if self.deadline.map_or(false, |deadline| ready > deadline) {
+                    return Err(());
+                }
+                timer::wake_at(ready, task::current());
+                Ok(Async::NotReady)
+            },
+        }
+    }
+}
+
+/// Returns the default capacity and refill rate of the given action's bucket.  Task and service
+/// reads get the largest bursts, other reads a moderate one, and mutating actions are held to a
+/// slower steady rate.
+pub fn default_limit(action: ECSAction) -> (u32, f64) {
+    match action {
+        ECSAction::DescribeTasks | ECSAction::DescribeServices |
+        ECSAction::DescribeContainerInstances => (100, 20.0),
+        _ => {
+            let name = action.to_string();
+            if name.starts_with("Describe") || name.starts_with("List") {
+                (50, 20.0)
+            } else {
+                (20, 5.0)
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{TokenBucket, RateLimiter, default_limit};
+    use action::ecs_action::ECSAction;
+    use futures::Future;
+    use std::f64;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_bucket_allows_burst_then_waits() {
+        let mut bucket = TokenBucket::new(2, 4.0);
+        let now = Instant::now();
+        assert!(bucket.try_take(now).is_ok());
+        assert!(bucket.try_take(now).is_ok());
+        let wait = bucket.try_take(now).unwrap_err();
+        assert!(wait > Duration::from_millis(240) && wait <= Duration::from_millis(250));
+        assert!(bucket.try_take(now + Duration::from_millis(250)).is_ok());
+    }
+
+    #[test]
+    fn test_bucket_refills_up_to_capacity() {
+        let mut bucket = TokenBucket::new(1, 10.0);
+        let now = Instant::now();
+        assert!(bucket.try_take(now).is_ok());
+        let later = now + Duration::from_secs(10);
+        assert!(bucket.try_take(later).is_ok());
+        assert!(bucket.try_take(later).is_err());
+    }
+
+    #[test]
+    fn test_limits_per_action() {
+        let mut limiter = RateLimiter::new();
+        limiter.set_limit(ECSAction::ListClusters, 1, 0.001).unwrap();
+        assert!(limiter.try_acquire(ECSAction::ListClusters).is_ok());
+        assert!(limiter.try_acquire(ECSAction::ListClusters).is_err());
+        assert!(limiter.try_acquire(ECSAction::DescribeTasks).is_ok());
+        assert_eq!((100, 20.0), limiter.limit(ECSAction::DescribeTasks));
+        assert_eq!((20, 5.0), default_limit(ECSAction::TagResource));
+        assert_eq!((50, 20.0), default_limit(ECSAction::ListAttributes));
+    }
+
+    #[test]
+    fn test_acquire_async_waits_for_refill() {
+        let mut limiter = RateLimiter::new();
+        limiter.set_limit(ECSAction::DescribeTasks, 1, 50.0).unwrap();
+        let limiter = Arc::new(limiter);
+        let start = Instant::now();
+        RateLimiter::acquire_async(limiter.clone(), ECSAction::DescribeTasks).wait().unwrap();
+        RateLimiter::acquire_async(limiter.clone(), ECSAction::DescribeTasks).wait().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(15));
+    }
+
+    #[test]
+    fn test_acquire_within_gives_up_before_the_timeout() {
+        let mut limiter = RateLimiter::new();
+        limiter.set_limit(ECSAction::DescribeTasks, 1, 1.0).unwrap();
+        let start = Instant::now();
+        assert!(limiter.acquire_within(ECSAction::DescribeTasks, Duration::from_millis(100)));
+        assert!(!limiter.acquire_within(ECSAction::DescribeTasks, Duration::from_millis(100)));
+        assert!(start.elapsed() < Duration::from_millis(100));
+
+        let limiter = Arc::new(limiter);
+        let acquired = RateLimiter::acquire_async_within(limiter.clone(),
+                                                         ECSAction::DescribeTasks,
+                                                         Duration::from_millis(100));
+        assert!(acquired.wait().is_err());
+        let acquired = RateLimiter::acquire_async_within(limiter, ECSAction::DescribeTasks, Duration::from_secs(2));
+        assert!(acquired.wait().is_ok());
+        assert!(start.elapsed() >= Duration::from_millis(900));
+    }
+
+    #[test]
+    fn test_set_limit_rejects_invalid_rates() {
+        let mut limiter = RateLimiter::new();
+        assert!(limiter.set_limit(ECSAction::ListClusters, 0, 1.0).is_err());
+        assert!(limiter.set_limit(ECSAction::ListClusters, 1, 0.0).is_err());
+        assert!(limiter.set_limit(ECSAction::ListClusters, 1, -1.0).is_err());
+        assert!(limiter.set_limit(ECSAction::ListClusters, 1, f64::NAN).is_err());
+        assert!(limiter.set_limit(ECSAction::ListClusters, 1, f64::INFINITY).is_err());
+        assert_eq!((50, 20.0), limiter.limit(ECSAction::ListClusters));
+    }
+}
@@ -1,33 +1,286 @@
-//! An enum defining the regions in which Amazon ECS is supported.
+//! An enum defining the regions in which Amazon ECS is supported, and the partitions they belong
+//! to.
 
+use error::{ECSError, ValidationError};
+use metadata::InstanceMetadataClient;
+use profile::{self, ProfileFile};
+
+use serde::ser::{Serialize, Serializer};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use std::env;
 use std::fmt::{Display, Formatter, Error};
+use std::str::FromStr;
+
+/// The name of the environment variable which selects the Region.
+pub const AWS_REGION: &'static str = "AWS_REGION";
+/// The name of the environment variable which selects the Region if AWS_REGION is not set.
+pub const AWS_DEFAULT_REGION: &'static str = "AWS_DEFAULT_REGION";
+/// The instance metadata path of the Region the instance runs in.
+const INSTANCE_REGION_PATH: &'static str = "/latest/meta-data/placement/region";
+
+/// Defines the Region enum from the code of each known region, along with the lookups between
+/// variants and codes.
+macro_rules! regions {
+    ($($variant:ident => $code:expr),+ $(,)*) => {
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum Region {
+            $($variant,)+
+            /// A region this crate does not know about yet, named by its code, e.g. "xx-east-1".
+            Custom(String),
+        }
+
+        impl Region {
+            /// Gets the code of the region, e.g. "us-west-2".
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(&Region::$variant => $code,)+
+                    &Region::Custom(ref code) => code,
+                }
+            }
+
+            /// Returns every region this crate knows about.
+            pub fn known() -> Vec<Region> {
+                vec![$(Region::$variant),+]
+            }
+
+            /// Looks up the known region with the given code.
+            fn from_known(code: &str) -> Option<Region> {
+                match code {
+                    $(c if c == $code => Some(Region::$variant),)+
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+regions! {
+    USEast1 => "us-east-1",
+    USEast2 => "us-east-2",
+    USWest1 => "us-west-1",
+    USWest2 => "us-west-2",
+    AFSouth1 => "af-south-1",
+    APEast1 => "ap-east-1",
+    APEast2 => "ap-east-2",
+    APSouth1 => "ap-south-1",
+    APSouth2 => "ap-south-2",
+    APNortheast1 => "ap-northeast-1",
+    APNortheast2 => "ap-northeast-2",
+    APNortheast3 => "ap-northeast-3",
+    APSoutheast1 => "ap-southeast-1",
+    APSoutheast2 => "ap-southeast-2",
+    APSoutheast3 => "ap-southeast-3",
+    APSoutheast4 => "ap-southeast-4",
+    APSoutheast5 => "ap-southeast-5",
+    APSoutheast6 => "ap-southeast-6",
+    APSoutheast7 => "ap-southeast-7",
+    CACentral1 => "ca-central-1",
+    CAWest1 => "ca-west-1",
+    EUCentral1 => "eu-central-1",
+    EUCentral2 => "eu-central-2",
+    EUWest1 => "eu-west-1",
+    EUWest2 => "eu-west-2",
+    EUWest3 => "eu-west-3",
+    EUSouth1 => "eu-south-1",
+    EUSouth2 => "eu-south-2",
+    EUNorth1 => "eu-north-1",
+    ILCentral1 => "il-central-1",
+    MECentral1 => "me-central-1",
+    MESouth1 => "me-south-1",
+    MXCentral1 => "mx-central-1",
+    SAEast1 => "sa-east-1",
+    USGovEast1 => "us-gov-east-1",
+    USGovWest1 => "us-gov-west-1",
+    CNNorth1 => "cn-north-1",
+    CNNorthwest1 => "cn-northwest-1",
+}
+
+/// A group of regions sharing a DNS suffix, an ARN prefix and a set of credentials.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Partition {
+    /// The commercial regions.
+    Aws,
+    /// The China regions.
+    AwsCn,
+    /// The AWS GovCloud (US) regions.
+    AwsUsGov,
+}
+
+impl Region {
+    /// Resolves the Region from AWS_REGION, then AWS_DEFAULT_REGION, then the region setting of the
+    /// active profile in the shared config file, and finally the instance metadata service.
+    pub fn from_env() -> Result<Region, ECSError> {
+        for variable in &[AWS_REGION, AWS_DEFAULT_REGION] {
+            if let Ok(code) = env::var(variable) {
+                if !code.is_empty() {
+                    return code.parse().map_err(ECSError::from);
+                }
+            }
+        }
+        let active_profile = profile::active_profile();
+        let config = try!(ProfileFile::load_config());
+        if let Some(code) = config.get(&active_profile, "region") {
+            return code.parse().map_err(ECSError::from);
+        }
+        match InstanceMetadataClient::new().get(INSTANCE_REGION_PATH) {
+            Ok(code) => code.trim().parse().map_err(ECSError::from),
+            Err(error) => Err(ECSError::Region(format!("neither {} nor {} is set, the {} profile has no region, \
+                                                        and instance metadata is unavailable ({})",
+                                                       AWS_REGION, AWS_DEFAULT_REGION, active_profile, error))),
+        }
+    }
 
-#[derive(Clone, Copy, Debug)]
-pub enum Region {
-    USEast1,
-    USWest1,
-    USWest2,
-    EUWest1,
-    EUCentral1,
-    APNortheast1,
-    APSoutheast1,
-    APSoutheast2,
+    /// Gets the partition the region belongs to.  The partition of a Custom region is inferred
+    /// from the prefix of its code.
+    pub fn get_partition(&self) -> Partition {
+        let code = self.as_str();
+        if code.starts_with("cn-") {
+            Partition::AwsCn
+        } else if code.starts_with("us-gov-") {
+            Partition::AwsUsGov
+        } else {
+            Partition::Aws
+        }
+    }
+}
+
+impl Partition {
+    /// Gets the name of the partition used in ARNs, e.g. "aws-cn".
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            &Partition::Aws => "aws",
+            &Partition::AwsCn => "aws-cn",
+            &Partition::AwsUsGov => "aws-us-gov",
+        }
+    }
+
+    /// Gets the DNS suffix of the service endpoints in the partition, e.g. "amazonaws.com.cn".
+    pub fn get_dns_suffix(&self) -> &'static str {
+        match self {
+            &Partition::AwsCn => "amazonaws.com.cn",
+            _ => "amazonaws.com",
+        }
+    }
+
+    /// Gets the DNS suffix of the dual-stack (IPv4 and IPv6) service endpoints in the partition,
+    /// e.g. "api.aws".
+    pub fn get_dual_stack_dns_suffix(&self) -> &'static str {
+        match self {
+            &Partition::AwsCn => "api.amazonwebservices.com.cn",
+            _ => "api.aws",
+        }
+    }
+
+    /// Returns true if the partition has FIPS 140 validated service endpoints.
+    pub fn supports_fips(&self) -> bool {
+        match self {
+            &Partition::AwsCn => false,
+            _ => true,
+        }
+    }
 }
 
 /// Used primarily to map Region variants to their String representation.
 impl Display for Region {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        let region_str = match self {
-            &Region::USEast1 => "us-east-1",
-            &Region::USWest1 => "us-west-1",
-            &Region::USWest2 => "us-west-2",
-            &Region::EUWest1 => "eu-west-1",
-            &Region::EUCentral1 => "eu-central-1",
-            &Region::APNortheast1 => "ap-northeast-1",
-            &Region::APSoutheast1 => "ap-southeast-1",
-            &Region::APSoutheast2 => "ap-southeast-2",
-        };
-
-        write!(f, "{}", region_str)
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Display for Partition {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{}", self.get_name())
+    }
+}
+
+/// Parses a region code into a known Region, or a Custom region if the code is well-formed but
+/// unknown, e.g. "xx-east-1".
+impl FromStr for Region {
+    type Err = ValidationError;
+
+    fn from_str(code: &str) -> Result<Region, ValidationError> {
+        if let Some(region) = Region::from_known(code) {
+            return Ok(region);
+        }
+        let parts: Vec<&str> = code.split('-').collect();
+        let well_formed = parts.len() >= 3 &&
+                          parts.iter().all(|part| !part.is_empty()) &&
+                          parts[..parts.len() - 1].iter().all(|part| part.chars().all(|c| c >= 'a' && c <= 'z')) &&
+                          parts[parts.len() - 1].chars().all(|c| c.is_digit(10));
+        if !well_formed {
+            return Err(ValidationError::new("region",
+                                            format!("must look like us-west-2, got {}", code)));
+        }
+        Ok(Region::Custom(String::from(code)))
+    }
+}
+
+impl Serialize for Region {
+    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl Deserialize for Region {
+    fn deserialize<D>(deserializer: &mut D) -> Result<Region, D::Error>
+        where D: Deserializer
+    {
+        struct RegionVisitor;
+
+        impl Visitor for RegionVisitor {
+            type Value = Region;
+
+            fn visit_str<E>(&mut self, value: &str) -> Result<Region, E>
+                where E: de::Error
+            {
+                value.parse().map_err(|error: ValidationError| E::custom(error.to_string()))
+            }
+        }
+
+        deserializer.deserialize_str(RegionVisitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Region, Partition};
+    use serde_json;
+
+    #[test]
+    fn test_parse_known() {
+        for region in Region::known() {
+            assert_eq!(region, region.as_str().parse().unwrap());
+        }
+        assert_eq!(Region::USWest2, "us-west-2".parse().unwrap());
+    }
+
+    #[test]
+    fn test_parse_custom() {
+        assert_eq!(Region::Custom(String::from("xx-east-1")), "xx-east-1".parse().unwrap());
+        assert_eq!("xx-east-1", Region::Custom(String::from("xx-east-1")).to_string());
+        assert!("".parse::<Region>().is_err());
+        assert!("us-west".parse::<Region>().is_err());
+        assert!("US-WEST-2".parse::<Region>().is_err());
+        assert!("us-west-two".parse::<Region>().is_err());
+    }
+
+    #[test]
+    fn test_partitions() {
+        assert_eq!(Partition::Aws, Region::EUWest1.get_partition());
+        assert_eq!(Partition::AwsCn, Region::CNNorthwest1.get_partition());
+        assert_eq!("amazonaws.com.cn", Region::CNNorth1.get_partition().get_dns_suffix());
+        assert_eq!(Partition::AwsUsGov, Region::USGovWest1.get_partition());
+        assert_eq!("aws-us-gov", Region::Custom(String::from("us-gov-south-1")).get_partition().get_name());
+        assert_eq!("api.amazonwebservices.com.cn", Partition::AwsCn.get_dual_stack_dns_suffix());
+        assert!(!Partition::AwsCn.supports_fips());
+    }
+
+    #[test]
+    fn test_serde() {
+        assert_eq!("\"ap-southeast-2\"", serde_json::to_string(&Region::APSoutheast2).unwrap());
+        assert_eq!(Region::CACentral1, serde_json::from_str::<Region>("\"ca-central-1\"").unwrap());
+        assert!(serde_json::from_str::<Region>("\"nowhere\"").is_err());
     }
 }
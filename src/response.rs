@@ -0,0 +1,161 @@
+//! This module contains the ResponseMetadata describing how an operation went on the wire: the
+//! request ID ECS assigned to it, which AWS support needs to trace a request, the HTTP status and
+//! selected headers of the last response, how many attempts were made and how long they took.
+//!
+//! The *_with_metadata() variant of each action, e.g. ECSClient::list_clusters_with_metadata(),
+//! returns it alongside the typed response in a Response, or alongside the ECSError in an
+//! OperationError, as does ECSClient::send_with_metadata() for any action.  The plain per-action
+//! methods drop it, but a ServiceError still carries the request ID of the response it was built
+//! from.
+
+use transport::HttpResponse;
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// The response headers kept in a ResponseMetadata, besides the request ID.
+const SELECTED_HEADERS: &'static [&'static str] = &["x-amzn-RequestId", "x-amzn-ErrorType", "Date",
+                                                     "Retry-After", "Content-Type", "Content-Length"];
+
+/// What is known about the HTTP exchanges of an operation.  The request ID, status and headers
+/// describe the last attempt, and are None or empty if it received no response.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResponseMetadata {
+    request_id: Option<String>,
+    status_code: Option<u16>,
+    /// The selected headers of the last response, keyed by lowercase name.
+    headers: BTreeMap<String, String>,
+    attempts: u32,
+    elapsed: Duration,
+}
+
+/// The typed response to an operation, along with its ResponseMetadata.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Response<U> {
+    output: U,
+    metadata: ResponseMetadata,
+}
+
+impl ResponseMetadata {
+    /// Creates a ResponseMetadata for an operation which has not made any attempt yet.
+    pub fn new() -> Self {
+        ResponseMetadata::default()
+    }
+
+    /// Records the response received by the last attempt, or None if it received no response.
+    pub fn record_response(&mut self, response: Option<&HttpResponse>) {
+        self.headers.clear();
+        self.request_id = response.and_then(|response| response.get_request_id());
+        self.status_code = response.map(|response| response.get_status_code());
+        if let Some(response) = response {
+            for name in SELECTED_HEADERS {
+                if let Some(value) = response.get_headers().get_raw(name).and_then(|values| values.first()) {
+                    self.headers.insert(name.to_lowercase(), String::from_utf8_lossy(value).into_owned());
+                }
+            }
+        }
+    }
+
+    /// Records the number of attempts made so far and the time elapsed since the first one.
+    pub fn record_attempts(&mut self, attempts: u32, elapsed: Duration) {
+        self.attempts = attempts;
+        self.elapsed = elapsed;
+    }
+
+    /// Gets the ID ECS assigned to the last request, from the x-amzn-RequestId header.
+    pub fn get_request_id(&self) -> Option<&String> {
+        self.request_id.as_ref()
+    }
+
+    /// Gets the HTTP status code of the last response.
+    pub fn get_status_code(&self) -> Option<u16> {
+        self.status_code
+    }
+
+    /// Gets the value of one of the selected headers of the last response, e.g. "Date", by
+    /// case-insensitive name.
+    pub fn get_header(&self, name: &str) -> Option<&String> {
+        self.headers.get(&name.to_lowercase())
+    }
+
+    /// Gets the selected headers of the last response, keyed by lowercase name.
+    pub fn get_headers(&self) -> &BTreeMap<String, String> {
+        &self.headers
+    }
+
+    /// Gets the number of attempts made, including the first one.
+    pub fn get_attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Gets the time elapsed from the first attempt until the last one finished, including the
+    /// delays between retries.
+    pub fn get_elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Gets the number of times the request was sent again after failing.
+    pub fn get_retries(&self) -> u32 {
+        self.attempts.saturating_sub(1)
+    }
+}
+
+impl<U> Response<U> {
+    /// Creates a Response.
+    pub fn new(output: U, metadata: ResponseMetadata) -> Self {
+        Response {
+            output: output,
+            metadata: metadata,
+        }
+    }
+
+    /// Gets the typed response.
+    pub fn get_output(&self) -> &U {
+        &self.output
+    }
+
+    /// Gets the metadata of the operation.
+    pub fn get_metadata(&self) -> &ResponseMetadata {
+        &self.metadata
+    }
+
+    /// Consumes the Response, returning the typed response.
+    pub fn into_output(self) -> U {
+        self.output
+    }
+
+    /// Consumes the Response, returning the typed response and the metadata.
+    pub fn into_parts(self) -> (U, ResponseMetadata) {
+        (self.output, self.metadata)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ResponseMetadata;
+    use transport::HttpResponse;
+    use hyper::header::Headers;
+    use std::time::Duration;
+
+    #[test]
+    fn test_record_response() {
+        let mut headers = Headers::new();
+        headers.set_raw("x-amzn-RequestId", vec![b"c0ffee00-1234".to_vec()]);
+        headers.set_raw("Date", vec![b"Sun, 18 Oct 2026 12:00:00 GMT".to_vec()]);
+        headers.set_raw("Set-Cookie", vec![b"ignored".to_vec()]);
+        let response = HttpResponse::new(400, headers, String::new());
+
+        let mut metadata = ResponseMetadata::new();
+        metadata.record_response(Some(&response));
+        metadata.record_attempts(3, Duration::from_millis(250));
+        assert_eq!(Some(&String::from("c0ffee00-1234")), metadata.get_request_id());
+        assert_eq!(Some(400), metadata.get_status_code());
+        assert_eq!(Some(&String::from("Sun, 18 Oct 2026 12:00:00 GMT")), metadata.get_header("date"));
+        assert_eq!(None, metadata.get_header("Set-Cookie"));
+        assert_eq!(2, metadata.get_retries());
+
+        metadata.record_response(None);
+        assert_eq!(None, metadata.get_request_id());
+        assert!(metadata.get_headers().is_empty());
+    }
+}
@@ -0,0 +1,139 @@
+//! This module contains the RetryPolicy which decides whether and when the ECSClient sends a
+//! failed request again.  Delays grow exponentially with each attempt and are drawn with "full
+//! jitter", i.e. uniformly between zero and the exponential cap, so that many clients throttled
+//! at once do not retry in lockstep.
+
+use error::ECSError;
+use sodiumoxide::randombytes;
+use std::cmp;
+use std::time::Duration;
+
+/// Decides how many times a request is attempted and how long to sleep between attempts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// The max number of times a request is sent, including the first attempt.
+    max_attempts: u32,
+    /// The delay cap before the first retry, in milliseconds.  The cap doubles for each
+    /// following retry.
+    base_delay_ms: u64,
+    /// The max delay cap, in milliseconds.
+    max_delay_ms: u64,
+}
+
+/// The default policy sends each request up to 3 times, with a base delay of 100 milliseconds
+/// and a max delay of 20 seconds, like the standard retry mode of the AWS SDKs.
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay_ms: 100,
+            max_delay_ms: 20000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a RetryPolicy with the given max attempts and base delay, and the default max
+    /// delay.
+    pub fn new(max_attempts: u32, base_delay: Duration) -> Self {
+        let mut policy = RetryPolicy::default();
+        policy.set_max_attempts(max_attempts);
+        policy.base_delay_ms = to_millis(base_delay);
+        policy
+    }
+
+    /// Creates a RetryPolicy which sends each request exactly once.
+    pub fn never() -> Self {
+        RetryPolicy::new(1, Duration::from_millis(0))
+    }
+
+    /// Sets the max number of times a request is sent, including the first attempt.  A value of
+    /// 0 is treated as 1.
+    pub fn set_max_attempts(&mut self, max_attempts: u32) {
+        self.max_attempts = cmp::max(max_attempts, 1);
+    }
+
+    /// Sets the max delay between two attempts.
+    pub fn set_max_delay(&mut self, max_delay: Duration) {
+        self.max_delay_ms = to_millis(max_delay);
+    }
+
+    /// Gets the max number of times a request is sent, including the first attempt.
+    pub fn get_max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns true if a request which failed with the given error on the given attempt (counting
+    /// from 1) should be sent again.
+    pub fn should_retry(&self, error: &ECSError, attempt: u32) -> bool {
+        attempt < self.max_attempts && error.is_retryable()
+    }
+
+    /// Returns the upper bound of the delay after the given attempt (counting from 1), which is
+    /// the base delay doubled for each previous retry, capped at the max delay.
+    pub fn delay_cap(&self, attempt: u32) -> Duration {
+        let exponent = cmp::min(attempt.saturating_sub(1), 32);
+        let cap = self.base_delay_ms.saturating_mul(1u64 << exponent);
+        Duration::from_millis(cmp::min(cap, self.max_delay_ms))
+    }
+
+    /// Returns a random delay to sleep after the given attempt (counting from 1), drawn uniformly
+    /// between zero and delay_cap(attempt).
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let cap = to_millis(self.delay_cap(attempt));
+        if cap == 0 {
+            return Duration::from_millis(0);
+        }
+        Duration::from_millis(random_u64() % (cap + 1))
+    }
+}
+
+/// Converts a Duration to whole milliseconds.
+pub fn to_millis(duration: Duration) -> u64 {
+    duration.as_secs().saturating_mul(1000) + (duration.subsec_nanos() / 1000000) as u64
+}
+
+/// Draws a random u64 from libsodium's random number generator.
+fn random_u64() -> u64 {
+    randombytes::randombytes(8).iter().fold(0, |acc, &byte| (acc << 8) | byte as u64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::RetryPolicy;
+    use error::{ECSError, ServiceError};
+    use std::time::Duration;
+
+    #[test]
+    fn test_delay_cap_grows_exponentially() {
+        let mut policy = RetryPolicy::new(10, Duration::from_millis(100));
+        policy.set_max_delay(Duration::from_millis(1000));
+        assert_eq!(Duration::from_millis(100), policy.delay_cap(1));
+        assert_eq!(Duration::from_millis(200), policy.delay_cap(2));
+        assert_eq!(Duration::from_millis(800), policy.delay_cap(4));
+        assert_eq!(Duration::from_millis(1000), policy.delay_cap(5));
+        assert_eq!(Duration::from_millis(1000), policy.delay_cap(100));
+    }
+
+    #[test]
+    fn test_delay_has_full_jitter() {
+        let policy = RetryPolicy::new(3, Duration::from_millis(100));
+        for attempt in 1..4 {
+            assert!(policy.delay(attempt) <= policy.delay_cap(attempt));
+        }
+    }
+
+    #[test]
+    fn test_should_retry() {
+        let policy = RetryPolicy::default();
+        let throttled = ECSError::Service(ServiceError::from_response(400,
+            "{\"__type\":\"ThrottlingException\",\"message\":\"Rate exceeded\"}"));
+        let invalid = ECSError::Service(ServiceError::from_response(400,
+            "{\"__type\":\"InvalidParameterException\",\"message\":\"bad\"}"));
+        assert!(policy.should_retry(&throttled, 1));
+        assert!(policy.should_retry(&throttled, 2));
+        assert!(!policy.should_retry(&throttled, 3));
+        assert!(!policy.should_retry(&invalid, 1));
+        assert!(!RetryPolicy::never().should_retry(&throttled, 1));
+    }
+}
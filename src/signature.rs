@@ -2,20 +2,16 @@
 //! order to make authenticated requests to any Amazon Web Service.
 
 use hyper::header::{Headers, Header, HeaderFormat, Host, AcceptEncoding, ContentType, ContentLength};
-use custom_headers::{XAmzTarget, XAmzDate};
+use custom_headers::{XAmzTarget, XAmzDate, XAmzSecurityToken};
+use credentials::Credentials;
 use sodiumoxide::crypto::hash::sha256;
 use sodiumoxide::crypto::auth::hmacsha256::State;
 use region::Region;
-use std::env;
 
 /// The default algorithm used for calculating the authentication signature.
 const SIGNING_ALGORITHM: &'static str = "AWS4-HMAC-SHA256";
 /// The termination string used in the credential scope value.
 const TERMINATION_STRING: &'static str = "aws4_request";
-/// The name of the environment variable in which your AWS Secret Access Key should be stored.
-const AWS_SECRET_ACCESS_KEY: &'static str = "AWS_SECRET_ACCESS_KEY";
-/// The name of the environment variable in which your AWS Access Key ID should be stored.
-const AWS_ACCESS_KEY_ID: &'static str = "AWS_ACCESS_KEY_ID";
 /// A String constant used in deriving the signing key.
 const AWS4: &'static str = "AWS4";
 /// A String constant used in deriving the signing key.
@@ -30,22 +26,38 @@ const SIGNATURE: &'static str = "Signature";
 /// Builds the Authorization HTTP header with all the required authentication information
 /// prescribed in http://docs.aws.amazon.com/general/latest/gr/sigv4-add-signature-to-request.html .
 /// Starts with the Signing Algorithm used, followed by a 'Credential=' key field, followed by your
-/// AWS Access Key ID (which is sourced from the environment variable AWS_ACCESS_KEY_ID), followed
+/// AWS Access Key ID (taken from the given Credentials), followed
 /// by the credential scope created during the signing process, followed by a comma, followed by
 /// a 'SignedHeaders=' key string with the signed_headers from the signing process following as
 /// the field, followed by the 'Signature=' key string followed by the signature calculated during
 /// the signing process!
-pub fn build_auth_header(headers: &Headers, body: &str, region: Region, serv_abbrev: &str) -> String {
-    let (signature, credential_scope, signed_headers) = calculate_signature(headers, body, region, serv_abbrev);
+pub fn build_auth_header(headers: &Headers,
+                         body: &str,
+                         credentials: &Credentials,
+                         region: &Region,
+                         serv_abbrev: &str) -> String {
+    build_auth_header_for_path(headers, "/", body, credentials, region, serv_abbrev)
+}
+
+/// Builds the Authorization HTTP header as build_auth_header() does, for a request sent to the
+/// given path rather than to the root of the endpoint.
+pub fn build_auth_header_for_path(headers: &Headers,
+                                  path: &str,
+                                  body: &str,
+                                  credentials: &Credentials,
+                                  region: &Region,
+                                  serv_abbrev: &str) -> String {
+    let (signature, credential_scope, signed_headers) =
+        calculate_signature_for_path(headers, path, body, credentials, region, serv_abbrev);
     let mut auth_header = String::from(SIGNING_ALGORITHM);
     auth_header.push_str(" ");
 
     auth_header.push_str(CREDENTIAL);
     auth_header.push_str("=");
-    auth_header.push_str(&get_from_environment(AWS_ACCESS_KEY_ID));
+    auth_header.push_str(credentials.get_access_key_id());
     auth_header.push_str("/");
     auth_header.push_str(&credential_scope);
-    println!("CREDENTIAL SCOPE!! {}", credential_scope);
+    trace!("signed headers {} for credential scope {}", signed_headers, credential_scope);
     auth_header.push_str(", ");
 
     auth_header.push_str(SIGNED_HEADERS);
@@ -56,7 +68,6 @@ pub fn build_auth_header(headers: &Headers, body: &str, region: Region, serv_abb
     auth_header.push_str(SIGNATURE);
     auth_header.push_str("=");
     auth_header.push_str(&signature);
-    println!("auth_header={}", auth_header);
     auth_header
 }
 
@@ -66,12 +77,24 @@ pub fn build_auth_header(headers: &Headers, body: &str, region: Region, serv_abb
 /// these values can be used to construct the Authorization HTTP header.
 pub fn calculate_signature(headers: &Headers,
                            body: &str,
-                           region: Region,
+                           credentials: &Credentials,
+                           region: &Region,
                            serv_abbrev: &str) -> (String, String, String) {
-    let (canonical_request, signed_headers) = build_canonical_request(headers, body);
+    calculate_signature_for_path(headers, "/", body, credentials, region, serv_abbrev)
+}
+
+/// Calculates the signature as calculate_signature() does, for a request sent to the given path
+/// rather than to the root of the endpoint.
+pub fn calculate_signature_for_path(headers: &Headers,
+                                    path: &str,
+                                    body: &str,
+                                    credentials: &Credentials,
+                                    region: &Region,
+                                    serv_abbrev: &str) -> (String, String, String) {
+    let (canonical_request, signed_headers) = build_canonical_request(headers, path, body);
     let hashed_canonical_request = hash_to_hex(&canonical_request);
     let (string_to_sign, credential_scope) = build_string_to_sign(headers, region, serv_abbrev, &hashed_canonical_request);
-    let signing_key = derive_signing_key(headers, region, serv_abbrev);
+    let signing_key = derive_signing_key(headers, credentials, region, serv_abbrev);
     let signature = sign(&signing_key, &string_to_sign);
     (signature, credential_scope, signed_headers)
 }
@@ -86,10 +109,11 @@ pub fn calculate_signature(headers: &Headers,
 /// and returned in a (String, String)=(canonical_request, signed_headers) tuple so that the
 /// signed_headers value used during the signing process can be built into the Authorization
 /// HTTP header.
-fn build_canonical_request(headers: &Headers, body: &str) -> (String, String) {
+fn build_canonical_request(headers: &Headers, path: &str, body: &str) -> (String, String) {
     let mut canon_req = String::from("POST\n");
     let mut signed_headers = String::new();
-    canon_req.push_str("/\n"); // canonical URI (empty)
+    canon_req.push_str(&canonical_uri(path));
+    canon_req.push_str("\n");
     canon_req.push_str("\n"); // canonical query string (empty)
 
     // CANONICAL HEADERS
@@ -134,6 +158,16 @@ fn build_canonical_request(headers: &Headers, body: &str) -> (String, String) {
     signed_headers.push_str(&XAmzDate::header_name().to_lowercase());
     signed_headers.push_str(";");
 
+    // only present when signing with temporary credentials
+    if let Some(security_token) = headers.get::<XAmzSecurityToken>() {
+        canon_req.push_str(&self::fmt_canonical_header(
+                XAmzSecurityToken::header_name(),
+                &(security_token as &(HeaderFormat + Send + Sync)).to_string()
+        ));
+        signed_headers.push_str(&XAmzSecurityToken::header_name().to_lowercase());
+        signed_headers.push_str(";");
+    }
+
     let x_amz_target: &XAmzTarget = headers.get().unwrap();
     canon_req.push_str(&self::fmt_canonical_header(
             XAmzTarget::header_name(),
@@ -151,6 +185,33 @@ fn build_canonical_request(headers: &Headers, body: &str) -> (String, String) {
     (canon_req, signed_headers)
 }
 
+/// Builds the canonical URI of a request sent to the given path, which is "/" for the root of the
+/// endpoint.  Each segment of the path is URI-encoded, so a segment which was already
+/// percent-encoded on the wire is encoded a second time, as every service but S3 expects.
+fn canonical_uri(path: &str) -> String {
+    if path.is_empty() || path == "/" {
+        return String::from("/");
+    }
+    let segments: Vec<String> = path.split('/').map(uri_encode).collect();
+    let uri = segments.join("/");
+    if uri.starts_with('/') { uri } else { format!("/{}", uri) }
+}
+
+/// URI-encodes every byte of the input except the unreserved characters A-Z, a-z, 0-9, '-', '.',
+/// '_' and '~'.
+fn uri_encode(input: &str) -> String {
+    let mut encoded = String::new();
+    for byte in input.bytes() {
+        match byte {
+            b'A'...b'Z' | b'a'...b'z' | b'0'...b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
 /// Formats a single header according to the canonical format.  Header names must appear in
 /// lowercase, followed by a ':', followed by the header value, with consecutive spaces
 /// converted to single spaces.  A newline character terminates the String.
@@ -192,7 +253,7 @@ fn hex_encode(digest: &[u8; 32]) -> String {
 /// Returns a tuple containing (string_to_sign, credential_scope) so that credential scope value
 /// can be reused in the Authorization HTTP header.
 fn build_string_to_sign(headers: &Headers,
-                        region: Region,
+                        region: &Region,
                         serv_abbrev: &str,
                         hashed_canon_req: &str) -> (String, String) {
     // start with signing algorithm
@@ -217,7 +278,7 @@ fn build_string_to_sign(headers: &Headers,
 /// string "aws4_request", (each separated by a "/" character), followed by a newline character.
 /// For example:
 /// 20160421/us-east-1/ecs/aws4_request\n
-fn build_credential_scope(datetime: &str, region: Region, serv_abbrev: &str) -> String {
+fn build_credential_scope(datetime: &str, region: &Region, serv_abbrev: &str) -> String {
     let mut cred_scope = String::from(datetime.split("T").nth(0).unwrap());
     cred_scope.push_str("/");
     cred_scope.push_str(&region.to_string());
@@ -229,69 +290,33 @@ fn build_credential_scope(datetime: &str, region: Region, serv_abbrev: &str) ->
 }
 
 /// Derives the signing key from your AWS secret access key, the date of your request, the service
-/// name, and the region the request is being sent to.  AWS credentials are sourced from
-/// environment variables.  Your AWS secret access key must be stored in an environment variable
-/// called AWS_SECRET_ACCESS_KEY.
-fn derive_signing_key(headers: &Headers, region: Region, serv_abbrev: &str) -> [u8; 32] {
+/// name, and the region the request is being sent to.
+fn derive_signing_key(headers: &Headers, credentials: &Credentials, region: &Region, serv_abbrev: &str) -> [u8; 32] {
     let mut init_key = String::from(AWS4);
-    init_key.push_str(&get_from_environment(AWS_SECRET_ACCESS_KEY));
+    init_key.push_str(credentials.get_secret_access_key());
     let date: &XAmzDate = headers.get().unwrap();
     let date_val = date.0.split("T").nth(0).unwrap(); // use only the date portion
     // derive the key
-    println!("init_key={}", init_key);
     let mut state = State::init(&init_key.as_bytes());
 
-    println!("hashing date={}", date_val);
     state.update(&date_val.as_bytes());
     let date_key = state.finalize();
 
-    println!("hashing region={}", region.to_string());
     state = State::init(&date_key.0);
     state.update(&region.to_string().as_bytes());
     let region_key = state.finalize();
 
-    println!("hashing service={}", serv_abbrev);
     state = State::init(&region_key.0);
     state.update(&serv_abbrev.as_bytes());
     let service_key = state.finalize();
 
-    println!("hashing termination_string={}", AWS4_REQUEST);
     state = State::init(&service_key.0);
     state.update(&AWS4_REQUEST.as_bytes());
     let signing_key = state.finalize();
 
-    println!("derived signing key = {:?}", signing_key.0);
     signing_key.0
 }
 
-/// Gets the environment variable env_var_name from your current environment.  This is used to get
-/// your AWS Access Key ID from the AWS_ACCESS_KEY_ID environment variable and your AWS Secret 
-/// Key ID from the AWS_SECRET_KEY_ID environment variable.  If these variables aren't set, the
-/// client will panic.
-fn get_from_environment(env_var_name: &str) -> String {
-    match env::var(env_var_name) {
-        Ok(val) => {
-            if val == "" {
-                println!("Your AWS Secret Access Key and AWS Access Key ID must be stored in the \
-                    environment variables called AWS_SECRET_ACCESS_KEY and AWS_ACCESS_KEY_ID, \
-                    respectively.\nTry:\n$ export AWS_SECRET_ACCESS_KEY=\"your_secret_access_key\"\n\
-                    $ export AWS_ACCESS_KEY_ID=\"your_access_key_id\"\n");
-                panic!("Couldn't obtain AWS credentials from environment!");
-            } else {
-                val
-            }
-        }
-        Err(e) => {
-            println!("Couldn't interpret {}: {}", env_var_name, e);
-            println!("Your AWS Secret Access Key and AWS Access Key ID must be stored in the \
-                environment variables called AWS_SECRET_ACCESS_KEY and AWS_ACCESS_KEY_ID, \
-                respectively.\nTry:\n$ export AWS_SECRET_ACCESS_KEY=\"your_secret_access_key\"\n\
-                $ export AWS_ACCESS_KEY_ID=\"your_access_key_id\"\n");
-            panic!("Couldn't obtain AWS credentials from environment!");
-        }
-    }
-}
-
 /// Signs the 'string to sign' and returns the calculated signature.
 fn sign(signing_key: &[u8; 32], string_to_sign: &str) -> String {
     let mut state = State::init(signing_key);
@@ -302,7 +327,7 @@ fn sign(signing_key: &[u8; 32], string_to_sign: &str) -> String {
 
 #[cfg(test)]
 mod test {
-    use std::env;
+    use credentials::Credentials;
     use region::Region;
     use hyper::header::Headers;
     use custom_headers::XAmzDate;
@@ -311,31 +336,35 @@ mod test {
     fn test_digest_to_hex() {
         // expected hash value of an empty string
         let expected = String::from("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
-        println!("{}", super::hash_to_hex(""));
         assert_eq!(expected, super::hash_to_hex(""))
     }
     
     // using the example at http://docs.aws.amazon.com/general/latest/gr/sigv4-calculate-signature.html
+    #[test]
+    fn test_canonical_uri() {
+        assert_eq!("/", super::canonical_uri(""));
+        assert_eq!("/", super::canonical_uri("/"));
+        assert_eq!("/ecs/v1", super::canonical_uri("/ecs/v1"));
+        assert_eq!("/a%2520b/c~d/", super::canonical_uri("/a%20b/c~d/"));
+    }
+
     #[test]
     fn test_derive_signing_key() {
-        init_test_state();
         let test_headers = build_test_headers();
         
         let expected_bytes = vec![196, 175, 177, 204, 87, 113, 216, 113, 118, 58, 57, 62, 68,
             183, 3, 87, 27, 85, 204, 40, 66, 77, 26, 94, 134, 218, 110, 211, 193, 84, 164, 185];
-        let result = super::derive_signing_key(&test_headers, Region::USEast1, "iam");
+        let result = super::derive_signing_key(&test_headers, &test_credentials(), &Region::USEast1, "iam");
         assert_eq!(32, result.len());
         for (i, byte) in result.iter().enumerate() {
-            println!("{}", byte);
             assert_eq!(*expected_bytes.get(i).unwrap(), *byte as i32);
         }
     }
 
     #[test]
     fn test_sign() {
-        init_test_state();
         let test_headers = build_test_headers();
-        let signing_key = super::derive_signing_key(&test_headers, Region::USEast1, "iam");
+        let signing_key = super::derive_signing_key(&test_headers, &test_credentials(), &Region::USEast1, "iam");
         let string_to_sign = 
             "AWS4-HMAC-SHA256\n\
             20150830T123600Z\n\
@@ -352,8 +381,7 @@ mod test {
         headers
     }
 
-    fn init_test_state() {
-        let aws_secret_access_key = "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY";
-        env::set_var(super::AWS_SECRET_ACCESS_KEY, aws_secret_access_key);
+    fn test_credentials() -> Credentials {
+        Credentials::new(String::from("AKIDEXAMPLE"), String::from("wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY"))
     }
 }
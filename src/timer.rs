@@ -0,0 +1,157 @@
+//! This module contains a timer shared by every future in the crate which has to wait, such as
+//! RateLimiter::acquire_async().  A single background thread keeps a heap of pending wakeups and
+//! notifies each futures task once its instant has passed, so waiting neither blocks the task
+//! polling the future nor spawns a thread per wait, and works with any executor.
+
+use futures::{Future, Poll, Async};
+use futures::task::{self, Task};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::{Condvar, Mutex, Once, ONCE_INIT};
+use std::sync::atomic::{self, AtomicUsize, ATOMIC_USIZE_INIT};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A task to notify at a given instant.
+struct Wakeup {
+    when: Instant,
+    task: Task,
+}
+
+/// Ordered so that the earliest wakeup is at the top of a BinaryHeap, which is a max-heap.
+impl Ord for Wakeup {
+    fn cmp(&self, other: &Wakeup) -> Ordering {
+        other.when.cmp(&self.when)
+    }
+}
+
+impl PartialOrd for Wakeup {
+    fn partial_cmp(&self, other: &Wakeup) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Wakeup {
+    fn eq(&self, other: &Wakeup) -> bool {
+        self.when == other.when
+    }
+}
+
+impl Eq for Wakeup {}
+
+/// The state shared between the timer thread and the tasks registering wakeups.
+struct Timer {
+    wakeups: Mutex<BinaryHeap<Wakeup>>,
+    /// Signalled whenever a wakeup is registered, since it may be earlier than the one the timer
+    /// thread is sleeping until.
+    registered: Condvar,
+}
+
+static START: Once = ONCE_INIT;
+/// The address of the leaked timer, set once by START and never changed afterwards.
+static TIMER: AtomicUsize = ATOMIC_USIZE_INIT;
+
+/// Gets the shared timer, starting its thread the first time it is needed.
+fn timer() -> &'static Timer {
+    START.call_once(|| {
+        let timer: &'static Timer = leak(Box::new(Timer {
+            wakeups: Mutex::new(BinaryHeap::new()),
+            registered: Condvar::new(),
+        }));
+        TIMER.store(timer as *const Timer as usize, atomic::Ordering::SeqCst);
+        thread::Builder::new().name(String::from("ecs-client-timer"))
+                              .spawn(move || run(timer))
+                              .expect("failed to start the timer thread");
+    });
+    // call_once() returns only after the timer is stored, and a leaked timer is never freed
+    unsafe { &*(TIMER.load(atomic::Ordering::SeqCst) as *const Timer) }
+}
+
+/// Leaks a box, so that what it holds lives for the rest of the program.
+fn leak<T>(boxed: Box<T>) -> &'static T {
+    unsafe { &*Box::into_raw(boxed) }
+}
+
+/// Notifies each task once its wakeup is due, sleeping until the earliest pending one.
+fn run(timer: &Timer) {
+    let mut wakeups = timer.wakeups.lock().unwrap();
+    loop {
+        let now = Instant::now();
+        let mut due = Vec::new();
+        while wakeups.peek().map_or(false, |next| next.when <= now) {
+            due.push(wakeups.pop().unwrap());
+        }
+        if !due.is_empty() {
+            // notify without holding the lock, since notifying may run executor code
+            drop(wakeups);
+            for wakeup in due {
+                wakeup.task.notify();
+            }
+            wakeups = timer.wakeups.lock().unwrap();
+            continue;
+        }
+        let next = wakeups.peek().map(|next| next.when);
+        wakeups = match next {
+            Some(when) => timer.registered.wait_timeout(wakeups, when.duration_since(now)).unwrap().0,
+            None => timer.registered.wait(wakeups).unwrap(),
+        };
+    }
+}
+
+/// Notifies the given task once the given instant has passed.
+pub fn wake_at(when: Instant, task: Task) {
+    let timer = timer();
+    timer.wakeups.lock().unwrap().push(Wakeup {
+        when: when,
+        task: task,
+    });
+    timer.registered.notify_one();
+}
+
+/// Returns a future which completes once the given duration has passed.
+pub fn sleep(duration: Duration) -> Delay {
+    Delay { when: Instant::now() + duration }
+}
+
+/// A future which completes at a given instant, created by sleep().
+#[derive(Debug)]
+pub struct Delay {
+    when: Instant,
+}
+
+impl Future for Delay {
+    type Item = ();
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<(), ()> {
+        if Instant::now() >= self.when {
+            return Ok(Async::Ready(()));
+        }
+        wake_at(self.when, task::current());
+        Ok(Async::NotReady)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::sleep;
+    use futures::Future;
+    use futures::future;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_sleep() {
+        let start = Instant::now();
+        sleep(Duration::from_millis(20)).wait().unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn test_many_sleeps_share_the_timer() {
+        let start = Instant::now();
+        let delays: Vec<_> = (0..100).map(|i| sleep(Duration::from_millis(10 + i % 10))).collect();
+        future::join_all(delays).wait().unwrap();
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(19) && elapsed < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,246 @@
+//! This module defines the HttpTransport trait which the ECSClient sends its signed HTTP requests
+//! through, along with the hyper implementation used by default and a MockTransport which
+//! records requests and answers them with canned responses, for testing without a network.
+
+use builder::ClientConfig;
+use connector::ProxyConnector;
+use error::ECSError;
+use logging;
+
+use hyper;
+use hyper::client::pool::{self, Pool};
+use hyper::header::Headers;
+use hyper::net::Openssl;
+use openssl::ssl::{SslContext, SslMethod, SSL_VERIFY_PEER};
+use std::collections::VecDeque;
+use std::fmt::{self, Debug, Formatter};
+use std::io::{self, Read};
+use std::sync::{Arc, Mutex};
+
+/// The header in which ECS returns the ID AWS support needs to trace a request.
+pub const REQUEST_ID_HEADER: &'static str = "x-amzn-RequestId";
+
+/// A signed HTTP POST request, ready to be sent to an ECS endpoint.  Its Debug output redacts the
+/// Authorization and X-Amz-Security-Token headers.
+#[derive(Clone)]
+pub struct HttpRequest {
+    /// The URL the request is sent to.
+    url: String,
+    /// The headers of the request, including the Authorization header.
+    headers: Headers,
+    /// The json body of the request.
+    body: String,
+}
+
+/// An HTTP response received from an ECS endpoint.
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+    /// The status code of the response.
+    status_code: u16,
+    /// The headers of the response.
+    headers: Headers,
+    /// The body of the response.
+    body: String,
+}
+
+/// Sends HTTP requests on behalf of an ECSClient.  Implementations only move bytes: signing,
+/// retries and interpreting error responses are left to the client.
+pub trait HttpTransport : Debug + Send + Sync {
+    /// Sends the request and returns the response, whatever its status code.  An Err is only
+    /// returned when no response could be received at all.
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, ECSError>;
+}
+
+/// Used so that a transport can be shared between a client and the code inspecting it, as with a
+/// MockTransport in tests.
+impl<T: HttpTransport> HttpTransport for Arc<T> {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, ECSError> {
+        (**self).send(request)
+    }
+}
+
+/// The default HttpTransport, which sends requests with a pooled hyper::Client.
+#[derive(Debug)]
+pub struct HyperTransport {
+    client: hyper::Client,
+}
+
+/// An HttpTransport which records every request it is given and answers each with the next of a
+/// queue of canned responses.
+#[derive(Debug)]
+pub struct MockTransport {
+    /// The requests sent so far, in order.
+    requests: Mutex<Vec<HttpRequest>>,
+    /// The responses still to be returned, in order.
+    responses: Mutex<VecDeque<Result<HttpResponse, ECSError>>>,
+}
+
+impl HttpRequest {
+    /// Creates an HttpRequest.
+    pub fn new(url: String, headers: Headers, body: String) -> HttpRequest {
+        HttpRequest {
+            url: url,
+            headers: headers,
+            body: body,
+        }
+    }
+
+    /// Gets the URL the request is sent to.
+    pub fn get_url(&self) -> &String {
+        &self.url
+    }
+
+    /// Gets the headers of the request.
+    pub fn get_headers(&self) -> &Headers {
+        &self.headers
+    }
+
+    /// Gets the json body of the request.
+    pub fn get_body(&self) -> &String {
+        &self.body
+    }
+}
+
+impl Debug for HttpRequest {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("HttpRequest")
+         .field("url", &self.url)
+         .field("headers", &logging::redact_headers(&self.headers))
+         .field("body", &logging::redact_body(&self.body))
+         .finish()
+    }
+}
+
+impl HttpResponse {
+    /// Creates an HttpResponse.
+    pub fn new(status_code: u16, headers: Headers, body: String) -> HttpResponse {
+        HttpResponse {
+            status_code: status_code,
+            headers: headers,
+            body: body,
+        }
+    }
+
+    /// Gets the status code of the response.
+    pub fn get_status_code(&self) -> u16 {
+        self.status_code
+    }
+
+    /// Gets the headers of the response.
+    pub fn get_headers(&self) -> &Headers {
+        &self.headers
+    }
+
+    /// Gets the body of the response.
+    pub fn get_body(&self) -> &String {
+        &self.body
+    }
+
+    /// Gets the ID ECS assigned to the request, from the x-amzn-RequestId header.
+    pub fn get_request_id(&self) -> Option<String> {
+        self.headers.get_raw(REQUEST_ID_HEADER)
+                    .and_then(|values| values.first())
+                    .map(|value| String::from_utf8_lossy(value).into_owned())
+    }
+
+    /// Returns true if the status code is in the 2xx range.
+    pub fn is_success(&self) -> bool {
+        self.status_code >= 200 && self.status_code < 300
+    }
+}
+
+impl HyperTransport {
+    /// Creates a HyperTransport with a new hyper::Client.
+    pub fn new() -> HyperTransport {
+        HyperTransport::with_client(hyper::Client::new())
+    }
+
+    /// Creates a HyperTransport which sends requests with the given hyper::Client.
+    pub fn with_client(client: hyper::Client) -> HyperTransport {
+        HyperTransport {
+            client: client,
+        }
+    }
+
+    /// Creates a HyperTransport whose connections honour the timeouts, proxies, pool size and CA
+    /// bundle of the given ClientConfig.  Fails if the CA bundle cannot be loaded.
+    pub fn with_config(config: &ClientConfig) -> Result<HyperTransport, ECSError> {
+        let tls_error = |error| ECSError::Io(io::Error::new(io::ErrorKind::Other, format!("TLS setup failed: {}", error)));
+        let mut context = try!(SslContext::new(SslMethod::Sslv23).map_err(&tls_error));
+        try!(context.set_default_verify_paths().map_err(&tls_error));
+        if let Some(ref ca_bundle) = *config.get_ca_bundle() {
+            try!(context.set_CA_file(ca_bundle).map_err(&tls_error));
+        }
+        context.set_verify(SSL_VERIFY_PEER, None);
+        let ssl = Openssl {
+            context: Arc::new(context),
+        };
+
+        let connector = ProxyConnector::new(ssl, config.get_proxy().clone(), config.get_connect_timeout());
+        let pool = Pool::with_connector(pool::Config {
+            max_idle: config.get_max_idle_connections(),
+        }, connector);
+        let mut client = hyper::Client::with_connector(pool);
+        client.set_read_timeout(config.get_read_timeout());
+        client.set_write_timeout(config.get_write_timeout());
+        Ok(HyperTransport::with_client(client))
+    }
+}
+
+impl HttpTransport for HyperTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, ECSError> {
+        let mut response = try!(self.client.post(&request.url)
+                                           .headers(request.headers.clone())
+                                           .body(&request.body[..])
+                                           .send());
+        let mut body = String::new();
+        try!(response.read_to_string(&mut body));
+        Ok(HttpResponse::new(response.status.to_u16(), response.headers.clone(), body))
+    }
+}
+
+impl MockTransport {
+    /// Creates a MockTransport with no responses queued.
+    pub fn new() -> MockTransport {
+        MockTransport {
+            requests: Mutex::new(Vec::new()),
+            responses: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Queues a response with the given status code and body.
+    pub fn push_response(&self, status_code: u16, body: &str) {
+        self.responses.lock().unwrap().push_back(Ok(HttpResponse::new(status_code, Headers::new(), String::from(body))));
+    }
+
+    /// Queues a response with headers, such as x-amzn-RequestId.
+    pub fn push_http_response(&self, response: HttpResponse) {
+        self.responses.lock().unwrap().push_back(Ok(response));
+    }
+
+    /// Queues an error, as if no response could be received.
+    pub fn push_error(&self, error: ECSError) {
+        self.responses.lock().unwrap().push_back(Err(error));
+    }
+
+    /// Gets a copy of every request sent so far, in order.
+    pub fn get_requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Gets the number of queued responses which have not been returned yet.
+    pub fn remaining_responses(&self) -> usize {
+        self.responses.lock().unwrap().len()
+    }
+}
+
+impl HttpTransport for MockTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, ECSError> {
+        self.requests.lock().unwrap().push(request.clone());
+        match self.responses.lock().unwrap().pop_front() {
+            Some(response) => response,
+            None => Err(ECSError::Io(io::Error::new(io::ErrorKind::Other,
+                                                    "MockTransport has no response queued"))),
+        }
+    }
+}
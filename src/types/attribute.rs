@@ -0,0 +1,166 @@
+//! Defines the Attribute data type used to label container instances with custom metadata, along
+//! with the client-side validation of the attribute constraints documented by ECS.  Attributes can
+//! be referenced from memberOf placement constraints to control where tasks are placed.
+
+use custom_ser;
+use error::ValidationError;
+
+/// The maximum length of an attribute name or value.
+const MAX_LENGTH: usize = 128;
+/// The punctuation allowed in attribute names, in addition to letters and numbers.
+const NAME_PUNCTUATION: &'static str = "-_/\\.";
+/// The punctuation allowed in attribute values, in addition to letters and numbers.
+const VALUE_PUNCTUATION: &'static str = "-_.@/\\: ";
+
+string_enum! {
+    /// The type of resource an Attribute can be attached to.
+    pub enum TargetType {
+        ContainerInstance => "container-instance",
+    }
+}
+
+/// A name, optional value, and target used to label an ECS resource.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Attribute {
+    /// The name of the attribute.
+    name: String,
+    /// The value of the attribute.  Attributes without a value are used as simple labels.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    value: Option<String>,
+    /// The type of the resource the attribute is attached to.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    targetType: Option<TargetType>,
+    /// The ID of the resource the attribute is attached to, such as a container instance ARN.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    targetId: Option<String>,
+}
+
+impl Attribute {
+    /// Creates an Attribute with no target after checking the name and value against the
+    /// constraints ECS places on them.  The target is supplied by the surrounding request when
+    /// the attribute is registered along with a container instance.
+    pub fn new(name: String, value: Option<String>) -> Result<Attribute, ValidationError> {
+        try!(validate_name(&name));
+        if let Some(ref value) = value {
+            try!(validate_value(value));
+        }
+        Ok(Attribute {
+            name: name,
+            value: value,
+            targetType: None,
+            targetId: None,
+        })
+    }
+
+    /// Creates an Attribute attached to the given target after checking the name and value
+    /// against the constraints ECS places on them.
+    pub fn with_target(name: String,
+                       value: Option<String>,
+                       target_type: TargetType,
+                       target_id: String) -> Result<Attribute, ValidationError> {
+        let mut attribute = try!(Attribute::new(name, value));
+        attribute.targetType = Some(target_type);
+        attribute.targetId = Some(target_id);
+        Ok(attribute)
+    }
+
+    /// Gets the name of the attribute.
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    /// Gets the value of the attribute.
+    pub fn get_value(&self) -> &Option<String> {
+        &self.value
+    }
+
+    /// Gets the type of the resource the attribute is attached to.
+    pub fn get_target_type(&self) -> &Option<TargetType> {
+        &self.targetType
+    }
+
+    /// Gets the ID of the resource the attribute is attached to.
+    pub fn get_target_id(&self) -> &Option<String> {
+        &self.targetId
+    }
+}
+
+/// Checks an attribute name: 1-128 letters, numbers, hyphens, underscores, slashes, backslashes
+/// or periods.
+pub fn validate_name(name: &str) -> Result<(), ValidationError> {
+    try!(validate_length("attribute name", name));
+    validate_characters("attribute name", name, NAME_PUNCTUATION)
+}
+
+/// Checks an attribute value: 1-128 letters, numbers, hyphens, underscores, periods, at signs,
+/// slashes, backslashes, colons or spaces, neither starting nor ending with a space.
+pub fn validate_value(value: &str) -> Result<(), ValidationError> {
+    try!(validate_length("attribute value", value));
+    if value.starts_with(' ') || value.ends_with(' ') {
+        return Err(ValidationError::new("attribute value",
+                                        format!("cannot start or end with a space: '{}'", value)));
+    }
+    validate_characters("attribute value", value, VALUE_PUNCTUATION)
+}
+
+fn validate_length(parameter: &str, input: &str) -> Result<(), ValidationError> {
+    let length = input.chars().count();
+    if length == 0 || length > MAX_LENGTH {
+        return Err(ValidationError::new(parameter,
+                                        format!("must be between 1 and {} characters, got {}", MAX_LENGTH, length)));
+    }
+    Ok(())
+}
+
+fn validate_characters(parameter: &str, input: &str, punctuation: &str) -> Result<(), ValidationError> {
+    for c in input.chars() {
+        if !(((c as u32) < 128 && c.is_alphanumeric()) || punctuation.contains(c)) {
+            return Err(ValidationError::new(parameter, format!("character '{}' is not allowed: {}", c, input)));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Attribute, TargetType};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_serialize_with_target() {
+        let attribute = Attribute::with_target(String::from("stack"), Some(String::from("prod")),
+                                               TargetType::ContainerInstance, String::from("arn")).unwrap();
+        let expected: value::Value = serde_json::from_str(
+            "{\"name\":\"stack\",\"value\":\"prod\",\"targetType\":\"container-instance\",\
+              \"targetId\":\"arn\"}").unwrap();
+        let actual: value::Value = value::to_value(&attribute);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_serialize_label() {
+        let attribute = Attribute::new(String::from("ecs.capability.gpu"), None).unwrap();
+        let expected: value::Value = serde_json::from_str("{\"name\":\"ecs.capability.gpu\"}").unwrap();
+        let actual: value::Value = value::to_value(&attribute);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_name_rules() {
+        assert!(Attribute::new(String::from("team/app_1.x-y"), None).is_ok());
+        assert!(Attribute::new(String::new(), None).is_err());
+        assert!(Attribute::new(String::from("has space"), None).is_err());
+        assert!(Attribute::new(String::from("colon:"), None).is_err());
+    }
+
+    #[test]
+    fn test_value_rules() {
+        assert!(Attribute::new(String::from("owner"), Some(String::from("ops@example.com: a/b"))).is_ok());
+        assert!(Attribute::new(String::from("owner"), Some(String::from(" padded"))).is_err());
+        assert!(Attribute::new(String::from("owner"), Some(String::from("padded "))).is_err());
+        assert!(Attribute::new(String::from("owner"), Some(String::from("semi;colon"))).is_err());
+        assert!(Attribute::new(String::from("owner"), Some(String::new())).is_err());
+    }
+}
@@ -0,0 +1,425 @@
+//! Defines the CapacityProvider data type and the types used to describe how a cluster spreads
+//! tasks across its capacity providers.
+
+use custom_ser;
+use error::ValidationError;
+use types::tag::Tag;
+
+/// The name of the capacity provider AWS provides for Fargate tasks.
+pub const FARGATE: &'static str = "FARGATE";
+/// The name of the capacity provider AWS provides for Fargate Spot tasks.
+pub const FARGATE_SPOT: &'static str = "FARGATE_SPOT";
+/// The maximum relative weight of a CapacityProviderStrategyItem.
+const MAX_WEIGHT: u32 = 1000;
+/// The maximum base of a CapacityProviderStrategyItem.
+const MAX_BASE: u32 = 100000;
+/// The maximum length of a capacity provider name.
+const MAX_NAME_LENGTH: usize = 255;
+/// Prefixes reserved by AWS which cannot begin a custom capacity provider name.
+const RESERVED_NAME_PREFIXES: [&'static str; 3] = ["aws", "ecs", "fargate"];
+
+string_enum! {
+    /// Whether a capacity provider is usable.
+    pub enum CapacityProviderStatus {
+        Active => "ACTIVE",
+        Inactive => "INACTIVE",
+    }
+}
+
+string_enum! {
+    /// The progress of an update or deletion of a capacity provider.
+    pub enum CapacityProviderUpdateStatus {
+        DeleteInProgress => "DELETE_IN_PROGRESS",
+        DeleteComplete => "DELETE_COMPLETE",
+        DeleteFailed => "DELETE_FAILED",
+        UpdateInProgress => "UPDATE_IN_PROGRESS",
+        UpdateComplete => "UPDATE_COMPLETE",
+        UpdateFailed => "UPDATE_FAILED",
+    }
+}
+
+string_enum! {
+    /// Whether managed scaling is turned on for an Auto Scaling group capacity provider.
+    pub enum ManagedScalingStatus {
+        Enabled => "ENABLED",
+        Disabled => "DISABLED",
+    }
+}
+
+string_enum! {
+    /// Whether instances in an Auto Scaling group running tasks are protected from scale-in.
+    pub enum ManagedTerminationProtection {
+        Enabled => "ENABLED",
+        Disabled => "DISABLED",
+    }
+}
+
+/// The scaling settings ECS uses to manage the size of an Auto Scaling group.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ManagedScaling {
+    /// Whether managed scaling is turned on.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    status: Option<ManagedScalingStatus>,
+    /// The target utilization of the Auto Scaling group, as a percentage between 1 and 100.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    targetCapacity: Option<u8>,
+    /// The minimum number of instances to scale by at a time, between 1 and 10000.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    minimumScalingStepSize: Option<u16>,
+    /// The maximum number of instances to scale by at a time, between 1 and 10000.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    maximumScalingStepSize: Option<u16>,
+    /// The seconds a newly launched instance takes to contribute to CloudWatch metrics.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    instanceWarmupPeriod: Option<u16>,
+}
+
+/// The Auto Scaling group backing a capacity provider.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AutoScalingGroupProvider {
+    /// The Amazon Resource Name (ARN) of the Auto Scaling group.
+    autoScalingGroupArn: String,
+    /// The managed scaling settings of the capacity provider.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    managedScaling: Option<ManagedScaling>,
+    /// Whether instances running tasks are protected from scale-in.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    managedTerminationProtection: Option<ManagedTerminationProtection>,
+}
+
+/// The parts of an AutoScalingGroupProvider which can be changed by UpdateCapacityProvider.
+#[allow(non_snake_case)]
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct AutoScalingGroupProviderUpdate {
+    /// The new managed scaling settings of the capacity provider.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    managedScaling: Option<ManagedScaling>,
+    /// Whether instances running tasks are protected from scale-in.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    managedTerminationProtection: Option<ManagedTerminationProtection>,
+}
+
+/// A capacity provider, which manages the infrastructure tasks in a cluster run on.
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct CapacityProvider {
+    /// The Amazon Resource Name (ARN) of the capacity provider.
+    capacityProviderArn: Option<String>,
+    /// The name of the capacity provider.
+    name: Option<String>,
+    /// Whether the capacity provider is usable.
+    status: Option<CapacityProviderStatus>,
+    /// The Auto Scaling group backing the capacity provider.
+    autoScalingGroupProvider: Option<AutoScalingGroupProvider>,
+    /// The progress of the latest update or deletion of the capacity provider.
+    updateStatus: Option<CapacityProviderUpdateStatus>,
+    /// The reason for the latest update status.
+    updateStatusReason: Option<String>,
+    /// The tags applied to the capacity provider.
+    tags: Option<Vec<Tag>>,
+}
+
+/// One entry of a capacity provider strategy, describing how tasks are spread across capacity
+/// providers.  For example, a strategy of FARGATE with base 2 and weight 1 along with
+/// FARGATE_SPOT with weight 3 runs the first two tasks on FARGATE, then one of every four
+/// further tasks on FARGATE and the rest on FARGATE_SPOT.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CapacityProviderStrategyItem {
+    /// The short name of the capacity provider.
+    capacityProvider: String,
+    /// The relative share of tasks to place on this capacity provider, between 0 and 1000.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    weight: Option<u32>,
+    /// The minimum number of tasks to run on this capacity provider, between 0 and 100000.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    base: Option<u32>,
+}
+
+/// Implements some convenience methods for building a ManagedScaling.
+impl ManagedScaling {
+    /// Creates a ManagedScaling which turns managed scaling on with the given target capacity
+    /// percentage.
+    pub fn enabled(target_capacity: u8) -> Result<Self, ValidationError> {
+        if target_capacity < 1 || target_capacity > 100 {
+            return Err(ValidationError::new("targetCapacity",
+                                            format!("must be between 1 and 100, got {}", target_capacity)));
+        }
+        Ok(ManagedScaling {
+            status: Some(ManagedScalingStatus::Enabled),
+            targetCapacity: Some(target_capacity),
+            minimumScalingStepSize: None,
+            maximumScalingStepSize: None,
+            instanceWarmupPeriod: None,
+        })
+    }
+
+    /// Creates a ManagedScaling which turns managed scaling off.
+    pub fn disabled() -> Self {
+        ManagedScaling {
+            status: Some(ManagedScalingStatus::Disabled),
+            targetCapacity: None,
+            minimumScalingStepSize: None,
+            maximumScalingStepSize: None,
+            instanceWarmupPeriod: None,
+        }
+    }
+
+    /// Sets the minimum and maximum number of instances to scale by at a time.
+    pub fn set_scaling_step_sizes(&mut self, minimum: u16, maximum: u16) -> Result<(), ValidationError> {
+        if minimum < 1 || maximum > 10000 || minimum > maximum {
+            return Err(ValidationError::new("scalingStepSize",
+                                            format!("need 1 <= minimum <= maximum <= 10000, got {} and {}",
+                                                    minimum, maximum)));
+        }
+        self.minimumScalingStepSize = Some(minimum);
+        self.maximumScalingStepSize = Some(maximum);
+        Ok(())
+    }
+
+    /// Sets the seconds a newly launched instance takes to contribute to CloudWatch metrics.
+    pub fn set_instance_warmup_period(&mut self, seconds: u16) -> Result<(), ValidationError> {
+        if seconds > 10000 {
+            return Err(ValidationError::new("instanceWarmupPeriod",
+                                            format!("must be at most 10000, got {}", seconds)));
+        }
+        self.instanceWarmupPeriod = Some(seconds);
+        Ok(())
+    }
+
+    /// Gets whether managed scaling is turned on.
+    pub fn get_status(&self) -> &Option<ManagedScalingStatus> {
+        &self.status
+    }
+
+    /// Gets the target utilization percentage of the Auto Scaling group.
+    pub fn get_target_capacity(&self) -> &Option<u8> {
+        &self.targetCapacity
+    }
+}
+
+/// Implements some convenience methods for building an AutoScalingGroupProvider.
+impl AutoScalingGroupProvider {
+    /// Creates an AutoScalingGroupProvider for the Auto Scaling group with the given ARN.
+    pub fn new(auto_scaling_group_arn: String) -> Self {
+        AutoScalingGroupProvider {
+            autoScalingGroupArn: auto_scaling_group_arn,
+            managedScaling: None,
+            managedTerminationProtection: None,
+        }
+    }
+
+    /// Sets the managed scaling settings of the capacity provider.
+    pub fn set_managed_scaling(&mut self, managed_scaling: ManagedScaling) {
+        self.managedScaling = Some(managed_scaling);
+    }
+
+    /// Sets whether instances running tasks are protected from scale-in.
+    pub fn set_managed_termination_protection(&mut self, protection: ManagedTerminationProtection) {
+        self.managedTerminationProtection = Some(protection);
+    }
+
+    /// Gets the ARN of the Auto Scaling group.
+    pub fn get_auto_scaling_group_arn(&self) -> &String {
+        &self.autoScalingGroupArn
+    }
+
+    /// Gets the managed scaling settings of the capacity provider.
+    pub fn get_managed_scaling(&self) -> &Option<ManagedScaling> {
+        &self.managedScaling
+    }
+
+    /// Gets whether instances running tasks are protected from scale-in.
+    pub fn get_managed_termination_protection(&self) -> &Option<ManagedTerminationProtection> {
+        &self.managedTerminationProtection
+    }
+}
+
+/// Implements some convenience methods for building an AutoScalingGroupProviderUpdate.
+impl AutoScalingGroupProviderUpdate {
+    /// Creates an AutoScalingGroupProviderUpdate which changes nothing.
+    pub fn new() -> Self {
+        AutoScalingGroupProviderUpdate {
+            managedScaling: None,
+            managedTerminationProtection: None,
+        }
+    }
+
+    /// Sets the new managed scaling settings of the capacity provider.
+    pub fn set_managed_scaling(&mut self, managed_scaling: ManagedScaling) {
+        self.managedScaling = Some(managed_scaling);
+    }
+
+    /// Sets whether instances running tasks are protected from scale-in.
+    pub fn set_managed_termination_protection(&mut self, protection: ManagedTerminationProtection) {
+        self.managedTerminationProtection = Some(protection);
+    }
+}
+
+/// Implements some convenience methods for looking at the values in a CapacityProvider.
+impl CapacityProvider {
+    /// Gets the ARN of the capacity provider.
+    pub fn get_capacity_provider_arn(&self) -> &Option<String> {
+        &self.capacityProviderArn
+    }
+
+    /// Gets the name of the capacity provider.
+    pub fn get_name(&self) -> &Option<String> {
+        &self.name
+    }
+
+    /// Gets whether the capacity provider is usable.
+    pub fn get_status(&self) -> &Option<CapacityProviderStatus> {
+        &self.status
+    }
+
+    /// Gets the Auto Scaling group backing the capacity provider.
+    pub fn get_auto_scaling_group_provider(&self) -> &Option<AutoScalingGroupProvider> {
+        &self.autoScalingGroupProvider
+    }
+
+    /// Gets the progress of the latest update or deletion of the capacity provider.
+    pub fn get_update_status(&self) -> &Option<CapacityProviderUpdateStatus> {
+        &self.updateStatus
+    }
+
+    /// Gets the reason for the latest update status.
+    pub fn get_update_status_reason(&self) -> &Option<String> {
+        &self.updateStatusReason
+    }
+
+    /// Gets the tags applied to the capacity provider.
+    pub fn get_tags(&self) -> &Option<Vec<Tag>> {
+        &self.tags
+    }
+}
+
+/// Implements some convenience methods for building a CapacityProviderStrategyItem.
+impl CapacityProviderStrategyItem {
+    /// Creates a CapacityProviderStrategyItem with the given weight and base.
+    pub fn new(capacity_provider: String, weight: u32, base: u32) -> Result<Self, ValidationError> {
+        if weight > MAX_WEIGHT {
+            return Err(ValidationError::new("weight", format!("must be at most {}, got {}", MAX_WEIGHT, weight)));
+        }
+        if base > MAX_BASE {
+            return Err(ValidationError::new("base", format!("must be at most {}, got {}", MAX_BASE, base)));
+        }
+        Ok(CapacityProviderStrategyItem {
+            capacityProvider: capacity_provider,
+            weight: Some(weight),
+            base: Some(base),
+        })
+    }
+
+    /// Creates a CapacityProviderStrategyItem with the given weight and no base.
+    pub fn with_weight(capacity_provider: String, weight: u32) -> Result<Self, ValidationError> {
+        let mut item = try!(CapacityProviderStrategyItem::new(capacity_provider, weight, 0));
+        item.base = None;
+        Ok(item)
+    }
+
+    /// Gets the short name of the capacity provider.
+    pub fn get_capacity_provider(&self) -> &String {
+        &self.capacityProvider
+    }
+
+    /// Gets the relative share of tasks placed on the capacity provider.
+    pub fn get_weight(&self) -> u32 {
+        self.weight.unwrap_or(0)
+    }
+
+    /// Gets the minimum number of tasks run on the capacity provider.
+    pub fn get_base(&self) -> u32 {
+        self.base.unwrap_or(0)
+    }
+}
+
+/// Checks that a capacity provider strategy is one ECS will accept: at most one item may set a
+/// base, and no capacity provider may appear twice.
+pub fn validate_strategy(strategy: &[CapacityProviderStrategyItem]) -> Result<(), ValidationError> {
+    if strategy.iter().filter(|item| item.get_base() > 0).count() > 1 {
+        return Err(ValidationError::new("capacityProviderStrategy",
+                                        String::from("only one capacity provider can have a base defined")));
+    }
+    for (i, item) in strategy.iter().enumerate() {
+        if strategy[..i].iter().any(|other| other.capacityProvider == item.capacityProvider) {
+            return Err(ValidationError::new("capacityProviderStrategy",
+                                            format!("capacity provider {} appears more than once",
+                                                    item.capacityProvider)));
+        }
+    }
+    Ok(())
+}
+
+/// Checks the name of a new capacity provider: up to 255 letters, numbers, hyphens and
+/// underscores, not beginning with a prefix reserved by AWS.
+pub fn validate_name(name: &str) -> Result<(), ValidationError> {
+    if name.is_empty() || name.len() > MAX_NAME_LENGTH {
+        return Err(ValidationError::new("name",
+                                        format!("must be between 1 and {} characters, got {}", MAX_NAME_LENGTH, name.len())));
+    }
+    if let Some(c) = name.chars().find(|&c| !(((c as u32) < 128 && c.is_alphanumeric()) || c == '-' || c == '_')) {
+        return Err(ValidationError::new("name", format!("character '{}' is not allowed: {}", c, name)));
+    }
+    let lowercase = name.to_lowercase();
+    for prefix in &RESERVED_NAME_PREFIXES {
+        if lowercase.starts_with(prefix) {
+            return Err(ValidationError::new("name", format!("the {} prefix is reserved: {}", prefix, name)));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CapacityProviderStrategyItem, ManagedScaling, validate_strategy, validate_name, FARGATE, FARGATE_SPOT};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_serialize_strategy_item() {
+        let item = CapacityProviderStrategyItem::new(String::from(FARGATE), 1, 2).unwrap();
+        let expected: value::Value = serde_json::from_str(
+            "{\"capacityProvider\":\"FARGATE\",\"weight\":1,\"base\":2}").unwrap();
+        let actual: value::Value = value::to_value(&item);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_strategy_item_limits() {
+        assert!(CapacityProviderStrategyItem::new(String::from(FARGATE), 1001, 0).is_err());
+        assert!(CapacityProviderStrategyItem::new(String::from(FARGATE), 1000, 100001).is_err());
+    }
+
+    #[test]
+    fn test_validate_strategy() {
+        let fargate = CapacityProviderStrategyItem::new(String::from(FARGATE), 1, 2).unwrap();
+        let spot = CapacityProviderStrategyItem::with_weight(String::from(FARGATE_SPOT), 3).unwrap();
+        assert!(validate_strategy(&[fargate.clone(), spot]).is_ok());
+
+        let spot_with_base = CapacityProviderStrategyItem::new(String::from(FARGATE_SPOT), 3, 1).unwrap();
+        assert!(validate_strategy(&[fargate.clone(), spot_with_base]).is_err());
+        assert!(validate_strategy(&[fargate.clone(), fargate]).is_err());
+    }
+
+    #[test]
+    fn test_serialize_managed_scaling() {
+        let mut scaling = ManagedScaling::enabled(80).unwrap();
+        scaling.set_scaling_step_sizes(1, 10).unwrap();
+        let expected: value::Value = serde_json::from_str(
+            "{\"status\":\"ENABLED\",\"targetCapacity\":80,\"minimumScalingStepSize\":1,\
+              \"maximumScalingStepSize\":10}").unwrap();
+        let actual: value::Value = value::to_value(&scaling);
+        assert_eq!(expected, actual);
+        assert!(ManagedScaling::enabled(0).is_err());
+    }
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("my-asg_provider1").is_ok());
+        assert!(validate_name("FargateCustom").is_err());
+        assert!(validate_name("has space").is_err());
+    }
+}
@@ -0,0 +1,352 @@
+//! Defines the Cluster data type, a logical grouping of the tasks and services run on ECS, along
+//! with the settings and configuration which can be changed on an existing cluster.
+
+use custom_ser;
+use error::ValidationError;
+use types::capacity_provider::CapacityProviderStrategyItem;
+use types::tag::Tag;
+
+string_enum! {
+    /// The name of a cluster setting.
+    pub enum ClusterSettingName {
+        ContainerInsights => "containerInsights",
+    }
+}
+
+string_enum! {
+    /// How the output of commands run with ExecuteCommand is logged.
+    pub enum ExecuteCommandLogging {
+        /// Command output is not logged.
+        None => "NONE",
+        /// Command output is logged using the awslogs configuration of the task definition.
+        Default => "DEFAULT",
+        /// Command output is logged to the targets in the cluster's log configuration.
+        Override => "OVERRIDE",
+    }
+}
+
+/// A setting applied to a cluster, such as whether Container Insights is turned on.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ClusterSetting {
+    /// The name of the setting.
+    name: ClusterSettingName,
+    /// The value of the setting, e.g. enabled, disabled or enhanced for containerInsights.
+    value: String,
+}
+
+/// The configuration of a cluster.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ClusterConfiguration {
+    /// The details of the execute command configuration.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    executeCommandConfiguration: Option<ExecuteCommandConfiguration>,
+}
+
+/// How commands run with ExecuteCommand in a cluster are encrypted and logged.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExecuteCommandConfiguration {
+    /// The ID of the KMS key used to encrypt the data between the client and the container.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    kmsKeyId: Option<String>,
+    /// How the output of commands is logged.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    logging: Option<ExecuteCommandLogging>,
+    /// Where command output is logged.  Only used when logging is OVERRIDE.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    logConfiguration: Option<ExecuteCommandLogConfiguration>,
+}
+
+/// The CloudWatch Logs and S3 targets command output is logged to.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExecuteCommandLogConfiguration {
+    /// The name of the CloudWatch Logs log group to send logs to.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    cloudWatchLogGroupName: Option<String>,
+    /// Whether to use encryption on the CloudWatch logs.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    cloudWatchEncryptionEnabled: Option<bool>,
+    /// The name of the S3 bucket to send logs to.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    s3BucketName: Option<String>,
+    /// Whether to use encryption on the S3 logs.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    s3EncryptionEnabled: Option<bool>,
+    /// An optional folder in the S3 bucket to place logs in.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    s3KeyPrefix: Option<String>,
+}
+
+/// A compute cluster on ECS.
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct Cluster {
+    /// The Amazon Resource Name (ARN) of the cluster.
+    clusterArn: Option<String>,
+    /// The name of the cluster.
+    clusterName: Option<String>,
+    /// The status of the cluster, e.g. ACTIVE or INACTIVE.
+    status: Option<String>,
+    /// The number of container instances registered with the cluster.
+    registeredContainerInstancesCount: Option<i32>,
+    /// The number of tasks in the cluster in the RUNNING state.
+    runningTasksCount: Option<i32>,
+    /// The number of tasks in the cluster in the PENDING state.
+    pendingTasksCount: Option<i32>,
+    /// The number of services running on the cluster in an ACTIVE state.
+    activeServicesCount: Option<i32>,
+    /// The names of the capacity providers associated with the cluster.
+    capacityProviders: Option<Vec<String>>,
+    /// The capacity provider strategy used when no other strategy or launch type is specified.
+    defaultCapacityProviderStrategy: Option<Vec<CapacityProviderStrategyItem>>,
+    /// The tags applied to the cluster.
+    tags: Option<Vec<Tag>>,
+    /// The settings of the cluster.
+    settings: Option<Vec<ClusterSetting>>,
+    /// The configuration of the cluster.
+    configuration: Option<ClusterConfiguration>,
+}
+
+/// Implements some convenience methods for looking at the values in a Cluster.
+impl Cluster {
+    /// Gets the ARN of the cluster.
+    pub fn get_cluster_arn(&self) -> &Option<String> {
+        &self.clusterArn
+    }
+
+    /// Gets the name of the cluster.
+    pub fn get_cluster_name(&self) -> &Option<String> {
+        &self.clusterName
+    }
+
+    /// Gets the status of the cluster.
+    pub fn get_status(&self) -> &Option<String> {
+        &self.status
+    }
+
+    /// Gets the number of container instances registered with the cluster.
+    pub fn get_registered_container_instances_count(&self) -> &Option<i32> {
+        &self.registeredContainerInstancesCount
+    }
+
+    /// Gets the number of RUNNING tasks in the cluster.
+    pub fn get_running_tasks_count(&self) -> &Option<i32> {
+        &self.runningTasksCount
+    }
+
+    /// Gets the number of PENDING tasks in the cluster.
+    pub fn get_pending_tasks_count(&self) -> &Option<i32> {
+        &self.pendingTasksCount
+    }
+
+    /// Gets the number of ACTIVE services in the cluster.
+    pub fn get_active_services_count(&self) -> &Option<i32> {
+        &self.activeServicesCount
+    }
+
+    /// Gets the names of the capacity providers associated with the cluster.
+    pub fn get_capacity_providers(&self) -> &Option<Vec<String>> {
+        &self.capacityProviders
+    }
+
+    /// Gets the default capacity provider strategy of the cluster.
+    pub fn get_default_capacity_provider_strategy(&self) -> &Option<Vec<CapacityProviderStrategyItem>> {
+        &self.defaultCapacityProviderStrategy
+    }
+
+    /// Gets the tags applied to the cluster.
+    pub fn get_tags(&self) -> &Option<Vec<Tag>> {
+        &self.tags
+    }
+
+    /// Gets the settings of the cluster.
+    pub fn get_settings(&self) -> &Option<Vec<ClusterSetting>> {
+        &self.settings
+    }
+
+    /// Gets the configuration of the cluster.
+    pub fn get_configuration(&self) -> &Option<ClusterConfiguration> {
+        &self.configuration
+    }
+}
+
+/// Implements some convenience methods for building a ClusterSetting.
+impl ClusterSetting {
+    /// Creates a ClusterSetting.
+    pub fn new(name: ClusterSettingName, value: String) -> Self {
+        ClusterSetting {
+            name: name,
+            value: value,
+        }
+    }
+
+    /// Creates a ClusterSetting turning Container Insights on or off.
+    pub fn container_insights(enabled: bool) -> Self {
+        let value = if enabled { "enabled" } else { "disabled" };
+        ClusterSetting::new(ClusterSettingName::ContainerInsights, String::from(value))
+    }
+
+    /// Gets the name of the setting.
+    pub fn get_name(&self) -> ClusterSettingName {
+        self.name
+    }
+
+    /// Gets the value of the setting.
+    pub fn get_value(&self) -> &String {
+        &self.value
+    }
+}
+
+/// Implements some convenience methods for building a ClusterConfiguration.
+impl ClusterConfiguration {
+    /// Creates a ClusterConfiguration with the given execute command configuration.
+    pub fn with_execute_command_configuration(configuration: ExecuteCommandConfiguration) -> Self {
+        ClusterConfiguration {
+            executeCommandConfiguration: Some(configuration),
+        }
+    }
+
+    /// Gets the execute command configuration.
+    pub fn get_execute_command_configuration(&self) -> &Option<ExecuteCommandConfiguration> {
+        &self.executeCommandConfiguration
+    }
+}
+
+/// Implements some convenience methods for building an ExecuteCommandConfiguration.
+impl ExecuteCommandConfiguration {
+    /// Creates an ExecuteCommandConfiguration which logs command output with the given mode.
+    /// OVERRIDE needs somewhere to send logs to, so use with_log_configuration() for it instead.
+    pub fn new(logging: ExecuteCommandLogging) -> Result<Self, ValidationError> {
+        if logging == ExecuteCommandLogging::Override {
+            return Err(ValidationError::new("logging",
+                                            String::from("OVERRIDE logging requires a log configuration")));
+        }
+        Ok(ExecuteCommandConfiguration {
+            kmsKeyId: None,
+            logging: Some(logging),
+            logConfiguration: None,
+        })
+    }
+
+    /// Creates an ExecuteCommandConfiguration which logs command output to the given targets
+    /// using OVERRIDE logging.
+    pub fn with_log_configuration(log_configuration: ExecuteCommandLogConfiguration) -> Self {
+        ExecuteCommandConfiguration {
+            kmsKeyId: None,
+            logging: Some(ExecuteCommandLogging::Override),
+            logConfiguration: Some(log_configuration),
+        }
+    }
+
+    /// Sets the ID of the KMS key used to encrypt the data between the client and the container.
+    pub fn set_kms_key_id(&mut self, kms_key_id: String) {
+        self.kmsKeyId = Some(kms_key_id);
+    }
+
+    /// Gets the ID of the KMS key used to encrypt the session.
+    pub fn get_kms_key_id(&self) -> &Option<String> {
+        &self.kmsKeyId
+    }
+
+    /// Gets how the output of commands is logged.
+    pub fn get_logging(&self) -> &Option<ExecuteCommandLogging> {
+        &self.logging
+    }
+
+    /// Gets where command output is logged.
+    pub fn get_log_configuration(&self) -> &Option<ExecuteCommandLogConfiguration> {
+        &self.logConfiguration
+    }
+}
+
+/// Implements some convenience methods for building an ExecuteCommandLogConfiguration.
+impl ExecuteCommandLogConfiguration {
+    /// Creates an ExecuteCommandLogConfiguration with no targets.
+    pub fn new() -> Self {
+        ExecuteCommandLogConfiguration {
+            cloudWatchLogGroupName: None,
+            cloudWatchEncryptionEnabled: None,
+            s3BucketName: None,
+            s3EncryptionEnabled: None,
+            s3KeyPrefix: None,
+        }
+    }
+
+    /// Sends command output to the given CloudWatch Logs log group.
+    pub fn set_cloud_watch(&mut self, log_group_name: String, encryption_enabled: bool) {
+        self.cloudWatchLogGroupName = Some(log_group_name);
+        self.cloudWatchEncryptionEnabled = Some(encryption_enabled);
+    }
+
+    /// Sends command output to the given S3 bucket, optionally under a key prefix.
+    pub fn set_s3(&mut self, bucket_name: String, key_prefix: Option<String>, encryption_enabled: bool) {
+        self.s3BucketName = Some(bucket_name);
+        self.s3KeyPrefix = key_prefix;
+        self.s3EncryptionEnabled = Some(encryption_enabled);
+    }
+
+    /// Gets the name of the CloudWatch Logs log group.
+    pub fn get_cloud_watch_log_group_name(&self) -> &Option<String> {
+        &self.cloudWatchLogGroupName
+    }
+
+    /// Gets whether the CloudWatch logs are encrypted.
+    pub fn get_cloud_watch_encryption_enabled(&self) -> &Option<bool> {
+        &self.cloudWatchEncryptionEnabled
+    }
+
+    /// Gets the name of the S3 bucket.
+    pub fn get_s3_bucket_name(&self) -> &Option<String> {
+        &self.s3BucketName
+    }
+
+    /// Gets whether the S3 logs are encrypted.
+    pub fn get_s3_encryption_enabled(&self) -> &Option<bool> {
+        &self.s3EncryptionEnabled
+    }
+
+    /// Gets the folder in the S3 bucket logs are placed in.
+    pub fn get_s3_key_prefix(&self) -> &Option<String> {
+        &self.s3KeyPrefix
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Cluster, ExecuteCommandConfiguration, ExecuteCommandLogConfiguration, ExecuteCommandLogging};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_serialize_execute_command_configuration() {
+        let mut log_configuration = ExecuteCommandLogConfiguration::new();
+        log_configuration.set_cloud_watch(String::from("/ecs/exec"), true);
+        let mut configuration = ExecuteCommandConfiguration::with_log_configuration(log_configuration);
+        configuration.set_kms_key_id(String::from("key-id"));
+        let expected: value::Value = serde_json::from_str(
+            "{\"kmsKeyId\":\"key-id\",\"logging\":\"OVERRIDE\",\"logConfiguration\":\
+              {\"cloudWatchLogGroupName\":\"/ecs/exec\",\"cloudWatchEncryptionEnabled\":true}}").unwrap();
+        let actual: value::Value = value::to_value(&configuration);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_override_requires_log_configuration() {
+        assert!(ExecuteCommandConfiguration::new(ExecuteCommandLogging::Override).is_err());
+        assert!(ExecuteCommandConfiguration::new(ExecuteCommandLogging::Default).is_ok());
+    }
+
+    #[test]
+    fn test_deserialize_cluster() {
+        let cluster: Cluster = serde_json::from_str(
+            "{\"clusterName\":\"default\",\"status\":\"ACTIVE\",\
+              \"settings\":[{\"name\":\"containerInsights\",\"value\":\"enabled\"}],\
+              \"configuration\":{\"executeCommandConfiguration\":{\"logging\":\"DEFAULT\"}}}").unwrap();
+        assert_eq!("enabled", cluster.get_settings().as_ref().unwrap()[0].get_value());
+        let configuration = cluster.get_configuration().as_ref().unwrap();
+        assert_eq!(&Some(ExecuteCommandLogging::Default),
+                   configuration.get_execute_command_configuration().as_ref().unwrap().get_logging());
+    }
+}
@@ -0,0 +1,367 @@
+//! Defines the ContainerInstance data type along with the Resource and VersionInfo types which
+//! describe what a container instance brings to a cluster.
+
+use custom_ser;
+use std::cmp::{self, Ordering};
+use types::attribute::Attribute;
+use types::tag::Tag;
+
+string_enum! {
+    /// The type of value held by a Resource.
+    pub enum ResourceType {
+        Integer => "INTEGER",
+        Double => "DOUBLE",
+        Long => "LONG",
+        StringSet => "STRINGSET",
+    }
+}
+
+string_enum! {
+    /// The progress of a container agent update started by UpdateContainerAgent.
+    pub enum AgentUpdateStatus {
+        Pending => "PENDING",
+        Staging => "STAGING",
+        Staged => "STAGED",
+        Updating => "UPDATING",
+        Updated => "UPDATED",
+        Failed => "FAILED",
+    }
+}
+
+/// Describes a resource (such as CPU, MEMORY, PORTS or PORTS_UDP) that a container instance
+/// registers with a cluster or that remains available on it.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Resource {
+    /// The name of the resource, such as CPU, MEMORY, PORTS, PORTS_UDP, or a custom name.
+    name: String,
+    /// The type of the resource, which determines which of the value fields below is set.
+    #[serde(rename="type")]
+    resourceType: ResourceType,
+    /// The value of a DOUBLE resource.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    doubleValue: Option<f64>,
+    /// The value of a LONG resource.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    longValue: Option<i64>,
+    /// The value of an INTEGER resource.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    integerValue: Option<i32>,
+    /// The value of a STRINGSET resource, such as the list of reserved ports.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    stringSetValue: Option<Vec<String>>,
+}
+
+/// Describes the versions of the container agent and Docker running on a container instance.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct VersionInfo {
+    /// The version number of the container agent.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    agentVersion: Option<String>,
+    /// The Git commit hash of the container agent build.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    agentHash: Option<String>,
+    /// The Docker version running on the container instance.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    dockerVersion: Option<String>,
+}
+
+/// An EC2 instance running the container agent which has been registered with a cluster.
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct ContainerInstance {
+    /// The Amazon Resource Name (ARN) of the container instance.
+    containerInstanceArn: Option<String>,
+    /// The EC2 instance ID of the container instance.
+    ec2InstanceId: Option<String>,
+    /// The version counter for the container instance, incremented on every change.
+    version: Option<i64>,
+    /// The versions of the container agent and Docker running on the container instance.
+    versionInfo: Option<VersionInfo>,
+    /// The resources remaining on the container instance for new tasks.
+    remainingResources: Option<Vec<Resource>>,
+    /// The resources registered by the container instance when it joined the cluster.
+    registeredResources: Option<Vec<Resource>>,
+    /// The status of the container instance, e.g. ACTIVE, DRAINING or INACTIVE.
+    status: Option<String>,
+    /// Whether the container agent is connected to ECS.
+    agentConnected: Option<bool>,
+    /// The number of tasks on the container instance in the RUNNING state.
+    runningTasksCount: Option<i32>,
+    /// The number of tasks on the container instance in the PENDING state.
+    pendingTasksCount: Option<i32>,
+    /// The time the container instance was registered, in seconds since the epoch.
+    registeredAt: Option<f64>,
+    /// The progress of the most recent container agent update, if one was ever started.
+    agentUpdateStatus: Option<AgentUpdateStatus>,
+    /// The attributes of the container instance, both those set by the agent and custom ones.
+    attributes: Option<Vec<Attribute>>,
+    /// The tags applied to the container instance.
+    tags: Option<Vec<Tag>>,
+}
+
+/// Implements some convenience methods for building a Resource.
+impl Resource {
+    /// Creates an INTEGER Resource, such as CPU or MEMORY.
+    pub fn integer(name: String, value: i32) -> Self {
+        let mut resource = Resource::empty(name, ResourceType::Integer);
+        resource.integerValue = Some(value);
+        resource
+    }
+
+    /// Creates a DOUBLE Resource.
+    pub fn double(name: String, value: f64) -> Self {
+        let mut resource = Resource::empty(name, ResourceType::Double);
+        resource.doubleValue = Some(value);
+        resource
+    }
+
+    /// Creates a LONG Resource.
+    pub fn long(name: String, value: i64) -> Self {
+        let mut resource = Resource::empty(name, ResourceType::Long);
+        resource.longValue = Some(value);
+        resource
+    }
+
+    /// Creates a STRINGSET Resource, such as PORTS or PORTS_UDP.
+    pub fn string_set(name: String, values: Vec<String>) -> Self {
+        let mut resource = Resource::empty(name, ResourceType::StringSet);
+        resource.stringSetValue = Some(values);
+        resource
+    }
+
+    fn empty(name: String, resource_type: ResourceType) -> Self {
+        Resource {
+            name: name,
+            resourceType: resource_type,
+            doubleValue: None,
+            longValue: None,
+            integerValue: None,
+            stringSetValue: None,
+        }
+    }
+
+    /// Gets the name of the resource.
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    /// Gets the type of the resource.
+    pub fn get_type(&self) -> ResourceType {
+        self.resourceType
+    }
+
+    /// Gets the value of a DOUBLE resource.
+    pub fn get_double_value(&self) -> &Option<f64> {
+        &self.doubleValue
+    }
+
+    /// Gets the value of a LONG resource.
+    pub fn get_long_value(&self) -> &Option<i64> {
+        &self.longValue
+    }
+
+    /// Gets the value of an INTEGER resource.
+    pub fn get_integer_value(&self) -> &Option<i32> {
+        &self.integerValue
+    }
+
+    /// Gets the value of a STRINGSET resource.
+    pub fn get_string_set_value(&self) -> &Option<Vec<String>> {
+        &self.stringSetValue
+    }
+}
+
+/// Implements some convenience methods for building and reading a VersionInfo.
+impl VersionInfo {
+    /// Creates a VersionInfo with all fields set.
+    pub fn new(agent_version: String, agent_hash: String, docker_version: String) -> Self {
+        VersionInfo {
+            agentVersion: Some(agent_version),
+            agentHash: Some(agent_hash),
+            dockerVersion: Some(docker_version),
+        }
+    }
+
+    /// Gets the version number of the container agent.
+    pub fn get_agent_version(&self) -> &Option<String> {
+        &self.agentVersion
+    }
+
+    /// Gets the Git commit hash of the container agent build.
+    pub fn get_agent_hash(&self) -> &Option<String> {
+        &self.agentHash
+    }
+
+    /// Gets the Docker version running on the container instance.
+    pub fn get_docker_version(&self) -> &Option<String> {
+        &self.dockerVersion
+    }
+}
+
+/// Implements some convenience methods for looking at the values in a ContainerInstance.
+impl ContainerInstance {
+    /// Gets the ARN of the container instance.
+    pub fn get_container_instance_arn(&self) -> &Option<String> {
+        &self.containerInstanceArn
+    }
+
+    /// Gets the EC2 instance ID of the container instance.
+    pub fn get_ec2_instance_id(&self) -> &Option<String> {
+        &self.ec2InstanceId
+    }
+
+    /// Gets the version counter of the container instance.
+    pub fn get_version(&self) -> &Option<i64> {
+        &self.version
+    }
+
+    /// Gets the container agent and Docker versions of the container instance.
+    pub fn get_version_info(&self) -> &Option<VersionInfo> {
+        &self.versionInfo
+    }
+
+    /// Gets the resources remaining on the container instance.
+    pub fn get_remaining_resources(&self) -> &Option<Vec<Resource>> {
+        &self.remainingResources
+    }
+
+    /// Gets the resources registered by the container instance.
+    pub fn get_registered_resources(&self) -> &Option<Vec<Resource>> {
+        &self.registeredResources
+    }
+
+    /// Gets the status of the container instance.
+    pub fn get_status(&self) -> &Option<String> {
+        &self.status
+    }
+
+    /// Gets whether the container agent is connected to ECS.
+    pub fn get_agent_connected(&self) -> &Option<bool> {
+        &self.agentConnected
+    }
+
+    /// Gets the number of RUNNING tasks on the container instance.
+    pub fn get_running_tasks_count(&self) -> &Option<i32> {
+        &self.runningTasksCount
+    }
+
+    /// Gets the number of PENDING tasks on the container instance.
+    pub fn get_pending_tasks_count(&self) -> &Option<i32> {
+        &self.pendingTasksCount
+    }
+
+    /// Gets the registration time of the container instance, in seconds since the epoch.
+    pub fn get_registered_at(&self) -> &Option<f64> {
+        &self.registeredAt
+    }
+
+    /// Gets the progress of the most recent container agent update.
+    pub fn get_agent_update_status(&self) -> &Option<AgentUpdateStatus> {
+        &self.agentUpdateStatus
+    }
+
+    /// Returns true if the container agent on the instance is older than the given version, or
+    /// if the instance does not report its agent version at all.
+    pub fn is_agent_older_than(&self, version: &str) -> bool {
+        match self.versionInfo.as_ref().and_then(|info| info.agentVersion.as_ref()) {
+            Some(agent_version) => compare_versions(agent_version, version) == Ordering::Less,
+            None => true,
+        }
+    }
+
+    /// Gets the attributes of the container instance.
+    pub fn get_attributes(&self) -> &Option<Vec<Attribute>> {
+        &self.attributes
+    }
+
+    /// Gets the tags applied to the container instance.
+    pub fn get_tags(&self) -> &Option<Vec<Tag>> {
+        &self.tags
+    }
+}
+
+/// Compares two dotted version strings, such as "1.9.0" and "1.10.1", numerically component by
+/// component.  A leading 'v' and any non-numeric suffix of a component (as in "1.2.3-beta") are
+/// ignored, and missing components count as zero.
+pub fn compare_versions(left: &str, right: &str) -> Ordering {
+    let left = parse_version(left);
+    let right = parse_version(right);
+    for i in 0..cmp::max(left.len(), right.len()) {
+        let ordering = left.get(i).unwrap_or(&0).cmp(right.get(i).unwrap_or(&0));
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+fn parse_version(version: &str) -> Vec<u64> {
+    version.trim_left_matches('v').split('.').map(|component| {
+        let digits: String = component.chars().take_while(|c| c.is_digit(10)).collect();
+        digits.parse().unwrap_or(0)
+    }).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Resource, ContainerInstance, AgentUpdateStatus, compare_versions};
+    use std::cmp::Ordering;
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_integer_resource() {
+        let resource = Resource::integer(String::from("CPU"), 1024);
+        let expected: value::Value = serde_json::from_str(
+            "{\"name\":\"CPU\",\"type\":\"INTEGER\",\"integerValue\":1024}").unwrap();
+        let actual: value::Value = value::to_value(&resource);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_string_set_resource() {
+        let resource = Resource::string_set(String::from("PORTS"),
+                                            vec![String::from("22"), String::from("2376")]);
+        let expected: value::Value = serde_json::from_str(
+            "{\"name\":\"PORTS\",\"type\":\"STRINGSET\",\"stringSetValue\":[\"22\",\"2376\"]}").unwrap();
+        let actual: value::Value = value::to_value(&resource);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_deserialize_container_instance() {
+        let blob = "{\"containerInstanceArn\":\"arn:aws:ecs:us-east-1:012345678910:container-instance/abc\",\
+                    \"agentConnected\":true,\"runningTasksCount\":2,\
+                    \"versionInfo\":{\"agentVersion\":\"1.9.0\",\"dockerVersion\":\"DockerVersion: 1.9.1\"},\
+                    \"registeredResources\":[{\"name\":\"MEMORY\",\"type\":\"INTEGER\",\"integerValue\":995}]}";
+        let instance: ContainerInstance = serde_json::from_str(blob).unwrap();
+        assert_eq!(&Some(true), instance.get_agent_connected());
+        assert_eq!(&Some(2), instance.get_running_tasks_count());
+        let version_info = instance.get_version_info().as_ref().unwrap();
+        assert_eq!(&Some(String::from("1.9.0")), version_info.get_agent_version());
+        let resources = instance.get_registered_resources().as_ref().unwrap();
+        assert_eq!(&Some(995), resources[0].get_integer_value());
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(Ordering::Less, compare_versions("1.9.0", "1.10.0"));
+        assert_eq!(Ordering::Equal, compare_versions("v1.2", "1.2.0"));
+        assert_eq!(Ordering::Equal, compare_versions("1.2.3", "1.2.3-beta"));
+        assert_eq!(Ordering::Greater, compare_versions("2.0.0", "1.99.99"));
+    }
+
+    #[test]
+    fn test_is_agent_older_than() {
+        let instance: ContainerInstance = serde_json::from_str(
+            "{\"versionInfo\":{\"agentVersion\":\"1.9.0\"},\"agentUpdateStatus\":\"UPDATING\"}").unwrap();
+        assert!(instance.is_agent_older_than("1.10.0"));
+        assert!(!instance.is_agent_older_than("1.9.0"));
+        assert_eq!(&Some(AgentUpdateStatus::Updating), instance.get_agent_update_status());
+
+        let unknown: ContainerInstance = serde_json::from_str("{}").unwrap();
+        assert!(unknown.is_agent_older_than("1.0.0"));
+    }
+}
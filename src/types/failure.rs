@@ -0,0 +1,31 @@
+//! Defines the Failure data type which ECS returns alongside the results of Describe* actions
+//! for each requested resource it could not describe.
+
+/// A resource which could not be described, along with the reason why.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct Failure {
+    /// The Amazon Resource Name (ARN) or name of the failed resource.
+    arn: Option<String>,
+    /// The reason for the failure, e.g. MISSING.
+    reason: Option<String>,
+    /// The details of the failure.
+    detail: Option<String>,
+}
+
+/// Implements some convenience methods for looking at the values in a Failure.
+impl Failure {
+    /// Gets the ARN or name of the failed resource.
+    pub fn get_arn(&self) -> &Option<String> {
+        &self.arn
+    }
+
+    /// Gets the reason for the failure.
+    pub fn get_reason(&self) -> &Option<String> {
+        &self.reason
+    }
+
+    /// Gets the details of the failure.
+    pub fn get_detail(&self) -> &Option<String> {
+        &self.detail
+    }
+}
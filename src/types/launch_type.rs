@@ -0,0 +1,10 @@
+//! Defines the LaunchType data type, which selects the infrastructure a task runs on.
+
+string_enum! {
+    /// The infrastructure a task or task set runs on.
+    pub enum LaunchType {
+        EC2 => "EC2",
+        Fargate => "FARGATE",
+        External => "EXTERNAL",
+    }
+}
@@ -0,0 +1,19 @@
+//! This module defines the ECS data types which are nested inside the request and response types
+//! of the various actions supported by ECS.  See
+//! http://docs.aws.amazon.com/AmazonECS/latest/APIReference/API_Types.html for the full list.
+
+pub mod container_instance;
+pub mod state_change;
+pub mod tag;
+pub mod attribute;
+pub mod failure;
+pub mod capacity_provider;
+pub mod cluster;
+pub mod setting;
+pub mod launch_type;
+pub mod networking;
+pub mod task_set;
+pub mod session;
+pub mod service;
+pub mod task;
+pub mod task_definition;
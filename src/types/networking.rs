@@ -0,0 +1,123 @@
+//! Defines the data types which describe how the tasks of a service or task set are networked
+//! and exposed through load balancers.
+
+use custom_ser;
+
+string_enum! {
+    /// Whether the elastic network interface of an awsvpc task receives a public IP address.
+    pub enum AssignPublicIp {
+        Enabled => "ENABLED",
+        Disabled => "DISABLED",
+    }
+}
+
+/// The network configuration of tasks using the awsvpc network mode.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct NetworkConfiguration {
+    /// The subnets and security groups of the task's elastic network interface.
+    awsvpcConfiguration: AwsVpcConfiguration,
+}
+
+/// The subnets and security groups of an awsvpc task's elastic network interface.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AwsVpcConfiguration {
+    /// The IDs of up to 16 subnets to place the task in.
+    subnets: Vec<String>,
+    /// The IDs of up to 5 security groups to apply to the task.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    securityGroups: Option<Vec<String>>,
+    /// Whether the task receives a public IP address.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    assignPublicIp: Option<AssignPublicIp>,
+}
+
+/// A load balancer target group which the containers of a service or task set are registered
+/// with.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LoadBalancer {
+    /// The ARN of the Elastic Load Balancing target group.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    targetGroupArn: Option<String>,
+    /// The name of a Classic Load Balancer.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    loadBalancerName: Option<String>,
+    /// The name of the container to register with the load balancer.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    containerName: Option<String>,
+    /// The port on the container to register with the load balancer.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    containerPort: Option<u16>,
+}
+
+/// Implements some convenience methods for building a NetworkConfiguration.
+impl NetworkConfiguration {
+    /// Creates a NetworkConfiguration placing tasks in the given subnets and security groups.
+    pub fn awsvpc(subnets: Vec<String>, security_groups: Vec<String>, assign_public_ip: AssignPublicIp) -> Self {
+        NetworkConfiguration {
+            awsvpcConfiguration: AwsVpcConfiguration {
+                subnets: subnets,
+                securityGroups: Some(security_groups),
+                assignPublicIp: Some(assign_public_ip),
+            },
+        }
+    }
+
+    /// Gets the subnets and security groups of the task's elastic network interface.
+    pub fn get_awsvpc_configuration(&self) -> &AwsVpcConfiguration {
+        &self.awsvpcConfiguration
+    }
+}
+
+/// Implements some convenience methods for looking at the values in an AwsVpcConfiguration.
+impl AwsVpcConfiguration {
+    /// Gets the IDs of the subnets the task is placed in.
+    pub fn get_subnets(&self) -> &Vec<String> {
+        &self.subnets
+    }
+
+    /// Gets the IDs of the security groups applied to the task.
+    pub fn get_security_groups(&self) -> &Option<Vec<String>> {
+        &self.securityGroups
+    }
+
+    /// Gets whether the task receives a public IP address.
+    pub fn get_assign_public_ip(&self) -> &Option<AssignPublicIp> {
+        &self.assignPublicIp
+    }
+}
+
+/// Implements some convenience methods for building a LoadBalancer.
+impl LoadBalancer {
+    /// Creates a LoadBalancer registering the given container port with a target group.
+    pub fn target_group(target_group_arn: String, container_name: String, container_port: u16) -> Self {
+        LoadBalancer {
+            targetGroupArn: Some(target_group_arn),
+            loadBalancerName: None,
+            containerName: Some(container_name),
+            containerPort: Some(container_port),
+        }
+    }
+
+    /// Gets the ARN of the target group.
+    pub fn get_target_group_arn(&self) -> &Option<String> {
+        &self.targetGroupArn
+    }
+
+    /// Gets the name of the Classic Load Balancer.
+    pub fn get_load_balancer_name(&self) -> &Option<String> {
+        &self.loadBalancerName
+    }
+
+    /// Gets the name of the container registered with the load balancer.
+    pub fn get_container_name(&self) -> &Option<String> {
+        &self.containerName
+    }
+
+    /// Gets the port on the container registered with the load balancer.
+    pub fn get_container_port(&self) -> &Option<u16> {
+        &self.containerPort
+    }
+}
@@ -0,0 +1,228 @@
+//! Defines the Service data type along with the Deployment type which tracks each rollout of a
+//! service's task definition.
+
+use types::launch_type::LaunchType;
+use types::networking::{NetworkConfiguration, LoadBalancer};
+use types::task_set::TaskSet;
+use types::tag::Tag;
+
+/// A long running group of tasks which ECS keeps at a desired count.
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct Service {
+    /// The Amazon Resource Name (ARN) of the service.
+    serviceArn: Option<String>,
+    /// The name of the service.
+    serviceName: Option<String>,
+    /// The ARN of the cluster hosting the service.
+    clusterArn: Option<String>,
+    /// The status of the service, e.g. ACTIVE, DRAINING or INACTIVE.
+    status: Option<String>,
+    /// The number of tasks the service should keep running.
+    desiredCount: Option<i32>,
+    /// The number of tasks of the service in the RUNNING state.
+    runningCount: Option<i32>,
+    /// The number of tasks of the service in the PENDING state.
+    pendingCount: Option<i32>,
+    /// The launch type the tasks of the service run with.
+    launchType: Option<LaunchType>,
+    /// The task definition the service runs.
+    taskDefinition: Option<String>,
+    /// The current deployments of the service.  A service with a single deployment is not in the
+    /// middle of a rollout.
+    #[serde(default)]
+    deployments: Vec<Deployment>,
+    /// The task sets of a service using an EXTERNAL or CODE_DEPLOY deployment controller.
+    #[serde(default)]
+    taskSets: Vec<TaskSet>,
+    /// The network configuration of the tasks of the service.
+    networkConfiguration: Option<NetworkConfiguration>,
+    /// The load balancers the tasks of the service are registered with.
+    loadBalancers: Option<Vec<LoadBalancer>>,
+    /// The time the service was created, in seconds since the epoch.
+    createdAt: Option<f64>,
+    /// The tags applied to the service.
+    tags: Option<Vec<Tag>>,
+}
+
+/// A rollout of a particular task definition to a service.
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct Deployment {
+    /// The ID of the deployment.
+    id: Option<String>,
+    /// The status of the deployment, e.g. PRIMARY, ACTIVE or INACTIVE.
+    status: Option<String>,
+    /// The task definition the deployment rolls out.
+    taskDefinition: Option<String>,
+    /// The number of tasks the deployment should run.
+    desiredCount: Option<i32>,
+    /// The number of tasks of the deployment in the PENDING state.
+    pendingCount: Option<i32>,
+    /// The number of tasks of the deployment in the RUNNING state.
+    runningCount: Option<i32>,
+    /// The progress of the rollout, e.g. IN_PROGRESS, COMPLETED or FAILED.
+    rolloutState: Option<String>,
+    /// The time the deployment was created, in seconds since the epoch.
+    createdAt: Option<f64>,
+    /// The time the deployment was last updated, in seconds since the epoch.
+    updatedAt: Option<f64>,
+}
+
+/// Implements some convenience methods for looking at the values in a Service.
+impl Service {
+    /// Gets the ARN of the service.
+    pub fn get_service_arn(&self) -> &Option<String> {
+        &self.serviceArn
+    }
+
+    /// Gets the name of the service.
+    pub fn get_service_name(&self) -> &Option<String> {
+        &self.serviceName
+    }
+
+    /// Gets the ARN of the cluster hosting the service.
+    pub fn get_cluster_arn(&self) -> &Option<String> {
+        &self.clusterArn
+    }
+
+    /// Gets the status of the service.
+    pub fn get_status(&self) -> &Option<String> {
+        &self.status
+    }
+
+    /// Gets the number of tasks the service should keep running.
+    pub fn get_desired_count(&self) -> &Option<i32> {
+        &self.desiredCount
+    }
+
+    /// Gets the number of tasks of the service in the RUNNING state.
+    pub fn get_running_count(&self) -> &Option<i32> {
+        &self.runningCount
+    }
+
+    /// Gets the number of tasks of the service in the PENDING state.
+    pub fn get_pending_count(&self) -> &Option<i32> {
+        &self.pendingCount
+    }
+
+    /// Gets the launch type the tasks of the service run with.
+    pub fn get_launch_type(&self) -> &Option<LaunchType> {
+        &self.launchType
+    }
+
+    /// Gets the task definition the service runs.
+    pub fn get_task_definition(&self) -> &Option<String> {
+        &self.taskDefinition
+    }
+
+    /// Gets the current deployments of the service.
+    pub fn get_deployments(&self) -> &Vec<Deployment> {
+        &self.deployments
+    }
+
+    /// Gets the task sets of the service.
+    pub fn get_task_sets(&self) -> &Vec<TaskSet> {
+        &self.taskSets
+    }
+
+    /// Gets the network configuration of the tasks of the service.
+    pub fn get_network_configuration(&self) -> &Option<NetworkConfiguration> {
+        &self.networkConfiguration
+    }
+
+    /// Gets the load balancers the tasks of the service are registered with.
+    pub fn get_load_balancers(&self) -> &Option<Vec<LoadBalancer>> {
+        &self.loadBalancers
+    }
+
+    /// Gets the time the service was created.
+    pub fn get_created_at(&self) -> &Option<f64> {
+        &self.createdAt
+    }
+
+    /// Gets the tags applied to the service.
+    pub fn get_tags(&self) -> &Option<Vec<Tag>> {
+        &self.tags
+    }
+
+    /// Returns true if the service has finished rolling out, i.e. it has a single deployment and
+    /// is running exactly its desired count of tasks.  This is the condition the official
+    /// services-stable waiter checks for.
+    pub fn is_stable(&self) -> bool {
+        self.deployments.len() == 1 && self.runningCount == self.desiredCount
+    }
+}
+
+/// Implements some convenience methods for looking at the values in a Deployment.
+impl Deployment {
+    /// Gets the ID of the deployment.
+    pub fn get_id(&self) -> &Option<String> {
+        &self.id
+    }
+
+    /// Gets the status of the deployment.
+    pub fn get_status(&self) -> &Option<String> {
+        &self.status
+    }
+
+    /// Gets the task definition the deployment rolls out.
+    pub fn get_task_definition(&self) -> &Option<String> {
+        &self.taskDefinition
+    }
+
+    /// Gets the number of tasks the deployment should run.
+    pub fn get_desired_count(&self) -> &Option<i32> {
+        &self.desiredCount
+    }
+
+    /// Gets the number of tasks of the deployment in the PENDING state.
+    pub fn get_pending_count(&self) -> &Option<i32> {
+        &self.pendingCount
+    }
+
+    /// Gets the number of tasks of the deployment in the RUNNING state.
+    pub fn get_running_count(&self) -> &Option<i32> {
+        &self.runningCount
+    }
+
+    /// Gets the progress of the rollout.
+    pub fn get_rollout_state(&self) -> &Option<String> {
+        &self.rolloutState
+    }
+
+    /// Gets the time the deployment was created.
+    pub fn get_created_at(&self) -> &Option<f64> {
+        &self.createdAt
+    }
+
+    /// Gets the time the deployment was last updated.
+    pub fn get_updated_at(&self) -> &Option<f64> {
+        &self.updatedAt
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Service;
+    use serde_json;
+
+    #[test]
+    fn test_is_stable() {
+        let stable: Service = serde_json::from_str(
+            "{\"serviceName\":\"web\",\"desiredCount\":2,\"runningCount\":2,\
+              \"deployments\":[{\"id\":\"ecs-svc/1\",\"status\":\"PRIMARY\"}]}").unwrap();
+        assert!(stable.is_stable());
+
+        let rolling: Service = serde_json::from_str(
+            "{\"serviceName\":\"web\",\"desiredCount\":2,\"runningCount\":2,\
+              \"deployments\":[{\"id\":\"ecs-svc/2\",\"status\":\"PRIMARY\"},\
+                               {\"id\":\"ecs-svc/1\",\"status\":\"ACTIVE\"}]}").unwrap();
+        assert!(!rolling.is_stable());
+
+        let scaling: Service = serde_json::from_str(
+            "{\"serviceName\":\"web\",\"desiredCount\":2,\"runningCount\":1,\
+              \"deployments\":[{\"id\":\"ecs-svc/1\",\"status\":\"PRIMARY\"}]}").unwrap();
+        assert!(!scaling.is_stable());
+    }
+}
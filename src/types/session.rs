@@ -0,0 +1,58 @@
+//! Defines the Session data type returned by ExecuteCommand, which holds what is needed to open
+//! the Session Manager data channel to a container.
+
+use std::fmt::{Debug, Formatter, Error};
+
+/// An interactive session with a container, opened by an ExecuteCommand action.  The token value
+/// grants access to the session and is never printed by this type's Debug implementation.
+#[allow(non_snake_case)]
+#[derive(Deserialize, Clone)]
+pub struct Session {
+    /// The ID of the session.
+    sessionId: String,
+    /// The websocket URL of the data channel of the session.
+    streamUrl: String,
+    /// The token used to authenticate the connection to the data channel.
+    tokenValue: String,
+}
+
+/// Implements some convenience methods for looking at the values in a Session.
+impl Session {
+    /// Gets the ID of the session.
+    pub fn get_session_id(&self) -> &String {
+        &self.sessionId
+    }
+
+    /// Gets the websocket URL of the data channel of the session.
+    pub fn get_stream_url(&self) -> &String {
+        &self.streamUrl
+    }
+
+    /// Gets the token used to authenticate the connection to the data channel.
+    pub fn get_token_value(&self) -> &String {
+        &self.tokenValue
+    }
+}
+
+impl Debug for Session {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "Session {{ sessionId: {:?}, streamUrl: {:?}, tokenValue: \"<redacted>\" }}",
+               self.sessionId, self.streamUrl)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Session;
+    use serde_json;
+
+    #[test]
+    fn test_debug_redacts_token() {
+        let session: Session = serde_json::from_str(
+            "{\"sessionId\":\"ecs-execute-command-0123\",\"streamUrl\":\"wss://ssmmessages.us-west-2.amazonaws.com/v1/data-channel/ecs-execute-command-0123\",\
+              \"tokenValue\":\"AAEAAVerySecret\"}").unwrap();
+        let debug = format!("{:?}", session);
+        assert!(debug.contains("ecs-execute-command-0123"));
+        assert!(!debug.contains("VerySecret"));
+    }
+}
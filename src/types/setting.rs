@@ -0,0 +1,89 @@
+//! Defines the Setting data type which describes an ECS account setting, and the names of the
+//! settings which can be configured.
+
+open_string_enum! {
+    /// The name of an ECS account setting.
+    pub enum SettingName {
+        ServiceLongArnFormat => "serviceLongArnFormat",
+        TaskLongArnFormat => "taskLongArnFormat",
+        ContainerInstanceLongArnFormat => "containerInstanceLongArnFormat",
+        AwsvpcTrunking => "awsvpcTrunking",
+        ContainerInsights => "containerInsights",
+        FargateFIPSMode => "fargateFIPSMode",
+        TagResourceAuthorization => "tagResourceAuthorization",
+        FargateTaskRetirementWaitPeriod => "fargateTaskRetirementWaitPeriod",
+        GuardDutyActivate => "guardDutyActivate",
+        DefaultLogDriverMode => "defaultLogDriverMode",
+    }
+}
+
+/// The value which turns most account settings on.
+pub const ENABLED: &'static str = "enabled";
+/// The value which turns most account settings off.
+pub const DISABLED: &'static str = "disabled";
+
+/// An account setting, and the principal it applies to.
+#[allow(non_snake_case)]
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct Setting {
+    /// The name of the account setting.
+    name: SettingName,
+    /// The value of the account setting, e.g. enabled or disabled.
+    value: String,
+    /// The ARN of the IAM user, role or root user the setting applies to.
+    principalArn: Option<String>,
+}
+
+/// Implements some convenience methods for looking at the values in a Setting.
+impl Setting {
+    /// Gets the name of the account setting.
+    pub fn get_name(&self) -> &SettingName {
+        &self.name
+    }
+
+    /// Gets the value of the account setting.
+    pub fn get_value(&self) -> &String {
+        &self.value
+    }
+
+    /// Gets the ARN of the principal the setting applies to.
+    pub fn get_principal_arn(&self) -> &Option<String> {
+        &self.principalArn
+    }
+
+    /// Returns true if the setting has the value "enabled".
+    pub fn is_enabled(&self) -> bool {
+        self.value == ENABLED
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Setting, SettingName};
+    use serde_json;
+
+    #[test]
+    fn test_deserialize_setting() {
+        let setting: Setting = serde_json::from_str(
+            "{\"name\":\"containerInsights\",\"value\":\"enabled\",\
+              \"principalArn\":\"arn:aws:iam::012345678910:root\"}").unwrap();
+        assert_eq!(&SettingName::ContainerInsights, setting.get_name());
+        assert!(setting.is_enabled());
+    }
+
+    #[test]
+    fn test_setting_name_round_trip() {
+        let name: SettingName = "awsvpcTrunking".parse().unwrap();
+        assert_eq!(SettingName::AwsvpcTrunking, name);
+        assert_eq!("awsvpcTrunking", name.to_string());
+        assert!("notASetting".parse::<SettingName>().is_err());
+    }
+
+    #[test]
+    fn test_deserialize_unknown_setting() {
+        let setting: Setting = serde_json::from_str(
+            "{\"name\":\"someFutureSetting\",\"value\":\"enabled\"}").unwrap();
+        assert_eq!(&SettingName::Unknown(String::from("someFutureSetting")), setting.get_name());
+        assert_eq!("someFutureSetting", setting.get_name().to_string());
+    }
+}
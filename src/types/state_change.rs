@@ -0,0 +1,179 @@
+//! Defines the types a container agent uses to report the state of the containers, network
+//! bindings and task attachments it manages back to ECS.
+
+use custom_ser;
+
+string_enum! {
+    /// The transport protocol used by a NetworkBinding.
+    pub enum TransportProtocol {
+        Tcp => "tcp",
+        Udp => "udp",
+    }
+}
+
+/// Describes a port on the container instance which has been bound to a port on a container.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct NetworkBinding {
+    /// The IP address that the container is bound to on the container instance.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    bindIP: Option<String>,
+    /// The port number on the container that is used with the network binding.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    containerPort: Option<u16>,
+    /// The port number on the host that is used with the network binding.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    hostPort: Option<u16>,
+    /// The protocol used for the network binding.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    protocol: Option<TransportProtocol>,
+}
+
+/// A change in the state of a single container, reported as part of a SubmitTaskStateChange
+/// request.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct ContainerStateChange {
+    /// The name of the container.
+    containerName: String,
+    /// The container image SHA 256 digest.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    imageDigest: Option<String>,
+    /// The ID of the Docker container.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    runtimeId: Option<String>,
+    /// The exit code of the container, if it has stopped.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    exitCode: Option<i32>,
+    /// Any network bindings associated with the container.
+    #[serde(skip_serializing_if="custom_ser::is_empty")]
+    networkBindings: Vec<NetworkBinding>,
+    /// The reason for the state change.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    reason: Option<String>,
+    /// The status of the container, e.g. RUNNING or STOPPED.
+    status: String,
+}
+
+/// A change in the status of an elastic network interface or other attachment of a task.
+#[allow(non_snake_case)]
+#[derive(Serialize, Debug)]
+pub struct AttachmentStateChange {
+    /// The Amazon Resource Name (ARN) of the attachment.
+    attachmentArn: String,
+    /// The status of the attachment, e.g. ATTACHED or DETACHED.
+    status: String,
+}
+
+/// Implements some convenience methods for building and reading a NetworkBinding.
+impl NetworkBinding {
+    /// Creates a NetworkBinding with all fields set.
+    pub fn new(bind_ip: String, container_port: u16, host_port: u16, protocol: TransportProtocol) -> Self {
+        NetworkBinding {
+            bindIP: Some(bind_ip),
+            containerPort: Some(container_port),
+            hostPort: Some(host_port),
+            protocol: Some(protocol),
+        }
+    }
+
+    /// Gets the IP address the container is bound to.
+    pub fn get_bind_ip(&self) -> &Option<String> {
+        &self.bindIP
+    }
+
+    /// Gets the port number on the container.
+    pub fn get_container_port(&self) -> &Option<u16> {
+        &self.containerPort
+    }
+
+    /// Gets the port number on the host.
+    pub fn get_host_port(&self) -> &Option<u16> {
+        &self.hostPort
+    }
+
+    /// Gets the protocol of the binding.
+    pub fn get_protocol(&self) -> &Option<TransportProtocol> {
+        &self.protocol
+    }
+}
+
+/// Implements some convenience methods for building a ContainerStateChange.
+impl ContainerStateChange {
+    /// Creates a ContainerStateChange for the named container with only its status set.
+    pub fn new(container_name: String, status: String) -> Self {
+        ContainerStateChange {
+            containerName: container_name,
+            imageDigest: None,
+            runtimeId: None,
+            exitCode: None,
+            networkBindings: Vec::new(),
+            reason: None,
+            status: status,
+        }
+    }
+
+    /// Sets the container image SHA 256 digest.
+    pub fn set_image_digest(&mut self, image_digest: String) {
+        self.imageDigest = Some(image_digest);
+    }
+
+    /// Sets the ID of the Docker container.
+    pub fn set_runtime_id(&mut self, runtime_id: String) {
+        self.runtimeId = Some(runtime_id);
+    }
+
+    /// Sets the exit code of the container.
+    pub fn set_exit_code(&mut self, exit_code: i32) {
+        self.exitCode = Some(exit_code);
+    }
+
+    /// Adds a network binding to report for the container.
+    pub fn add_network_binding(&mut self, binding: NetworkBinding) {
+        self.networkBindings.push(binding);
+    }
+
+    /// Sets the reason for the state change.
+    pub fn set_reason(&mut self, reason: String) {
+        self.reason = Some(reason);
+    }
+}
+
+/// Implements some convenience methods for building an AttachmentStateChange.
+impl AttachmentStateChange {
+    /// Creates an AttachmentStateChange for the given attachment ARN and status.
+    pub fn new(attachment_arn: String, status: String) -> Self {
+        AttachmentStateChange {
+            attachmentArn: attachment_arn,
+            status: status,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{NetworkBinding, ContainerStateChange, TransportProtocol};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_container_state_change_with_bindings() {
+        let mut change = ContainerStateChange::new(String::from("web"), String::from("RUNNING"));
+        change.add_network_binding(NetworkBinding::new(String::from("0.0.0.0"), 80, 32768, TransportProtocol::Tcp));
+        let expected: value::Value = serde_json::from_str(
+            "{\"containerName\":\"web\",\"status\":\"RUNNING\",\"networkBindings\":\
+             [{\"bindIP\":\"0.0.0.0\",\"containerPort\":80,\"hostPort\":32768,\"protocol\":\"tcp\"}]}").unwrap();
+        let actual: value::Value = value::to_value(&change);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_container_state_change_omits_empty_bindings() {
+        let mut change = ContainerStateChange::new(String::from("web"), String::from("STOPPED"));
+        change.set_exit_code(137);
+        let expected: value::Value = serde_json::from_str(
+            "{\"containerName\":\"web\",\"status\":\"STOPPED\",\"exitCode\":137}").unwrap();
+        let actual: value::Value = value::to_value(&change);
+        assert_eq!(expected, actual);
+    }
+}
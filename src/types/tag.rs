@@ -0,0 +1,180 @@
+//! Defines the Tag data type used to attach metadata to ECS resources, along with the
+//! client-side validation of the tagging constraints documented by ECS.
+
+use custom_ser;
+use error::ValidationError;
+
+/// The maximum number of tags a single resource can have.
+pub const MAX_TAGS: usize = 50;
+/// The maximum length of a tag key, in unicode characters.
+const MAX_KEY_LENGTH: usize = 128;
+/// The maximum length of a tag value, in unicode characters.
+const MAX_VALUE_LENGTH: usize = 256;
+/// The prefix reserved for tags created by AWS itself.  It is matched case-insensitively.
+const RESERVED_PREFIX: &'static str = "aws:";
+/// The punctuation allowed in tag keys and values, in addition to letters, numbers and spaces.
+const ALLOWED_PUNCTUATION: &'static str = "+-=._:/@";
+
+string_enum! {
+    /// Where the tags of the tasks started by a service or RunTask are copied from.
+    pub enum PropagateTags {
+        TaskDefinition => "TASK_DEFINITION",
+        Service => "SERVICE",
+        None => "NONE",
+    }
+}
+
+/// Metadata applied to an ECS resource, consisting of a key and an optional value.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Tag {
+    /// The key which categorizes the tag.
+    key: String,
+    /// The value which describes the tag within its category.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    value: Option<String>,
+}
+
+impl Tag {
+    /// Creates a Tag after checking the key and value against the constraints ECS places on
+    /// them: keys are 1-128 characters, values at most 256, both are limited to letters, numbers,
+    /// spaces and the characters + - = . _ : / @, and neither may begin with the reserved aws:
+    /// prefix.
+    pub fn new(key: String, value: String) -> Result<Tag, ValidationError> {
+        try!(validate_key(&key));
+        try!(validate_value(&value));
+        Ok(Tag {
+            key: key,
+            value: Some(value),
+        })
+    }
+
+    /// Creates a Tag with a key but no value, after checking the key as new() does.
+    pub fn with_key(key: String) -> Result<Tag, ValidationError> {
+        try!(validate_key(&key));
+        Ok(Tag {
+            key: key,
+            value: None,
+        })
+    }
+
+    /// Gets the key of the tag.
+    pub fn get_key(&self) -> &String {
+        &self.key
+    }
+
+    /// Gets the value of the tag, if it has one.
+    pub fn get_value(&self) -> &Option<String> {
+        &self.value
+    }
+}
+
+/// Checks that a set of tags can be applied to a single resource.
+pub fn validate_tags(tags: &[Tag]) -> Result<(), ValidationError> {
+    if tags.len() > MAX_TAGS {
+        return Err(ValidationError::new("tags",
+                                        format!("a resource can have at most {} tags, got {}", MAX_TAGS, tags.len())));
+    }
+    for tag in tags {
+        try!(validate_key(&tag.key));
+        if let Some(ref value) = tag.value {
+            try!(validate_value(value));
+        }
+    }
+    Ok(())
+}
+
+/// Checks a tag key against the constraints ECS places on it.
+pub fn validate_key(key: &str) -> Result<(), ValidationError> {
+    let length = key.chars().count();
+    if length == 0 || length > MAX_KEY_LENGTH {
+        return Err(ValidationError::new("tag key",
+                                        format!("must be between 1 and {} characters, got {}", MAX_KEY_LENGTH, length)));
+    }
+    validate_characters("tag key", key)
+}
+
+/// Checks a tag value against the constraints ECS places on it.
+fn validate_value(value: &str) -> Result<(), ValidationError> {
+    let length = value.chars().count();
+    if length > MAX_VALUE_LENGTH {
+        return Err(ValidationError::new("tag value",
+                                        format!("must be at most {} characters, got {}", MAX_VALUE_LENGTH, length)));
+    }
+    validate_characters("tag value", value)
+}
+
+/// Checks that a tag key or value uses only the allowed characters and does not begin with the
+/// reserved prefix.
+fn validate_characters(parameter: &str, input: &str) -> Result<(), ValidationError> {
+    if input.to_lowercase().starts_with(RESERVED_PREFIX) {
+        return Err(ValidationError::new(parameter,
+                                        format!("the {} prefix is reserved for use by AWS: {}", RESERVED_PREFIX, input)));
+    }
+    for c in input.chars() {
+        if !(c.is_alphanumeric() || c == ' ' || ALLOWED_PUNCTUATION.contains(c)) {
+            return Err(ValidationError::new(parameter, format!("character '{}' is not allowed: {}", c, input)));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Tag, validate_tags};
+    use std::iter;
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_serialize() {
+        let tag = Tag::new(String::from("team"), String::from("platform")).unwrap();
+        let expected: value::Value = serde_json::from_str("{\"key\":\"team\",\"value\":\"platform\"}").unwrap();
+        let actual: value::Value = value::to_value(&tag);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_tag_without_value() {
+        let tag = Tag::with_key(String::from("archived")).unwrap();
+        let expected: value::Value = serde_json::from_str("{\"key\":\"archived\"}").unwrap();
+        assert_eq!(expected, value::to_value(&tag));
+
+        let tag: Tag = serde_json::from_str("{\"key\":\"archived\"}").unwrap();
+        assert_eq!("archived", tag.get_key());
+        assert_eq!(&None, tag.get_value());
+        assert!(Tag::with_key(String::from("aws:archived")).is_err());
+    }
+
+    #[test]
+    fn test_allowed_characters() {
+        assert!(Tag::new(String::from("cost-center:id/1"), String::from("a+b=c @d_e.f")).is_ok());
+        assert!(Tag::new(String::from("team"), String::new()).is_ok());
+        assert!(Tag::new(String::from("team!"), String::from("platform")).is_err());
+    }
+
+    #[test]
+    fn test_length_limits() {
+        assert!(Tag::new(String::new(), String::from("value")).is_err());
+        assert!(Tag::new(repeat('k', 128), repeat('v', 256)).is_ok());
+        assert!(Tag::new(repeat('k', 129), String::from("value")).is_err());
+        assert!(Tag::new(String::from("key"), repeat('v', 257)).is_err());
+    }
+
+    #[test]
+    fn test_reserved_prefix() {
+        assert!(Tag::new(String::from("aws:cloudformation:stack-name"), String::from("x")).is_err());
+        assert!(Tag::new(String::from("AWS:Name"), String::from("x")).is_err());
+        assert!(Tag::new(String::from("name"), String::from("aws:x")).is_err());
+    }
+
+    #[test]
+    fn test_max_tags() {
+        let tags: Vec<Tag> = (0..51).map(|i| Tag::new(format!("key{}", i), String::new()).unwrap()).collect();
+        assert!(validate_tags(&tags[..50]).is_ok());
+        assert!(validate_tags(&tags).is_err());
+    }
+
+    fn repeat(c: char, n: usize) -> String {
+        iter::repeat(c).take(n).collect()
+    }
+}
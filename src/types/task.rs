@@ -0,0 +1,174 @@
+//! Defines the Task data type along with the Container type describing each container running
+//! inside a task.
+
+use types::launch_type::LaunchType;
+use types::tag::Tag;
+
+/// The lastStatus of a task or container which has started running.
+pub const RUNNING: &'static str = "RUNNING";
+/// The lastStatus of a task or container which has stopped.
+pub const STOPPED: &'static str = "STOPPED";
+
+/// A running instantiation of a task definition.
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct Task {
+    /// The Amazon Resource Name (ARN) of the task.
+    taskArn: Option<String>,
+    /// The ARN of the cluster hosting the task.
+    clusterArn: Option<String>,
+    /// The ARN of the task definition the task runs.
+    taskDefinitionArn: Option<String>,
+    /// The ARN of the container instance hosting the task.
+    containerInstanceArn: Option<String>,
+    /// The last status reported for the task, e.g. PENDING, RUNNING or STOPPED.
+    lastStatus: Option<String>,
+    /// The status ECS is moving the task towards.
+    desiredStatus: Option<String>,
+    /// The launch type the task runs with.
+    launchType: Option<LaunchType>,
+    /// The tag specified when the task was started, e.g. the ID of a service deployment.
+    startedBy: Option<String>,
+    /// The reason the task was stopped.
+    stoppedReason: Option<String>,
+    /// A code summarizing why the task was stopped, e.g. EssentialContainerExited.
+    stopCode: Option<String>,
+    /// The time the task was created, in seconds since the epoch.
+    createdAt: Option<f64>,
+    /// The time the task started running, in seconds since the epoch.
+    startedAt: Option<f64>,
+    /// The time the task stopped, in seconds since the epoch.
+    stoppedAt: Option<f64>,
+    /// The containers running in the task.
+    #[serde(default)]
+    containers: Vec<Container>,
+    /// The tags applied to the task.
+    tags: Option<Vec<Tag>>,
+}
+
+/// A container running inside a task.
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct Container {
+    /// The ARN of the container.
+    containerArn: Option<String>,
+    /// The name of the container, as given in the task definition.
+    name: Option<String>,
+    /// The last status reported for the container.
+    lastStatus: Option<String>,
+    /// The exit code of the container, if it has exited.
+    exitCode: Option<i32>,
+    /// The reason the container is in its current state.
+    reason: Option<String>,
+}
+
+/// Implements some convenience methods for looking at the values in a Task.
+impl Task {
+    /// Gets the ARN of the task.
+    pub fn get_task_arn(&self) -> &Option<String> {
+        &self.taskArn
+    }
+
+    /// Gets the ARN of the cluster hosting the task.
+    pub fn get_cluster_arn(&self) -> &Option<String> {
+        &self.clusterArn
+    }
+
+    /// Gets the ARN of the task definition the task runs.
+    pub fn get_task_definition_arn(&self) -> &Option<String> {
+        &self.taskDefinitionArn
+    }
+
+    /// Gets the ARN of the container instance hosting the task.
+    pub fn get_container_instance_arn(&self) -> &Option<String> {
+        &self.containerInstanceArn
+    }
+
+    /// Gets the last status reported for the task.
+    pub fn get_last_status(&self) -> &Option<String> {
+        &self.lastStatus
+    }
+
+    /// Gets the status ECS is moving the task towards.
+    pub fn get_desired_status(&self) -> &Option<String> {
+        &self.desiredStatus
+    }
+
+    /// Gets the launch type the task runs with.
+    pub fn get_launch_type(&self) -> &Option<LaunchType> {
+        &self.launchType
+    }
+
+    /// Gets the tag specified when the task was started.
+    pub fn get_started_by(&self) -> &Option<String> {
+        &self.startedBy
+    }
+
+    /// Gets the reason the task was stopped.
+    pub fn get_stopped_reason(&self) -> &Option<String> {
+        &self.stoppedReason
+    }
+
+    /// Gets a code summarizing why the task was stopped.
+    pub fn get_stop_code(&self) -> &Option<String> {
+        &self.stopCode
+    }
+
+    /// Gets the time the task was created.
+    pub fn get_created_at(&self) -> &Option<f64> {
+        &self.createdAt
+    }
+
+    /// Gets the time the task started running.
+    pub fn get_started_at(&self) -> &Option<f64> {
+        &self.startedAt
+    }
+
+    /// Gets the time the task stopped.
+    pub fn get_stopped_at(&self) -> &Option<f64> {
+        &self.stoppedAt
+    }
+
+    /// Gets the containers running in the task.
+    pub fn get_containers(&self) -> &Vec<Container> {
+        &self.containers
+    }
+
+    /// Gets the tags applied to the task.
+    pub fn get_tags(&self) -> &Option<Vec<Tag>> {
+        &self.tags
+    }
+
+    /// Returns true if the last status reported for the task is the given status.
+    pub fn has_last_status(&self, status: &str) -> bool {
+        self.lastStatus.as_ref().map_or(false, |last_status| last_status == status)
+    }
+}
+
+/// Implements some convenience methods for looking at the values in a Container.
+impl Container {
+    /// Gets the ARN of the container.
+    pub fn get_container_arn(&self) -> &Option<String> {
+        &self.containerArn
+    }
+
+    /// Gets the name of the container.
+    pub fn get_name(&self) -> &Option<String> {
+        &self.name
+    }
+
+    /// Gets the last status reported for the container.
+    pub fn get_last_status(&self) -> &Option<String> {
+        &self.lastStatus
+    }
+
+    /// Gets the exit code of the container.
+    pub fn get_exit_code(&self) -> &Option<i32> {
+        &self.exitCode
+    }
+
+    /// Gets the reason the container is in its current state.
+    pub fn get_reason(&self) -> &Option<String> {
+        &self.reason
+    }
+}
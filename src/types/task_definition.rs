@@ -0,0 +1,322 @@
+//! Defines the TaskDefinition data type, the blueprint ECS runs tasks and services from, along
+//! with the ContainerDefinition type describing each container it contains.
+
+use custom_ser;
+use types::launch_type::LaunchType;
+use types::state_change::TransportProtocol;
+
+string_enum! {
+    /// The Docker networking mode the containers of a task use.
+    pub enum NetworkMode {
+        Bridge => "bridge",
+        Host => "host",
+        AwsVpc => "awsvpc",
+        None => "none",
+    }
+}
+
+/// A port on a container mapped to a port on the host it runs on.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PortMapping {
+    /// The port number on the container.
+    containerPort: u16,
+    /// The port number on the host.  Left unset, an ephemeral port is chosen in bridge mode.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    hostPort: Option<u16>,
+    /// The protocol used for the port mapping.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    protocol: Option<TransportProtocol>,
+}
+
+/// An environment variable passed to a container.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct KeyValuePair {
+    /// The name of the environment variable.
+    name: String,
+    /// The value of the environment variable.
+    value: String,
+}
+
+/// A container which is part of a task definition.
+#[allow(non_snake_case)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ContainerDefinition {
+    /// The name of the container, unique within the task definition.
+    name: String,
+    /// The image the container is started from, e.g. nginx:latest.
+    image: String,
+    /// The number of CPU units reserved for the container.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    cpu: Option<i32>,
+    /// The hard limit on the memory of the container, in MiB.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    memory: Option<i32>,
+    /// The soft limit on the memory of the container, in MiB.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    memoryReservation: Option<i32>,
+    /// Whether the task stops when this container stops.  Containers are essential by default.
+    #[serde(skip_serializing_if="custom_ser::is_none")]
+    essential: Option<bool>,
+    /// The ports exposed by the container.
+    #[serde(default, skip_serializing_if="custom_ser::is_empty")]
+    portMappings: Vec<PortMapping>,
+    /// The environment variables passed to the container.
+    #[serde(default, skip_serializing_if="custom_ser::is_empty")]
+    environment: Vec<KeyValuePair>,
+    /// The entry point of the container, replacing the ENTRYPOINT of the image.
+    #[serde(default, skip_serializing_if="custom_ser::is_empty")]
+    entryPoint: Vec<String>,
+    /// The command run in the container, replacing the CMD of the image.
+    #[serde(default, skip_serializing_if="custom_ser::is_empty")]
+    command: Vec<String>,
+}
+
+/// A registered revision of a task definition.
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct TaskDefinition {
+    /// The full Amazon Resource Name (ARN) of the task definition, including its revision.
+    taskDefinitionArn: Option<String>,
+    /// The family the task definition is a revision of.
+    family: Option<String>,
+    /// The revision of the task definition within its family.
+    revision: Option<i32>,
+    /// The status of the task definition, e.g. ACTIVE or INACTIVE.
+    status: Option<String>,
+    /// The containers of the task definition.
+    #[serde(default)]
+    containerDefinitions: Vec<ContainerDefinition>,
+    /// The Docker networking mode the containers use.
+    networkMode: Option<NetworkMode>,
+    /// The ARN of the IAM role the containers can assume.
+    taskRoleArn: Option<String>,
+    /// The ARN of the IAM role the container agent uses to pull images and publish logs.
+    executionRoleArn: Option<String>,
+    /// The number of CPU units used by the task, as a string.
+    cpu: Option<String>,
+    /// The amount of memory used by the task in MiB, as a string.
+    memory: Option<String>,
+    /// The launch types the task definition was validated against.
+    #[serde(default)]
+    requiresCompatibilities: Vec<LaunchType>,
+    /// The time the task definition was registered, in seconds since the epoch.
+    registeredAt: Option<f64>,
+}
+
+/// Implements some convenience methods for building a PortMapping.
+impl PortMapping {
+    /// Creates a PortMapping for a container port, leaving the host port to ECS.
+    pub fn new(container_port: u16) -> Self {
+        PortMapping {
+            containerPort: container_port,
+            hostPort: None,
+            protocol: None,
+        }
+    }
+
+    /// Sets the port number on the host.
+    pub fn set_host_port(&mut self, host_port: u16) {
+        self.hostPort = Some(host_port);
+    }
+
+    /// Sets the protocol used for the port mapping.
+    pub fn set_protocol(&mut self, protocol: TransportProtocol) {
+        self.protocol = Some(protocol);
+    }
+
+    /// Gets the port number on the container.
+    pub fn get_container_port(&self) -> u16 {
+        self.containerPort
+    }
+
+    /// Gets the port number on the host.
+    pub fn get_host_port(&self) -> &Option<u16> {
+        &self.hostPort
+    }
+}
+
+/// Implements some convenience methods for building a KeyValuePair.
+impl KeyValuePair {
+    /// Creates a KeyValuePair.
+    pub fn new(name: String, value: String) -> Self {
+        KeyValuePair {
+            name: name,
+            value: value,
+        }
+    }
+
+    /// Gets the name of the environment variable.
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    /// Gets the value of the environment variable.
+    pub fn get_value(&self) -> &String {
+        &self.value
+    }
+}
+
+/// Implements some convenience methods for building and looking at a ContainerDefinition.
+impl ContainerDefinition {
+    /// Creates a ContainerDefinition running the given image.
+    pub fn new(name: String, image: String) -> Self {
+        ContainerDefinition {
+            name: name,
+            image: image,
+            cpu: None,
+            memory: None,
+            memoryReservation: None,
+            essential: None,
+            portMappings: Vec::new(),
+            environment: Vec::new(),
+            entryPoint: Vec::new(),
+            command: Vec::new(),
+        }
+    }
+
+    /// Sets the number of CPU units reserved for the container.
+    pub fn set_cpu(&mut self, cpu: i32) {
+        self.cpu = Some(cpu);
+    }
+
+    /// Sets the hard limit on the memory of the container, in MiB.
+    pub fn set_memory(&mut self, memory: i32) {
+        self.memory = Some(memory);
+    }
+
+    /// Sets the soft limit on the memory of the container, in MiB.
+    pub fn set_memory_reservation(&mut self, memory_reservation: i32) {
+        self.memoryReservation = Some(memory_reservation);
+    }
+
+    /// Sets whether the task stops when this container stops.
+    pub fn set_essential(&mut self, essential: bool) {
+        self.essential = Some(essential);
+    }
+
+    /// Adds a port exposed by the container.
+    pub fn add_port_mapping(&mut self, port_mapping: PortMapping) {
+        self.portMappings.push(port_mapping);
+    }
+
+    /// Adds an environment variable passed to the container.
+    pub fn add_environment(&mut self, name: String, value: String) {
+        self.environment.push(KeyValuePair::new(name, value));
+    }
+
+    /// Sets the entry point of the container.
+    pub fn set_entry_point(&mut self, entry_point: Vec<String>) {
+        self.entryPoint = entry_point;
+    }
+
+    /// Sets the command run in the container.
+    pub fn set_command(&mut self, command: Vec<String>) {
+        self.command = command;
+    }
+
+    /// Gets the name of the container.
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    /// Gets the image the container is started from.
+    pub fn get_image(&self) -> &String {
+        &self.image
+    }
+
+    /// Gets the ports exposed by the container.
+    pub fn get_port_mappings(&self) -> &Vec<PortMapping> {
+        &self.portMappings
+    }
+
+    /// Gets the environment variables passed to the container.
+    pub fn get_environment(&self) -> &Vec<KeyValuePair> {
+        &self.environment
+    }
+}
+
+/// Implements some convenience methods for looking at the values in a TaskDefinition.
+impl TaskDefinition {
+    /// Gets the full ARN of the task definition.
+    pub fn get_task_definition_arn(&self) -> &Option<String> {
+        &self.taskDefinitionArn
+    }
+
+    /// Gets the family the task definition is a revision of.
+    pub fn get_family(&self) -> &Option<String> {
+        &self.family
+    }
+
+    /// Gets the revision of the task definition within its family.
+    pub fn get_revision(&self) -> &Option<i32> {
+        &self.revision
+    }
+
+    /// Gets the status of the task definition.
+    pub fn get_status(&self) -> &Option<String> {
+        &self.status
+    }
+
+    /// Gets the containers of the task definition.
+    pub fn get_container_definitions(&self) -> &Vec<ContainerDefinition> {
+        &self.containerDefinitions
+    }
+
+    /// Gets the Docker networking mode the containers use.
+    pub fn get_network_mode(&self) -> &Option<NetworkMode> {
+        &self.networkMode
+    }
+
+    /// Gets the ARN of the IAM role the containers can assume.
+    pub fn get_task_role_arn(&self) -> &Option<String> {
+        &self.taskRoleArn
+    }
+
+    /// Gets the ARN of the IAM role the container agent uses.
+    pub fn get_execution_role_arn(&self) -> &Option<String> {
+        &self.executionRoleArn
+    }
+
+    /// Gets the number of CPU units used by the task.
+    pub fn get_cpu(&self) -> &Option<String> {
+        &self.cpu
+    }
+
+    /// Gets the amount of memory used by the task, in MiB.
+    pub fn get_memory(&self) -> &Option<String> {
+        &self.memory
+    }
+
+    /// Gets the launch types the task definition was validated against.
+    pub fn get_requires_compatibilities(&self) -> &Vec<LaunchType> {
+        &self.requiresCompatibilities
+    }
+
+    /// Gets the time the task definition was registered.
+    pub fn get_registered_at(&self) -> &Option<f64> {
+        &self.registeredAt
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{NetworkMode, TaskDefinition};
+    use serde_json;
+
+    #[test]
+    fn test_deserialize_task_definition() {
+        let task_definition: TaskDefinition = serde_json::from_str(
+            "{\"taskDefinitionArn\":\"arn:aws:ecs:us-west-2:012345678910:task-definition/web:3\",\
+              \"family\":\"web\",\"revision\":3,\"status\":\"ACTIVE\",\"networkMode\":\"awsvpc\",\
+              \"containerDefinitions\":[{\"name\":\"nginx\",\"image\":\"nginx:latest\",\
+                                         \"portMappings\":[{\"containerPort\":80,\"protocol\":\"tcp\"}]}],\
+              \"requiresCompatibilities\":[\"FARGATE\"]}").unwrap();
+        assert_eq!(&Some(3), task_definition.get_revision());
+        assert_eq!(&Some(NetworkMode::AwsVpc), task_definition.get_network_mode());
+        let container = &task_definition.get_container_definitions()[0];
+        assert_eq!("nginx", container.get_name());
+        assert_eq!(80, container.get_port_mappings()[0].get_container_port());
+        assert!(container.get_environment().is_empty());
+    }
+}
@@ -0,0 +1,266 @@
+//! Defines the TaskSet data type, a set of tasks within a service which is managed by an external
+//! deployment controller, and the Scale type which sizes it.
+
+use custom_ser;
+use error::ValidationError;
+use types::capacity_provider::CapacityProviderStrategyItem;
+use types::launch_type::LaunchType;
+use types::networking::{NetworkConfiguration, LoadBalancer};
+use types::tag::Tag;
+
+string_enum! {
+    /// The unit of a Scale value.
+    pub enum ScaleUnit {
+        Percent => "PERCENT",
+    }
+}
+
+string_enum! {
+    /// The role of a task set within its service.
+    pub enum TaskSetStatus {
+        /// The task set serves production traffic.
+        Primary => "PRIMARY",
+        /// The task set does not serve production traffic.
+        Active => "ACTIVE",
+        /// The tasks in the task set are being stopped and their targets deregistered.
+        Draining => "DRAINING",
+    }
+}
+
+string_enum! {
+    /// Whether a task set has reached its computed desired count.
+    pub enum StabilityStatus {
+        SteadyState => "STEADY_STATE",
+        Stabilizing => "STABILIZING",
+    }
+}
+
+/// The size of a task set, as a percentage of the desired count of its service.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Scale {
+    /// The value of the scale, between 0 and 100.
+    value: f64,
+    /// The unit of the value.
+    unit: ScaleUnit,
+}
+
+/// A set of tasks within a service using the EXTERNAL deployment controller.
+#[allow(non_snake_case)]
+#[derive(Deserialize, Debug)]
+pub struct TaskSet {
+    /// The ID of the task set.
+    id: Option<String>,
+    /// The Amazon Resource Name (ARN) of the task set.
+    taskSetArn: Option<String>,
+    /// The ARN of the service the task set belongs to.
+    serviceArn: Option<String>,
+    /// The ARN of the cluster the service belongs to.
+    clusterArn: Option<String>,
+    /// The tag specified when the task set was started, e.g. by a CodeDeploy deployment.
+    startedBy: Option<String>,
+    /// The external ID the deployment controller associated with the task set.
+    externalId: Option<String>,
+    /// The role of the task set within its service.
+    status: Option<TaskSetStatus>,
+    /// The task definition the task set runs.
+    taskDefinition: Option<String>,
+    /// The number of tasks the task set should run, computed from its scale.
+    computedDesiredCount: Option<i32>,
+    /// The number of tasks in the task set in the PENDING state.
+    pendingCount: Option<i32>,
+    /// The number of tasks in the task set in the RUNNING state.
+    runningCount: Option<i32>,
+    /// The time the task set was created, in seconds since the epoch.
+    createdAt: Option<f64>,
+    /// The time the task set was last updated, in seconds since the epoch.
+    updatedAt: Option<f64>,
+    /// The launch type the tasks in the task set run with.
+    launchType: Option<LaunchType>,
+    /// The capacity provider strategy the task set uses.
+    capacityProviderStrategy: Option<Vec<CapacityProviderStrategyItem>>,
+    /// The Fargate platform version the tasks in the task set run on.
+    platformVersion: Option<String>,
+    /// The network configuration of the tasks in the task set.
+    networkConfiguration: Option<NetworkConfiguration>,
+    /// The load balancers the tasks in the task set are registered with.
+    loadBalancers: Option<Vec<LoadBalancer>>,
+    /// The size of the task set, relative to its service.
+    scale: Option<Scale>,
+    /// Whether the task set has reached its computed desired count.
+    stabilityStatus: Option<StabilityStatus>,
+    /// The time the stability status last changed, in seconds since the epoch.
+    stabilityStatusAt: Option<f64>,
+    /// The tags applied to the task set.
+    tags: Option<Vec<Tag>>,
+}
+
+/// Implements some convenience methods for building a Scale.
+impl Scale {
+    /// Creates a Scale of the given percentage of the service's desired count.
+    pub fn percent(value: f64) -> Result<Scale, ValidationError> {
+        if !(value >= 0.0 && value <= 100.0) {
+            return Err(ValidationError::new("scale", format!("must be between 0 and 100 percent, got {}", value)));
+        }
+        Ok(Scale {
+            value: value,
+            unit: ScaleUnit::Percent,
+        })
+    }
+
+    /// Gets the value of the scale.
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    /// Gets the unit of the value.
+    pub fn get_unit(&self) -> ScaleUnit {
+        self.unit
+    }
+}
+
+/// Implements some convenience methods for looking at the values in a TaskSet.
+impl TaskSet {
+    /// Gets the ID of the task set.
+    pub fn get_id(&self) -> &Option<String> {
+        &self.id
+    }
+
+    /// Gets the ARN of the task set.
+    pub fn get_task_set_arn(&self) -> &Option<String> {
+        &self.taskSetArn
+    }
+
+    /// Gets the ARN of the service the task set belongs to.
+    pub fn get_service_arn(&self) -> &Option<String> {
+        &self.serviceArn
+    }
+
+    /// Gets the ARN of the cluster the service belongs to.
+    pub fn get_cluster_arn(&self) -> &Option<String> {
+        &self.clusterArn
+    }
+
+    /// Gets the tag specified when the task set was started.
+    pub fn get_started_by(&self) -> &Option<String> {
+        &self.startedBy
+    }
+
+    /// Gets the external ID the deployment controller associated with the task set.
+    pub fn get_external_id(&self) -> &Option<String> {
+        &self.externalId
+    }
+
+    /// Gets the role of the task set within its service.
+    pub fn get_status(&self) -> &Option<TaskSetStatus> {
+        &self.status
+    }
+
+    /// Gets the task definition the task set runs.
+    pub fn get_task_definition(&self) -> &Option<String> {
+        &self.taskDefinition
+    }
+
+    /// Gets the number of tasks the task set should run.
+    pub fn get_computed_desired_count(&self) -> &Option<i32> {
+        &self.computedDesiredCount
+    }
+
+    /// Gets the number of PENDING tasks in the task set.
+    pub fn get_pending_count(&self) -> &Option<i32> {
+        &self.pendingCount
+    }
+
+    /// Gets the number of RUNNING tasks in the task set.
+    pub fn get_running_count(&self) -> &Option<i32> {
+        &self.runningCount
+    }
+
+    /// Gets the creation time of the task set, in seconds since the epoch.
+    pub fn get_created_at(&self) -> &Option<f64> {
+        &self.createdAt
+    }
+
+    /// Gets the last update time of the task set, in seconds since the epoch.
+    pub fn get_updated_at(&self) -> &Option<f64> {
+        &self.updatedAt
+    }
+
+    /// Gets the launch type the tasks in the task set run with.
+    pub fn get_launch_type(&self) -> &Option<LaunchType> {
+        &self.launchType
+    }
+
+    /// Gets the capacity provider strategy the task set uses.
+    pub fn get_capacity_provider_strategy(&self) -> &Option<Vec<CapacityProviderStrategyItem>> {
+        &self.capacityProviderStrategy
+    }
+
+    /// Gets the Fargate platform version the tasks in the task set run on.
+    pub fn get_platform_version(&self) -> &Option<String> {
+        &self.platformVersion
+    }
+
+    /// Gets the network configuration of the tasks in the task set.
+    pub fn get_network_configuration(&self) -> &Option<NetworkConfiguration> {
+        &self.networkConfiguration
+    }
+
+    /// Gets the load balancers the tasks in the task set are registered with.
+    pub fn get_load_balancers(&self) -> &Option<Vec<LoadBalancer>> {
+        &self.loadBalancers
+    }
+
+    /// Gets the size of the task set, relative to its service.
+    pub fn get_scale(&self) -> &Option<Scale> {
+        &self.scale
+    }
+
+    /// Gets whether the task set has reached its computed desired count.
+    pub fn get_stability_status(&self) -> &Option<StabilityStatus> {
+        &self.stabilityStatus
+    }
+
+    /// Gets the time the stability status last changed, in seconds since the epoch.
+    pub fn get_stability_status_at(&self) -> &Option<f64> {
+        &self.stabilityStatusAt
+    }
+
+    /// Gets the tags applied to the task set.
+    pub fn get_tags(&self) -> &Option<Vec<Tag>> {
+        &self.tags
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Scale, TaskSet, TaskSetStatus, StabilityStatus};
+    use serde_json;
+    use serde_json::value;
+
+    #[test]
+    fn test_serialize_scale() {
+        let scale = Scale::percent(50.0).unwrap();
+        let expected: value::Value = serde_json::from_str("{\"value\":50.0,\"unit\":\"PERCENT\"}").unwrap();
+        let actual: value::Value = value::to_value(&scale);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_scale_limits() {
+        assert!(Scale::percent(0.0).is_ok());
+        assert!(Scale::percent(100.0).is_ok());
+        assert!(Scale::percent(100.5).is_err());
+        assert!(Scale::percent(-1.0).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_task_set() {
+        let task_set: TaskSet = serde_json::from_str(
+            "{\"id\":\"ecs-svc/1234\",\"status\":\"PRIMARY\",\"computedDesiredCount\":4,\
+              \"scale\":{\"value\":100.0,\"unit\":\"PERCENT\"},\"stabilityStatus\":\"STABILIZING\",\
+              \"launchType\":\"FARGATE\"}").unwrap();
+        assert_eq!(&Some(TaskSetStatus::Primary), task_set.get_status());
+        assert_eq!(&Some(StabilityStatus::Stabilizing), task_set.get_stability_status());
+        assert_eq!(100.0, task_set.get_scale().unwrap().get_value());
+    }
+}
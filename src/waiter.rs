@@ -0,0 +1,256 @@
+//! This module contains waiters which poll a Describe* action until the resources it describes
+//! reach a desired state, mirroring the waiters of the official AWS SDKs and CLI.
+
+use action::describe_services::DescribeServicesResponse;
+use action::describe_tasks::DescribeTasksResponse;
+use error::{ECSError, WaiterError};
+use types::task;
+use std::thread;
+use std::time::Duration;
+
+/// The Failure reason ECS reports for a resource which does not exist.
+const MISSING: &'static str = "MISSING";
+
+/// Polls for a response of type T until it matches the success condition, a failure matcher
+/// matches, or max_attempts responses have been polled.
+pub struct Waiter<T> {
+    /// The time to sleep between attempts.
+    delay: Duration,
+    /// The max number of responses to poll before giving up.
+    max_attempts: u32,
+    /// Returns true once the polled response is in the desired state.
+    success: Box<Fn(&T) -> bool>,
+    /// Each returns a description of the state matched if the polled response is in a state from
+    /// which the desired state can no longer be reached.
+    failure_matchers: Vec<Box<Fn(&T) -> Option<String>>>,
+}
+
+impl<T> Waiter<T> {
+    /// Creates a Waiter with the given success condition, no failure matchers, and the delay and
+    /// max attempts of the official services-stable waiter (15 seconds and 40 attempts).
+    pub fn new<F>(success: F) -> Self
+        where F: Fn(&T) -> bool + 'static
+    {
+        Waiter {
+            delay: Duration::from_secs(15),
+            max_attempts: 40,
+            success: Box::new(success),
+            failure_matchers: Vec::new(),
+        }
+    }
+
+    /// Sets the time to sleep between attempts.
+    pub fn set_delay(&mut self, delay: Duration) {
+        self.delay = delay;
+    }
+
+    /// Sets the max number of responses to poll before giving up.
+    pub fn set_max_attempts(&mut self, max_attempts: u32) {
+        self.max_attempts = max_attempts;
+    }
+
+    /// Adds a matcher which stops the waiter with a WaiterError::FailureState when it returns a
+    /// description of the state it matched.  Failure matchers are checked before the success
+    /// condition.
+    pub fn add_failure_matcher<F>(&mut self, matcher: F)
+        where F: Fn(&T) -> Option<String> + 'static
+    {
+        self.failure_matchers.push(Box::new(matcher));
+    }
+
+    /// Calls poll until its response reaches the desired state, sleeping for the configured
+    /// delay between attempts, and returns the final response.  Stops with a
+    /// WaiterError::Request as soon as poll fails.
+    pub fn wait<F>(&self, mut poll: F) -> Result<T, WaiterError>
+        where F: FnMut() -> Result<T, ECSError>
+    {
+        for attempt in 1..(self.max_attempts + 1) {
+            let response = try!(poll());
+            for matcher in &self.failure_matchers {
+                if let Some(state) = matcher(&response) {
+                    return Err(WaiterError::FailureState(state));
+                }
+            }
+            if (self.success)(&response) {
+                return Ok(response);
+            }
+            if attempt < self.max_attempts {
+                thread::sleep(self.delay);
+            }
+        }
+        Err(WaiterError::MaxAttemptsExceeded(self.max_attempts))
+    }
+}
+
+/// Creates a Waiter matching the official services-stable waiter: it succeeds once every service
+/// has a single deployment running its desired count of tasks, and fails if any service is
+/// missing, DRAINING or INACTIVE.
+pub fn services_stable() -> Waiter<DescribeServicesResponse> {
+    let mut waiter = Waiter::new(|response: &DescribeServicesResponse| {
+        response.get_services().iter().all(|service| service.is_stable())
+    });
+    waiter.add_failure_matcher(|response: &DescribeServicesResponse| {
+        missing(response.get_failures().iter().map(|failure| (failure.get_reason(), failure.get_arn())))
+    });
+    waiter.add_failure_matcher(|response: &DescribeServicesResponse| {
+        response.get_services().iter().filter_map(|service| {
+            match service.get_status().as_ref().map(|status| &status[..]) {
+                Some(status @ "DRAINING") | Some(status @ "INACTIVE") =>
+                    Some(format!("service {} is {}", describe(service.get_service_name()), status)),
+                _ => None,
+            }
+        }).next()
+    });
+    waiter
+}
+
+/// Creates a Waiter matching the official tasks-running waiter: it succeeds once every task is
+/// RUNNING, and fails if any task is missing or has STOPPED.
+pub fn tasks_running() -> Waiter<DescribeTasksResponse> {
+    let mut waiter = tasks_waiter(task::RUNNING);
+    waiter.add_failure_matcher(|response: &DescribeTasksResponse| {
+        missing(response.get_failures().iter().map(|failure| (failure.get_reason(), failure.get_arn())))
+    });
+    waiter.add_failure_matcher(|response: &DescribeTasksResponse| {
+        response.get_tasks().iter()
+                .find(|stopped| stopped.has_last_status(task::STOPPED))
+                .map(|stopped| format!("task {} is STOPPED", describe(stopped.get_task_arn())))
+    });
+    waiter
+}
+
+/// Creates a Waiter matching the official tasks-stopped waiter: it succeeds once every task has
+/// STOPPED.  A task which is missing is not treated as a failure, since ECS eventually forgets
+/// stopped tasks, so the waiter keeps polling until its max attempts.
+pub fn tasks_stopped() -> Waiter<DescribeTasksResponse> {
+    tasks_waiter(task::STOPPED)
+}
+
+/// Creates a Waiter which succeeds once every task has the given lastStatus, polling with the
+/// delay and max attempts of the official task waiters (6 seconds and 100 attempts).
+fn tasks_waiter(status: &'static str) -> Waiter<DescribeTasksResponse> {
+    let mut waiter = Waiter::new(move |response: &DescribeTasksResponse| {
+        !response.get_tasks().is_empty() &&
+        response.get_tasks().iter().all(|polled| polled.has_last_status(status))
+    });
+    waiter.set_delay(Duration::from_secs(6));
+    waiter.set_max_attempts(100);
+    waiter
+}
+
+/// Returns a description of the first (reason, arn) pair whose reason is MISSING.
+fn missing<'a, I>(failures: I) -> Option<String>
+    where I: Iterator<Item=(&'a Option<String>, &'a Option<String>)>
+{
+    for (reason, arn) in failures {
+        if reason.as_ref().map_or(false, |reason| reason == MISSING) {
+            return Some(format!("{} is MISSING", describe(arn)));
+        }
+    }
+    None
+}
+
+/// Describes an optional name or ARN for use in a WaiterError.
+fn describe(name: &Option<String>) -> String {
+    name.clone().unwrap_or(String::from("<unknown>"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{services_stable, tasks_running, tasks_stopped};
+    use action::describe_services::DescribeServicesResponse;
+    use action::describe_tasks::DescribeTasksResponse;
+    use error::{ECSError, ServiceError, WaiterError};
+    use serde_json;
+    use std::time::Duration;
+
+    fn poll_from<T>(responses: Vec<&'static str>) -> Box<FnMut() -> Result<T, ECSError>>
+        where T: ::serde::de::Deserialize + 'static
+    {
+        let mut responses = responses.into_iter();
+        Box::new(move || Ok(serde_json::from_str(responses.next().unwrap()).unwrap()))
+    }
+
+    /// Asserts that the waiter stopped in a failure state with the given description.
+    fn assert_failure_state<T>(result: Result<T, WaiterError>, expected: &str) {
+        match result {
+            Err(WaiterError::FailureState(state)) => assert_eq!(expected, state),
+            Err(error) => panic!("expected a failure state, got {}", error),
+            Ok(_) => panic!("expected a failure state, got a response"),
+        }
+    }
+
+    #[test]
+    fn test_services_stable() {
+        let mut waiter = services_stable();
+        waiter.set_delay(Duration::from_millis(0));
+        let mut poll = poll_from::<DescribeServicesResponse>(vec![
+            "{\"services\":[{\"serviceName\":\"web\",\"status\":\"ACTIVE\",\"desiredCount\":2,\"runningCount\":1,\
+                             \"deployments\":[{\"id\":\"ecs-svc/2\"},{\"id\":\"ecs-svc/1\"}]}]}",
+            "{\"services\":[{\"serviceName\":\"web\",\"status\":\"ACTIVE\",\"desiredCount\":2,\"runningCount\":2,\
+                             \"deployments\":[{\"id\":\"ecs-svc/2\"}]}]}"]);
+        let response = waiter.wait(|| poll()).unwrap();
+        assert!(response.get_services()[0].is_stable());
+    }
+
+    #[test]
+    fn test_services_stable_inactive() {
+        let waiter = services_stable();
+        let mut poll = poll_from::<DescribeServicesResponse>(vec![
+            "{\"services\":[{\"serviceName\":\"web\",\"status\":\"INACTIVE\",\"deployments\":[]}]}"]);
+        assert_failure_state(waiter.wait(|| poll()), "service web is INACTIVE");
+    }
+
+    #[test]
+    fn test_tasks_running_stopped() {
+        let waiter = tasks_running();
+        let mut poll = poll_from::<DescribeTasksResponse>(vec![
+            "{\"tasks\":[{\"taskArn\":\"t1\",\"lastStatus\":\"STOPPED\"}]}"]);
+        assert_failure_state(waiter.wait(|| poll()), "task t1 is STOPPED");
+    }
+
+    #[test]
+    fn test_tasks_missing() {
+        let waiter = tasks_running();
+        let mut poll = poll_from::<DescribeTasksResponse>(vec![
+            "{\"tasks\":[],\"failures\":[{\"arn\":\"t1\",\"reason\":\"MISSING\"}]}"]);
+        assert_failure_state(waiter.wait(|| poll()), "t1 is MISSING");
+    }
+
+    #[test]
+    fn test_tasks_stopped_ignores_missing() {
+        let mut waiter = tasks_stopped();
+        waiter.set_delay(Duration::from_millis(0));
+        waiter.set_max_attempts(2);
+        let mut poll = poll_from::<DescribeTasksResponse>(vec![
+            "{\"tasks\":[],\"failures\":[{\"arn\":\"t1\",\"reason\":\"MISSING\"}]}",
+            "{\"tasks\":[],\"failures\":[{\"arn\":\"t1\",\"reason\":\"MISSING\"}]}"]);
+        match waiter.wait(|| poll()) {
+            Err(WaiterError::MaxAttemptsExceeded(attempts)) => assert_eq!(2, attempts),
+            _ => panic!("expected the waiter to keep polling past a MISSING task"),
+        }
+    }
+
+    #[test]
+    fn test_max_attempts() {
+        let mut waiter = tasks_stopped();
+        waiter.set_delay(Duration::from_millis(0));
+        waiter.set_max_attempts(2);
+        let mut poll = poll_from::<DescribeTasksResponse>(vec![
+            "{\"tasks\":[{\"taskArn\":\"t1\",\"lastStatus\":\"RUNNING\"}]}",
+            "{\"tasks\":[{\"taskArn\":\"t1\",\"lastStatus\":\"STOPPING\"}]}"]);
+        match waiter.wait(|| poll()) {
+            Err(WaiterError::MaxAttemptsExceeded(attempts)) => assert_eq!(2, attempts),
+            _ => panic!("expected the waiter to exceed its max attempts"),
+        }
+    }
+
+    #[test]
+    fn test_request_error() {
+        let waiter = tasks_stopped();
+        match waiter.wait(|| Err(ECSError::Service(ServiceError::from_response(400, "{}")))) {
+            Err(WaiterError::Request(_)) => (),
+            _ => panic!("expected the waiter to stop on the failed request"),
+        }
+    }
+}
@@ -0,0 +1,358 @@
+extern crate ecs_client;
+extern crate hyper;
+
+use ecs_client::ecs_client::ECSClient;
+use ecs_client::action::{list_clusters, describe_services, describe_tasks};
+use ecs_client::action::{register_task_definition, run_task, create_service, create_cluster};
+use ecs_client::action::{register_container_instance, put_attributes, list_attributes, update_container_agent};
+use ecs_client::action::{describe_container_instances, tag_resource, untag_resource, list_tags_for_resource};
+use ecs_client::action::{create_capacity_provider, put_cluster_capacity_providers, delete_capacity_provider};
+use ecs_client::action::{describe_capacity_providers, put_account_setting, list_account_settings};
+use ecs_client::action::{delete_account_setting, create_task_set, update_service_primary_task_set};
+use ecs_client::action::{describe_task_sets, delete_task_set, execute_command};
+use ecs_client::custom_headers::XAmzTarget;
+use ecs_client::error::ECSError;
+use ecs_client::fake_server::FakeECS;
+use ecs_client::interceptor::Interceptor;
+use ecs_client::logging::RequestContext;
+use ecs_client::region::Region;
+use ecs_client::types::attribute::{Attribute, TargetType};
+use ecs_client::types::capacity_provider::{AutoScalingGroupProvider, CapacityProviderStatus};
+use ecs_client::types::container_instance::{AgentUpdateStatus, VersionInfo};
+use ecs_client::types::setting::SettingName;
+use ecs_client::types::tag::Tag;
+use ecs_client::types::task_definition::ContainerDefinition;
+use ecs_client::types::task_set::{Scale, TaskSetStatus, StabilityStatus};
+use ecs_client::waiter;
+
+use hyper::header::{Authorization, ContentType, Headers};
+use std::env;
+use std::io::Read;
+use std::time::Duration;
+
+/// Starts a FakeECS and creates an ECSClient pointed at it, with dummy credentials in the
+/// environment.
+fn fake_client() -> (ECSClient, FakeECS) {
+    env::set_var("AWS_ACCESS_KEY_ID", "AKIDEXAMPLE");
+    env::set_var("AWS_SECRET_ACCESS_KEY", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY");
+    let fake = FakeECS::start(Region::USWest2).unwrap();
+    let mut client = ECSClient::for_region(Region::USWest2);
+    client.set_endpoint(&fake.endpoint()).unwrap();
+    (client, fake)
+}
+
+#[test]
+fn test_list_clusters_pages() {
+    let (client, fake) = fake_client();
+    fake.add_cluster("default");
+    fake.add_cluster("staging");
+    fake.add_cluster("production");
+
+    let arns: Vec<String> = client.list_clusters_all(list_clusters::ListClustersRequest::with_max_results(2))
+                                  .map(|arn| arn.unwrap())
+                                  .collect();
+    assert_eq!(vec!["arn:aws:ecs:us-west-2:012345678910:cluster/default",
+                    "arn:aws:ecs:us-west-2:012345678910:cluster/staging",
+                    "arn:aws:ecs:us-west-2:012345678910:cluster/production"],
+               arns);
+}
+
+#[test]
+fn test_tasks_running_then_stopped() {
+    let (client, fake) = fake_client();
+    fake.add_cluster("default");
+    let task = fake.run_task("default", "web:1");
+    let mut running = waiter::tasks_running();
+    running.set_delay(Duration::from_millis(0));
+
+    let request = describe_tasks::DescribeTasksRequest::new(String::from("default"), vec![task.clone()]).unwrap();
+    let response = client.wait_until_tasks_running(request.clone(), &running).unwrap();
+    assert_eq!(Some(String::from("RUNNING")), *response.get_tasks()[0].get_last_status());
+
+    fake.stop_task(&task, "Scaling down");
+    let mut stopped = waiter::tasks_stopped();
+    stopped.set_delay(Duration::from_millis(0));
+    let response = client.wait_until_tasks_stopped(request, &stopped).unwrap();
+    assert_eq!(Some(String::from("STOPPED")), *response.get_tasks()[0].get_last_status());
+}
+
+#[test]
+fn test_services_stable_after_rollout() {
+    let (client, fake) = fake_client();
+    fake.add_cluster("default");
+    fake.add_service("default", "web", "web:1", 2);
+    fake.update_service("web", "web:2");
+    let mut stable = waiter::services_stable();
+    stable.set_delay(Duration::from_millis(0));
+
+    let request = describe_services::DescribeServicesRequest::new(String::from("default"),
+                                                                  vec![String::from("web")]).unwrap();
+    let response = client.wait_until_services_stable(request, &stable).unwrap();
+    assert!(response.get_services()[0].is_stable());
+}
+
+/// Rewrites the X-Amz-Target of every request to an action the fake server does not model.
+#[derive(Debug)]
+struct UnmodelledTarget;
+
+impl Interceptor for UnmodelledTarget {
+    fn before_signing(&self, _context: &RequestContext, headers: &mut Headers) -> Result<(), ECSError> {
+        headers.set(XAmzTarget(String::from("AmazonEC2ContainerServiceV20141113.DescribeClusters")));
+        Ok(())
+    }
+}
+
+/// Asserts that a call failed with a service error of the given type.
+fn assert_service_error<T: std::fmt::Debug>(error_type: &str, result: Result<T, ECSError>) {
+    match result {
+        Err(ECSError::Service(error)) => assert_eq!(error_type, *error.get_error_type()),
+        other => panic!("expected a {}, got {:?}", error_type, other),
+    }
+}
+
+/// Registers a task definition with a single container, returning its family and revision.
+fn register(client: &ECSClient, family: &str) -> String {
+    let request = register_task_definition::RegisterTaskDefinitionRequest::new(
+        String::from(family), vec![ContainerDefinition::new(String::from("app"), String::from("nginx"))]);
+    let response = client.register_task_definition(request).unwrap();
+    let definition = response.get_task_definition();
+    format!("{}:{}", definition.get_family().as_ref().unwrap(), definition.get_revision().unwrap())
+}
+
+#[test]
+fn test_unsupported_action() {
+    let (mut client, fake) = fake_client();
+    fake.add_cluster("default");
+    client.add_interceptor(Box::new(UnmodelledTarget));
+    assert_service_error("UnknownOperationException", client.list_clusters(list_clusters::ListClustersRequest::new()));
+}
+
+#[test]
+fn test_task_definition_revisions_and_run_task() {
+    let (client, fake) = fake_client();
+    fake.add_cluster("default");
+    assert_eq!("web:1", register(&client, "web"));
+    assert_eq!("web:2", register(&client, "web"));
+
+    let mut request = run_task::RunTaskRequest::new(String::from("default"), String::from("web"));
+    request.set_count(2).unwrap();
+    request.set_started_by(String::from("deploy"));
+    let response = client.run_task(request).unwrap();
+    assert_eq!(2, response.get_tasks().len());
+    for task in response.get_tasks() {
+        assert_eq!(Some(String::from("arn:aws:ecs:us-west-2:012345678910:task-definition/web:2")),
+                   *task.get_task_definition_arn());
+        assert_eq!(Some(String::from("deploy")), *task.get_started_by());
+    }
+
+    let request = run_task::RunTaskRequest::new(String::from("default"), String::from("web:1"));
+    let task = client.run_task(request).unwrap().get_tasks()[0].get_task_definition_arn().clone();
+    assert_eq!(Some(String::from("arn:aws:ecs:us-west-2:012345678910:task-definition/web:1")), task);
+
+    let request = run_task::RunTaskRequest::new(String::from("default"), String::from("worker"));
+    assert_service_error("ClientException", client.run_task(request));
+    let request = run_task::RunTaskRequest::new(String::from("staging"), String::from("web"));
+    assert_service_error("ClusterNotFoundException", client.run_task(request));
+}
+
+#[test]
+fn test_created_service_becomes_stable() {
+    let (client, _fake) = fake_client();
+    client.create_cluster(create_cluster::CreateClusterRequest::with_name(String::from("default"))).unwrap();
+    let task_definition = register(&client, "web");
+    let mut request = create_service::CreateServiceRequest::new(String::from("default"),
+                                                                String::from("web"),
+                                                                task_definition.clone());
+    request.set_desired_count(2);
+    client.create_service(request).unwrap();
+
+    let mut stable = waiter::services_stable();
+    stable.set_delay(Duration::from_millis(0));
+    let request = describe_services::DescribeServicesRequest::new(String::from("default"),
+                                                                  vec![String::from("web")]).unwrap();
+    let response = client.wait_until_services_stable(request, &stable).unwrap();
+    assert_eq!(Some(2), *response.get_services()[0].get_running_count());
+
+    let request = create_service::CreateServiceRequest::new(String::from("default"),
+                                                            String::from("web"),
+                                                            task_definition);
+    assert_service_error("InvalidParameterException", client.create_service(request));
+}
+
+#[test]
+fn test_container_instance_attributes_and_agent_update() {
+    let (client, fake) = fake_client();
+    fake.add_cluster("default");
+    let mut request = register_container_instance::RegisterContainerInstanceRequest::new();
+    request.set_cluster(String::from("default"));
+    request.set_version_info(VersionInfo::new(String::from("1.14.0"), String::from("abc"), String::from("1.12.6")));
+    request.add_attribute(Attribute::new(String::from("stack"), Some(String::from("blue"))).unwrap());
+    let response = client.register_container_instance(request).unwrap();
+    let arn = response.get_container_instance().get_container_instance_arn().clone().unwrap();
+
+    let attribute = Attribute::with_target(String::from("zone"),
+                                           Some(String::from("a")),
+                                           TargetType::ContainerInstance,
+                                           arn.clone()).unwrap();
+    let request = put_attributes::PutAttributesRequest::for_cluster(String::from("default"), vec![attribute]).unwrap();
+    client.put_attributes(request).unwrap();
+    let mut request = list_attributes::ListAttributesRequest::new(TargetType::ContainerInstance);
+    request.set_cluster(String::from("default"));
+    request.set_attribute(String::from("zone"), None).unwrap();
+    let response = client.list_attributes(request).unwrap();
+    assert_eq!(1, response.get_attributes().len());
+    assert_eq!(Some(arn.clone()), *response.get_attributes()[0].get_target_id());
+
+    let missing = Attribute::with_target(String::from("zone"),
+                                         Some(String::from("b")),
+                                         TargetType::ContainerInstance,
+                                         String::from("0123")).unwrap();
+    let request = put_attributes::PutAttributesRequest::for_cluster(String::from("default"), vec![missing]).unwrap();
+    assert_service_error("TargetNotFoundException", client.put_attributes(request));
+
+    let request = update_container_agent::UpdateContainerAgentRequest::for_cluster(String::from("default"), arn.clone());
+    let response = client.update_container_agent(request).unwrap();
+    assert_eq!(Some(AgentUpdateStatus::Pending), *response.get_container_instance().get_agent_update_status());
+    let request = describe_container_instances::DescribeContainerInstancesRequest::new(String::from("default"),
+                                                                                      vec![arn]).unwrap();
+    let response = client.describe_container_instances(request).unwrap();
+    let instance = &response.get_container_instances()[0];
+    assert_eq!(Some(AgentUpdateStatus::Updated), *instance.get_agent_update_status());
+    assert_eq!(2, instance.get_attributes().as_ref().unwrap().len());
+}
+
+#[test]
+fn test_tag_and_untag_resource() {
+    let (client, fake) = fake_client();
+    let arn = fake.add_cluster("default");
+    let tags = vec![Tag::new(String::from("team"), String::from("web")).unwrap(),
+                    Tag::new(String::from("env"), String::from("prod")).unwrap()];
+    client.tag_resource(tag_resource::TagResourceRequest::new(arn.clone(), tags).unwrap()).unwrap();
+    let request = untag_resource::UntagResourceRequest::new(arn.clone(), vec![String::from("env")]).unwrap();
+    client.untag_resource(request).unwrap();
+
+    let response = client.list_tags_for_resource(list_tags_for_resource::ListTagsForResourceRequest::new(arn)).unwrap();
+    let keys: Vec<&String> = response.get_tags().iter().map(|tag| tag.get_key()).collect();
+    assert_eq!(vec!["team"], keys);
+
+    let request = list_tags_for_resource::ListTagsForResourceRequest::new(String::from("arn:aws:ecs:nowhere"));
+    assert_service_error("InvalidParameterException", client.list_tags_for_resource(request));
+}
+
+#[test]
+fn test_capacity_provider_in_use_cannot_be_deleted() {
+    let (client, fake) = fake_client();
+    fake.add_cluster("default");
+    let provider = AutoScalingGroupProvider::new(String::from("arn:aws:autoscaling:us-west-2:012345678910:asg"));
+    let request = create_capacity_provider::CreateCapacityProviderRequest::new(String::from("spot"), provider).unwrap();
+    client.create_capacity_provider(request).unwrap();
+    let request = put_cluster_capacity_providers::PutClusterCapacityProvidersRequest::new(
+        String::from("default"), vec![String::from("spot")], Vec::new()).unwrap();
+    let response = client.put_cluster_capacity_providers(request).unwrap();
+    assert_eq!(Some(vec![String::from("spot")]), *response.get_cluster().get_capacity_providers());
+
+    let request = delete_capacity_provider::DeleteCapacityProviderRequest::new(String::from("spot"));
+    assert_service_error("ResourceInUseException", client.delete_capacity_provider(request));
+    let request = put_cluster_capacity_providers::PutClusterCapacityProvidersRequest::new(
+        String::from("default"), Vec::new(), Vec::new()).unwrap();
+    client.put_cluster_capacity_providers(request).unwrap();
+    let request = delete_capacity_provider::DeleteCapacityProviderRequest::new(String::from("spot"));
+    client.delete_capacity_provider(request).unwrap();
+
+    let request = describe_capacity_providers::DescribeCapacityProvidersRequest::with_capacity_providers(
+        vec![String::from("spot"), String::from("missing")]);
+    let response = client.describe_capacity_providers(request).unwrap();
+    assert_eq!(Some(CapacityProviderStatus::Inactive), *response.get_capacity_providers()[0].get_status());
+    assert_eq!(1, response.get_failures().len());
+}
+
+#[test]
+fn test_account_settings() {
+    let (client, _fake) = fake_client();
+    let request = put_account_setting::PutAccountSettingRequest::new(SettingName::ContainerInsights,
+                                                                     String::from("enabled"));
+    let response = client.put_account_setting(request).unwrap();
+    assert_eq!(Some(String::from("arn:aws:iam::012345678910:root")), *response.get_setting().get_principal_arn());
+
+    let response = client.list_account_settings(list_account_settings::ListAccountSettingsRequest::new()).unwrap();
+    assert_eq!(1, response.get_settings().len());
+    assert!(response.get_settings()[0].is_enabled());
+
+    let request = delete_account_setting::DeleteAccountSettingRequest::new(SettingName::ContainerInsights);
+    client.delete_account_setting(request).unwrap();
+    let response = client.list_account_settings(list_account_settings::ListAccountSettingsRequest::new()).unwrap();
+    assert!(response.get_settings().is_empty());
+}
+
+#[test]
+fn test_task_set_scales_to_its_share_of_the_service() {
+    let (client, fake) = fake_client();
+    fake.add_cluster("default");
+    let task_definition = register(&client, "web");
+    fake.add_service("default", "web", &task_definition, 4);
+    let mut request = create_task_set::CreateTaskSetRequest::new(String::from("default"),
+                                                                 String::from("web"),
+                                                                 task_definition);
+    request.set_scale(Scale::percent(50.0).unwrap());
+    let response = client.create_task_set(request).unwrap();
+    assert_eq!(Some(2), *response.get_task_set().get_computed_desired_count());
+    let id = response.get_task_set().get_id().clone().unwrap();
+
+    let request = update_service_primary_task_set::UpdateServicePrimaryTaskSetRequest::new(
+        String::from("default"), String::from("web"), id.clone());
+    client.update_service_primary_task_set(request).unwrap();
+    let request = describe_task_sets::DescribeTaskSetsRequest::new(String::from("default"), String::from("web"));
+    let response = client.describe_task_sets(request).unwrap();
+    let task_set = &response.get_task_sets()[0];
+    assert_eq!(Some(TaskSetStatus::Primary), *task_set.get_status());
+    assert_eq!(Some(2), *task_set.get_running_count());
+    assert_eq!(Some(StabilityStatus::SteadyState), *task_set.get_stability_status());
+
+    let request = delete_task_set::DeleteTaskSetRequest::new(String::from("default"), String::from("web"), id.clone());
+    assert_service_error("InvalidParameterException", client.delete_task_set(request));
+    let mut request = delete_task_set::DeleteTaskSetRequest::new(String::from("default"), String::from("web"), id);
+    request.set_force(true);
+    client.delete_task_set(request).unwrap();
+    let request = describe_task_sets::DescribeTaskSetsRequest::new(String::from("default"), String::from("web"));
+    assert!(client.describe_task_sets(request).unwrap().get_task_sets().is_empty());
+}
+
+#[test]
+fn test_execute_command_needs_a_running_task() {
+    let (client, fake) = fake_client();
+    fake.add_cluster("default");
+    let task = fake.run_task("default", "web:1");
+    let mut request = execute_command::ExecuteCommandRequest::new(task.clone(), String::from("/bin/sh"));
+    request.set_cluster(String::from("default"));
+    // the task is still PENDING until this request has been handled
+    let response = client.execute_command(request).unwrap();
+    assert_eq!(Some(task.clone()), *response.get_task_arn());
+    assert!(response.get_session().get_stream_url().starts_with("wss://"));
+
+    fake.stop_task(&task, "Done");
+    let mut request = execute_command::ExecuteCommandRequest::new(task, String::from("/bin/sh"));
+    request.set_cluster(String::from("default"));
+    assert_service_error("InvalidParameterException", client.execute_command(request));
+}
+
+#[test]
+fn test_invalid_signature_rejected() {
+    let (_client, fake) = fake_client();
+    let body = "{}";
+    let mut response = hyper::Client::new()
+        .post(&fake.endpoint())
+        .header(ContentType("application/x-amz-json-1.1".parse().unwrap()))
+        .header(ecs_client::custom_headers::XAmzTarget(
+            String::from("AmazonEC2ContainerServiceV20141113.ListClusters")))
+        .header(ecs_client::custom_headers::XAmzDate(String::from("20160101T000000Z")))
+        .header(hyper::header::AcceptEncoding(vec![]))
+        .header(Authorization(String::from("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20160101/us-west-2/ecs/\
+                                            aws4_request, SignedHeaders=host, Signature=0000")))
+        .body(body)
+        .send()
+        .unwrap();
+    let mut reply = String::new();
+    response.read_to_string(&mut reply).unwrap();
+    assert_eq!(hyper::status::StatusCode::Forbidden, response.status);
+    assert!(reply.contains("InvalidSignatureException"));
+}
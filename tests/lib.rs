@@ -8,6 +8,6 @@ use ecs_client::region::Region;
 fn test_list_clusters_no_fields() {
     let ecs_client = ECSClient::for_region(Region::USWest2);
     let request = list_clusters::ListClustersRequest::new();
-    let response = ecs_client.list_clusters(request);
+    let response = ecs_client.list_clusters(request).unwrap();
     println!("got response...\n{:?}", response);
 }
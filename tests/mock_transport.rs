@@ -0,0 +1,430 @@
+extern crate ecs_client;
+extern crate serde_json;
+extern crate hyper;
+
+use ecs_client::ecs_client::ECSClient;
+use ecs_client::action::{list_clusters, describe_tasks};
+use ecs_client::action::ecs_action::ECSAction;
+use ecs_client::credentials::{Credentials, CredentialsProvider};
+use ecs_client::custom_headers::{XAmzTarget, XAmzSecurityToken};
+use ecs_client::error::{ECSError, ServiceError};
+use ecs_client::interceptor::Interceptor;
+use ecs_client::logging::RequestContext;
+use ecs_client::metrics::InMemoryMetrics;
+use ecs_client::rate_limit::RateLimiter;
+use ecs_client::region::Region;
+use ecs_client::retry::RetryPolicy;
+use ecs_client::signature;
+use ecs_client::transport::{HttpRequest, HttpResponse, MockTransport};
+use ecs_client::waiter;
+
+use hyper::header::{Authorization, Headers, Host, UserAgent};
+
+use std::env;
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Creates an ECSClient which sends its requests to the returned MockTransport, with dummy
+/// credentials in the environment and no delay between retries.
+fn mock_client() -> (ECSClient, Arc<MockTransport>) {
+    env::set_var("AWS_ACCESS_KEY_ID", "AKIDEXAMPLE");
+    env::set_var("AWS_SECRET_ACCESS_KEY", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY");
+    let transport = Arc::new(MockTransport::new());
+    let mut client = ECSClient::with_transport(Region::USWest2, Box::new(transport.clone()));
+    client.set_retry_policy(RetryPolicy::new(3, Duration::from_millis(0)));
+    (client, transport)
+}
+
+#[test]
+fn test_list_clusters_request_and_response() {
+    let (client, transport) = mock_client();
+    transport.push_response(200, "{\"clusterArns\":[\"arn:aws:ecs:us-west-2:012345678910:cluster/default\"]}");
+
+    let response = client.list_clusters(list_clusters::ListClustersRequest::with_max_results(10)).unwrap();
+    assert_eq!(1, response.get_cluster_arns().len());
+
+    let requests = transport.get_requests();
+    assert_eq!(1, requests.len());
+    assert_eq!("https://ecs.us-west-2.amazonaws.com", *requests[0].get_url());
+    let target: &XAmzTarget = requests[0].get_headers().get().unwrap();
+    assert_eq!("AmazonEC2ContainerServiceV20141113.ListClusters", target.0);
+    let body: serde_json::Value = serde_json::from_str(requests[0].get_body()).unwrap();
+    assert_eq!(serde_json::from_str::<serde_json::Value>("{\"maxResults\":10}").unwrap(), body);
+}
+
+#[test]
+fn test_error_response() {
+    let (client, transport) = mock_client();
+    transport.push_response(400, "{\"__type\":\"ClusterNotFoundException\",\"message\":\"Cluster not found.\"}");
+
+    match client.list_clusters(list_clusters::ListClustersRequest::new()) {
+        Err(ECSError::Service(error)) => {
+            assert_eq!(400, error.get_status_code());
+            assert_eq!("ClusterNotFoundException", *error.get_error_type());
+        },
+        other => panic!("expected a service error, got {:?}", other),
+    }
+    assert_eq!(1, transport.get_requests().len());
+}
+
+#[test]
+fn test_throttled_request_is_retried() {
+    let (client, transport) = mock_client();
+    transport.push_response(400, "{\"__type\":\"ThrottlingException\",\"message\":\"Rate exceeded\"}");
+    transport.push_response(200, "{\"clusterArns\":[]}");
+
+    assert!(client.list_clusters(list_clusters::ListClustersRequest::new()).is_ok());
+    assert_eq!(2, transport.get_requests().len());
+}
+
+#[test]
+fn test_pagination_follows_next_token() {
+    let (client, transport) = mock_client();
+    transport.push_response(200, "{\"clusterArns\":[\"a\",\"b\"],\"nextToken\":\"page2\"}");
+    transport.push_response(200, "{\"clusterArns\":[\"c\"]}");
+
+    let arns: Vec<String> = client.list_clusters_all(list_clusters::ListClustersRequest::new())
+                                  .collect::<Result<_, _>>()
+                                  .unwrap();
+    assert_eq!(vec!["a", "b", "c"], arns);
+    let requests = transport.get_requests();
+    assert_eq!(2, requests.len());
+    assert!(requests[1].get_body().contains("\"nextToken\":\"page2\""));
+}
+
+#[test]
+fn test_wait_until_tasks_stopped() {
+    let (client, transport) = mock_client();
+    transport.push_response(200, "{\"tasks\":[{\"taskArn\":\"t1\",\"lastStatus\":\"RUNNING\"}]}");
+    transport.push_response(200, "{\"tasks\":[{\"taskArn\":\"t1\",\"lastStatus\":\"STOPPED\"}]}");
+
+    let mut stopped = waiter::tasks_stopped();
+    stopped.set_delay(Duration::from_millis(0));
+    let request = describe_tasks::DescribeTasksRequest::new(String::from("default"), vec![String::from("t1")]).unwrap();
+    let response = client.wait_until_tasks_stopped(request, &stopped).unwrap();
+    assert_eq!(1, response.get_tasks().len());
+    assert_eq!(0, transport.remaining_responses());
+}
+
+#[test]
+fn test_custom_endpoint_and_signing_region() {
+    let (mut client, transport) = mock_client();
+    client.set_endpoint("http://localhost:4566").unwrap();
+    client.set_signing_region(Region::EUWest1);
+    transport.push_response(200, "{\"clusterArns\":[]}");
+
+    client.list_clusters(list_clusters::ListClustersRequest::new()).unwrap();
+    let requests = transport.get_requests();
+    assert_eq!("http://localhost:4566", *requests[0].get_url());
+    let host: &Host = requests[0].get_headers().get().unwrap();
+    assert_eq!("localhost", host.hostname);
+    assert_eq!(Some(4566), host.port);
+    let authorization: &Authorization<String> = requests[0].get_headers().get().unwrap();
+    assert!(authorization.0.contains("/eu-west-1/ecs/aws4_request"));
+}
+
+#[test]
+fn test_endpoint_path_is_signed() {
+    let (mut client, transport) = mock_client();
+    client.set_endpoint("http://localhost:4566/ecs/v1").unwrap();
+    transport.push_response(200, "{\"clusterArns\":[]}");
+
+    client.list_clusters(list_clusters::ListClustersRequest::new()).unwrap();
+    let requests = transport.get_requests();
+    assert_eq!("http://localhost:4566/ecs/v1", *requests[0].get_url());
+    let credentials = Credentials::new(String::from("AKIDEXAMPLE"),
+                                       String::from("wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY"));
+    let headers = requests[0].get_headers();
+    let body = requests[0].get_body();
+    let authorization: &Authorization<String> = headers.get().unwrap();
+    let region = Region::USWest2;
+    let expected = signature::build_auth_header_for_path(headers, "/ecs/v1", body, &credentials, &region, "ecs");
+    assert_eq!(expected, authorization.0);
+    let unsigned_path = signature::build_auth_header(headers, body, &credentials, &region, "ecs");
+    assert!(unsigned_path != authorization.0);
+}
+
+#[test]
+fn test_fips_endpoint() {
+    let (mut client, transport) = mock_client();
+    client.set_use_fips(true);
+    transport.push_response(200, "{\"clusterArns\":[]}");
+
+    client.list_clusters(list_clusters::ListClustersRequest::new()).unwrap();
+    let requests = transport.get_requests();
+    assert_eq!("https://ecs-fips.us-west-2.amazonaws.com", *requests[0].get_url());
+    let host: &Host = requests[0].get_headers().get().unwrap();
+    assert_eq!("ecs-fips.us-west-2.amazonaws.com", host.hostname);
+}
+
+#[test]
+fn test_temporary_credentials_are_signed() {
+    let (mut client, transport) = mock_client();
+    let mut credentials = Credentials::new(String::from("ASIAEXAMPLE"), String::from("secret"));
+    credentials.set_session_token(String::from("session-token"));
+    client.set_credentials_provider(Box::new(credentials));
+    transport.push_response(200, "{\"clusterArns\":[]}");
+
+    client.list_clusters(list_clusters::ListClustersRequest::new()).unwrap();
+    let requests = transport.get_requests();
+    let token: &XAmzSecurityToken = requests[0].get_headers().get().unwrap();
+    assert_eq!("session-token", token.0);
+    let authorization: &Authorization<String> = requests[0].get_headers().get().unwrap();
+    assert!(authorization.0.starts_with("AWS4-HMAC-SHA256 Credential=ASIAEXAMPLE/"));
+    assert!(authorization.0.contains("x-amz-date;x-amz-security-token;x-amz-target"));
+    let debug = format!("{:?}", requests[0]);
+    assert!(!debug.contains("session-token"));
+    assert!(!debug.contains("Signature="));
+}
+
+#[test]
+fn test_builder_sets_user_agent() {
+    env::set_var("AWS_ACCESS_KEY_ID", "AKIDEXAMPLE");
+    env::set_var("AWS_SECRET_ACCESS_KEY", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY");
+    let transport = Arc::new(MockTransport::new());
+    let client = ECSClient::builder().region(Region::USWest2)
+                                     .transport(Box::new(transport.clone()))
+                                     .app_id("deploy-bot")
+                                     .build()
+                                     .unwrap();
+    transport.push_response(200, "{\"clusterArns\":[]}");
+
+    client.list_clusters(list_clusters::ListClustersRequest::new()).unwrap();
+    let requests = transport.get_requests();
+    let user_agent: &UserAgent = requests[0].get_headers().get().unwrap();
+    assert!(user_agent.0.starts_with("ecs_client/"));
+    assert!(user_agent.0.ends_with(" app/deploy-bot"));
+    assert!(ECSClient::builder().region(Region::USWest2).app_id("deploy bot").build().is_err());
+}
+
+#[test]
+fn test_operation_timeout_stops_retries() {
+    let (mut client, transport) = mock_client();
+    client.set_retry_policy(RetryPolicy::new(5, Duration::from_secs(10)));
+    client.set_operation_timeout(Duration::from_millis(1));
+    for _ in 0..5 {
+        transport.push_response(500, "{\"__type\":\"ServerException\",\"message\":\"Internal error\"}");
+    }
+
+    let started = Instant::now();
+    assert!(client.list_clusters(list_clusters::ListClustersRequest::new()).is_err());
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn test_operation_timeout_bounds_rate_limit_wait() {
+    let (mut client, transport) = mock_client();
+    let mut rate_limiter = RateLimiter::new();
+    rate_limiter.set_limit(ECSAction::ListClusters, 1, 0.5).unwrap();
+    client.set_rate_limiter(Arc::new(rate_limiter));
+    client.set_operation_timeout(Duration::from_millis(200));
+    transport.push_response(200, "{\"clusterArns\":[]}");
+    transport.push_response(200, "{\"clusterArns\":[]}");
+
+    let started = Instant::now();
+    client.list_clusters(list_clusters::ListClustersRequest::new()).unwrap();
+    let error = client.send_with_metadata::<_, list_clusters::ListClustersResponse>(
+        ECSAction::ListClusters, list_clusters::ListClustersRequest::new()).unwrap_err();
+    assert!(started.elapsed() < Duration::from_millis(200));
+    match *error.get_error() {
+        ECSError::Io(ref error) => assert_eq!(io::ErrorKind::TimedOut, error.kind()),
+        ref other => panic!("expected a timeout, got {:?}", other),
+    }
+    assert_eq!(0, error.get_metadata().get_attempts());
+    assert_eq!(1, transport.get_requests().len());
+}
+
+/// Creates a response whose x-amzn-RequestId header holds the given request ID.
+fn response_with_request_id(status_code: u16, request_id: &str, body: &str) -> HttpResponse {
+    let mut headers = Headers::new();
+    headers.set_raw("x-amzn-RequestId", vec![request_id.as_bytes().to_vec()]);
+    HttpResponse::new(status_code, headers, String::from(body))
+}
+
+#[test]
+fn test_response_metadata() {
+    let (client, transport) = mock_client();
+    transport.push_http_response(response_with_request_id(
+        400, "request-1", "{\"__type\":\"ThrottlingException\",\"message\":\"Rate exceeded\"}"));
+    transport.push_http_response(response_with_request_id(200, "request-2", "{\"clusterArns\":[\"a\"]}"));
+
+    let response = client.send_with_metadata::<_, list_clusters::ListClustersResponse>(
+        ECSAction::ListClusters, list_clusters::ListClustersRequest::new()).unwrap();
+    assert_eq!(1, response.get_output().get_cluster_arns().len());
+    let metadata = response.get_metadata();
+    assert_eq!(Some(&String::from("request-2")), metadata.get_request_id());
+    assert_eq!(Some(200), metadata.get_status_code());
+    assert_eq!(2, metadata.get_attempts());
+    assert_eq!(1, metadata.get_retries());
+}
+
+#[test]
+fn test_error_metadata() {
+    let (client, transport) = mock_client();
+    transport.push_http_response(response_with_request_id(
+        400, "request-3", "{\"__type\":\"ClusterNotFoundException\",\"message\":\"Cluster not found.\"}"));
+
+    let error = client.send_with_metadata::<_, list_clusters::ListClustersResponse>(
+        ECSAction::ListClusters, list_clusters::ListClustersRequest::new()).unwrap_err();
+    assert_eq!(Some(&String::from("request-3")), error.get_metadata().get_request_id());
+    assert_eq!(1, error.get_metadata().get_attempts());
+
+    transport.push_http_response(response_with_request_id(
+        400, "request-4", "{\"__type\":\"ClusterNotFoundException\",\"message\":\"Cluster not found.\"}"));
+    match client.list_clusters(list_clusters::ListClustersRequest::new()) {
+        Err(ECSError::Service(error)) => {
+            assert_eq!(Some(&String::from("request-4")), error.get_request_id());
+            assert!(error.to_string().contains("request id request-4"));
+        },
+        other => panic!("expected a service error, got {:?}", other),
+    }
+}
+
+/// A CredentialsProvider which never has any credentials.
+#[derive(Debug)]
+struct NoCredentials;
+
+impl CredentialsProvider for NoCredentials {
+    fn get_credentials(&self) -> Result<Credentials, ECSError> {
+        Err(ECSError::Credentials(String::from("no credentials configured")))
+    }
+}
+
+#[test]
+fn test_per_action_metadata() {
+    let (mut client, transport) = mock_client();
+    transport.push_http_response(response_with_request_id(200, "request-5", "{\"clusterArns\":[\"a\"]}"));
+    let response = client.list_clusters_with_metadata(list_clusters::ListClustersRequest::new()).unwrap();
+    assert_eq!(1, response.get_output().get_cluster_arns().len());
+    assert_eq!(Some(&String::from("request-5")), response.get_metadata().get_request_id());
+
+    // a dropped connection is retried, and the response of the last attempt fails to parse
+    transport.push_error(ECSError::Io(io::Error::new(io::ErrorKind::ConnectionReset, "connection reset")));
+    transport.push_http_response(response_with_request_id(200, "request-6", "not json"));
+    let error = client.list_clusters_with_metadata(list_clusters::ListClustersRequest::new()).unwrap_err();
+    match *error.get_error() {
+        ECSError::Json(_) => (),
+        ref other => panic!("expected a json error, got {:?}", other),
+    }
+    assert_eq!(Some(&String::from("request-6")), error.get_metadata().get_request_id());
+    assert_eq!(2, error.get_metadata().get_attempts());
+
+    // the last attempt received no response, so there is no request ID to report
+    transport.push_http_response(response_with_request_id(
+        500, "request-7", "{\"__type\":\"ServerException\",\"message\":\"Internal error\"}"));
+    for _ in 0..2 {
+        transport.push_error(ECSError::Io(io::Error::new(io::ErrorKind::ConnectionReset, "connection reset")));
+    }
+    let request = describe_tasks::DescribeTasksRequest::new(String::from("default"),
+                                                            vec![String::from("task")]).unwrap();
+    let error = client.describe_tasks_with_metadata(request).unwrap_err();
+    match *error.get_error() {
+        ECSError::Io(ref error) => assert_eq!(io::ErrorKind::ConnectionReset, error.kind()),
+        ref other => panic!("expected an io error, got {:?}", other),
+    }
+    assert_eq!(None, error.get_metadata().get_request_id());
+    assert_eq!(3, error.get_metadata().get_attempts());
+
+    client.set_credentials_provider(Box::new(NoCredentials));
+    let error = client.list_clusters_with_metadata(list_clusters::ListClustersRequest::new()).unwrap_err();
+    match *error.get_error() {
+        ECSError::Credentials(_) => (),
+        ref other => panic!("expected a credentials error, got {:?}", other),
+    }
+    assert_eq!(1, error.get_metadata().get_attempts());
+    assert_eq!(6, transport.get_requests().len());
+}
+
+/// An Interceptor which records the hooks called, tags every request with a header, sets the
+/// maxResults of its body and fails the first attempt to send as if it were throttled.
+#[derive(Debug, Default)]
+struct RecordingInterceptor {
+    calls: Mutex<Vec<String>>,
+}
+
+impl RecordingInterceptor {
+    fn record(&self, context: &RequestContext, hook: &str) {
+        self.calls.lock().unwrap().push(format!("{}:{}", hook, context.get_attempt()));
+    }
+}
+
+impl Interceptor for RecordingInterceptor {
+    fn before_serialization(&self, context: &RequestContext, input: &mut serde_json::Value) -> Result<(), ECSError> {
+        self.record(context, "before_serialization");
+        if let serde_json::Value::Object(ref mut fields) = *input {
+            fields.insert(String::from("maxResults"), serde_json::Value::U64(5));
+        }
+        Ok(())
+    }
+
+    fn before_signing(&self, context: &RequestContext, headers: &mut Headers) -> Result<(), ECSError> {
+        self.record(context, "before_signing");
+        headers.set_raw("X-Audit-Id", vec![b"audit-1".to_vec()]);
+        Ok(())
+    }
+
+    fn after_signing(&self, context: &RequestContext, _request: &HttpRequest) -> Result<(), ECSError> {
+        self.record(context, "after_signing");
+        Ok(())
+    }
+
+    fn before_transmit(&self, context: &RequestContext, _request: &HttpRequest) -> Result<(), ECSError> {
+        self.record(context, "before_transmit");
+        if context.get_attempt() == 1 {
+            return Err(ECSError::Service(ServiceError::from_response(
+                400, "{\"__type\":\"ThrottlingException\",\"message\":\"injected\"}")));
+        }
+        Ok(())
+    }
+
+    fn after_response(&self, context: &RequestContext, _response: &HttpResponse) -> Result<(), ECSError> {
+        self.record(context, "after_response");
+        Ok(())
+    }
+
+    fn on_error(&self, context: &RequestContext, _error: &ECSError) {
+        self.record(context, "on_error");
+    }
+}
+
+#[test]
+fn test_interceptor_hooks() {
+    let (mut client, transport) = mock_client();
+    let interceptor = Arc::new(RecordingInterceptor::default());
+    client.add_interceptor(Box::new(interceptor.clone()));
+    transport.push_response(200, "{\"clusterArns\":[]}");
+
+    client.list_clusters(list_clusters::ListClustersRequest::new()).unwrap();
+    assert_eq!(vec!["before_serialization:1", "before_signing:1", "after_signing:1", "before_transmit:1",
+                    "on_error:1", "before_signing:2", "after_signing:2", "before_transmit:2",
+                    "after_response:2"],
+               *interceptor.calls.lock().unwrap());
+
+    let requests = transport.get_requests();
+    assert_eq!(1, requests.len());
+    assert_eq!(&[b"audit-1".to_vec()][..], requests[0].get_headers().get_raw("X-Audit-Id").unwrap());
+    let body: serde_json::Value = serde_json::from_str(requests[0].get_body()).unwrap();
+    assert_eq!(Some(5), body.find("maxResults").and_then(|max_results| max_results.as_u64()));
+}
+
+#[test]
+fn test_metrics_sink() {
+    let (mut client, transport) = mock_client();
+    let metrics = Arc::new(InMemoryMetrics::new());
+    client.set_metrics_sink(metrics.clone());
+    transport.push_response(400, "{\"__type\":\"ThrottlingException\",\"message\":\"Rate exceeded\"}");
+    transport.push_response(200, "{\"clusterArns\":[]}");
+    transport.push_response(400, "{\"__type\":\"ClientException\",\"message\":\"bad\"}");
+
+    assert!(client.list_clusters(list_clusters::ListClustersRequest::new()).is_ok());
+    assert!(client.list_clusters(list_clusters::ListClustersRequest::new()).is_err());
+
+    let list_clusters = metrics.get(ECSAction::ListClusters).unwrap();
+    assert_eq!(2, list_clusters.get_requests());
+    assert_eq!(1, list_clusters.get_retries());
+    assert_eq!(1, list_clusters.get_throttles());
+    assert_eq!(Some(&1), list_clusters.get_errors().get("ClientException"));
+    assert_eq!(2, list_clusters.get_latency().get_count());
+    assert!(metrics.render_prometheus().contains("ecs_client_throttles_total{action=\"ListClusters\"} 1\n"));
+}
@@ -0,0 +1,233 @@
+extern crate ecs_client;
+extern crate futures;
+extern crate hyper;
+extern crate serde_json;
+
+use ecs_client::ecs_client::ECSClient;
+use ecs_client::action::list_clusters::{ListClustersRequest, ListClustersResponse};
+use ecs_client::action::describe_tasks::DescribeTasksRequest;
+use ecs_client::action::ecs_action::ECSAction;
+use ecs_client::error::ECSError;
+use ecs_client::pooled_client::PooledECSClient;
+use ecs_client::rate_limit::RateLimiter;
+use ecs_client::region::Region;
+use ecs_client::retry::RetryPolicy;
+use ecs_client::transport::{HttpTransport, HttpRequest, HttpResponse, MockTransport};
+
+use futures::{Future, Stream};
+use futures::future;
+use hyper::header::Headers;
+
+use std::env;
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Creates an ECSClient which sends its requests to the given transport, with dummy credentials
+/// in the environment and no delay between retries.
+fn client_with_transport(transport: Box<HttpTransport>) -> ECSClient {
+    env::set_var("AWS_ACCESS_KEY_ID", "AKIDEXAMPLE");
+    env::set_var("AWS_SECRET_ACCESS_KEY", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY");
+    let mut client = ECSClient::with_transport(Region::USWest2, transport);
+    client.set_retry_policy(RetryPolicy::new(3, Duration::from_millis(0)));
+    client
+}
+
+/// Creates an ECSClient which sends its requests to the returned MockTransport.
+fn mock_client() -> (ECSClient, Arc<MockTransport>) {
+    let transport = Arc::new(MockTransport::new());
+    (client_with_transport(Box::new(transport.clone())), transport)
+}
+
+/// A transport which holds each request for a while before answering it with an empty page of
+/// clusters, recording the most requests it has had in flight at once.
+#[derive(Debug)]
+struct SlowTransport {
+    /// The number of requests in flight, and the most there have been at once.
+    in_flight: Mutex<(usize, usize)>,
+}
+
+impl HttpTransport for SlowTransport {
+    fn send(&self, _: &HttpRequest) -> Result<HttpResponse, ECSError> {
+        {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            in_flight.0 += 1;
+            in_flight.1 = in_flight.1.max(in_flight.0);
+        }
+        thread::sleep(Duration::from_millis(50));
+        self.in_flight.lock().unwrap().0 -= 1;
+        Ok(HttpResponse::new(200, Headers::new(), String::from("{\"clusterArns\":[]}")))
+    }
+}
+
+#[test]
+fn test_requests_on_the_wire_are_capped_by_the_pool() {
+    let transport = Arc::new(SlowTransport { in_flight: Mutex::new((0, 0)) });
+    let client = PooledECSClient::new(client_with_transport(Box::new(transport.clone())), 2);
+
+    let start = Instant::now();
+    let calls: Vec<_> = (0..6).map(|_| client.list_clusters(ListClustersRequest::new())).collect();
+    assert_eq!(6, future::join_all(calls).wait().unwrap().len());
+    assert!(start.elapsed() >= Duration::from_millis(150));
+    assert_eq!(2, transport.in_flight.lock().unwrap().1);
+}
+
+#[test]
+fn test_concurrent_requests_through_mock_transport() {
+    let (client, transport) = mock_client();
+    for _ in 0..8 {
+        transport.push_response(200, "{\"clusterArns\":[\"a\"]}");
+    }
+    let client = PooledECSClient::new(client, 4);
+
+    let calls: Vec<_> = (0..8).map(|_| client.list_clusters(ListClustersRequest::new())).collect();
+    let responses = future::join_all(calls).wait().unwrap();
+    assert!(responses.iter().all(|response| response.get_cluster_arns().len() == 1));
+    assert_eq!(8, transport.get_requests().len());
+    assert_eq!(0, transport.remaining_responses());
+}
+
+#[test]
+fn test_rate_limit_wait_does_not_hold_a_worker() {
+    let (mut client, transport) = mock_client();
+    let mut rate_limiter = RateLimiter::new();
+    rate_limiter.set_limit(ECSAction::ListClusters, 1, 1.0).unwrap();
+    client.set_rate_limiter(Arc::new(rate_limiter));
+    for _ in 0..3 {
+        transport.push_response(200, "{\"clusterArns\":[],\"tasks\":[],\"failures\":[]}");
+    }
+    let client = PooledECSClient::new(client, 1);
+
+    let start = Instant::now();
+    let listed = client.list_clusters(ListClustersRequest::new())
+                       .join(client.list_clusters(ListClustersRequest::new()));
+    let listing = thread::spawn(move || listed.wait());
+    // the second listing now waits about a second for a token, while the only worker is free
+    thread::sleep(Duration::from_millis(50));
+    let request = DescribeTasksRequest::new(String::from("default"), vec![String::from("task")]).unwrap();
+    client.describe_tasks(request).wait().unwrap();
+    assert!(start.elapsed() < Duration::from_millis(500));
+
+    listing.join().unwrap().unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(900));
+    assert_eq!(3, transport.get_requests().len());
+}
+
+#[test]
+fn test_throttled_request_is_retried_with_metadata() {
+    let (client, transport) = mock_client();
+    transport.push_response(400, "{\"__type\":\"ThrottlingException\",\"message\":\"Rate exceeded\"}");
+    transport.push_response(200, "{\"clusterArns\":[\"a\"]}");
+    let client = PooledECSClient::new(client, 2);
+
+    let response = client.send_with_metadata::<_, ListClustersResponse>(
+        ECSAction::ListClusters, ListClustersRequest::new()).wait().unwrap();
+    assert_eq!(1, response.get_output().get_cluster_arns().len());
+    assert_eq!(2, response.get_metadata().get_attempts());
+
+    transport.push_response(400, "{\"__type\":\"ClusterNotFoundException\",\"message\":\"Cluster not found.\"}");
+    let error = client.send_with_metadata::<_, ListClustersResponse>(
+        ECSAction::ListClusters, ListClustersRequest::new()).wait().unwrap_err();
+    assert_eq!(1, error.get_metadata().get_attempts());
+    assert_eq!(3, transport.get_requests().len());
+}
+
+#[test]
+fn test_per_action_metadata() {
+    let (client, transport) = mock_client();
+    let mut headers = Headers::new();
+    headers.set_raw("x-amzn-RequestId", vec![b"request-1".to_vec()]);
+    transport.push_http_response(HttpResponse::new(200, headers, String::from("{\"clusterArns\":[\"a\"]}")));
+    transport.push_error(ECSError::Io(io::Error::new(io::ErrorKind::Other, "connection refused")));
+    let client = PooledECSClient::new(client, 2);
+
+    let response = client.list_clusters_with_metadata(ListClustersRequest::new()).wait().unwrap();
+    assert_eq!(Some(&String::from("request-1")), response.get_metadata().get_request_id());
+    assert_eq!(1, response.get_metadata().get_attempts());
+
+    let request = DescribeTasksRequest::new(String::from("default"), vec![String::from("task")]).unwrap();
+    let error = client.describe_tasks_with_metadata(request).wait().unwrap_err();
+    assert_eq!("Io", error.get_error().get_error_type());
+    assert_eq!(None, error.get_metadata().get_request_id());
+    assert_eq!(1, error.get_metadata().get_attempts());
+}
+
+#[test]
+fn test_page_stream_follows_next_token() {
+    let (client, transport) = mock_client();
+    transport.push_response(200, "{\"clusterArns\":[\"a\",\"b\"],\"nextToken\":\"page2\"}");
+    transport.push_response(200, "{\"clusterArns\":[\"c\"]}");
+    let client = PooledECSClient::new(client, 2);
+
+    let pages = client.pages(ListClustersRequest::new()).collect().wait().unwrap();
+    assert_eq!(2, pages.len());
+    assert_eq!(&vec![String::from("c")], pages[1].get_cluster_arns());
+
+    let requests = transport.get_requests();
+    assert_eq!(2, requests.len());
+    let body: serde_json::Value = serde_json::from_str(requests[1].get_body()).unwrap();
+    assert_eq!(serde_json::from_str::<serde_json::Value>("{\"nextToken\":\"page2\"}").unwrap(), body);
+}
+
+#[test]
+fn test_page_stream_stops_on_error() {
+    let (client, transport) = mock_client();
+    transport.push_response(200, "{\"clusterArns\":[\"a\"],\"nextToken\":\"page2\"}");
+    transport.push_response(400, "{\"__type\":\"ClusterNotFoundException\",\"message\":\"Cluster not found.\"}");
+    transport.push_response(200, "{\"clusterArns\":[\"b\"]}");
+    let client = PooledECSClient::new(client, 2);
+
+    let mut pages = client.pages(ListClustersRequest::new()).wait();
+    assert!(pages.next().unwrap().is_ok());
+    match pages.next() {
+        Some(Err(ECSError::Service(error))) => assert_eq!("ClusterNotFoundException", *error.get_error_type()),
+        other => panic!("expected a service error, got {:?}", other),
+    }
+    assert!(pages.next().is_none());
+    assert_eq!(2, transport.get_requests().len());
+    assert_eq!(1, transport.remaining_responses());
+}
+
+#[test]
+fn test_items_capped_stops_requesting_pages() {
+    let (client, transport) = mock_client();
+    transport.push_response(200, "{\"clusterArns\":[\"a\",\"b\",\"c\"],\"nextToken\":\"page2\"}");
+    transport.push_response(200, "{\"clusterArns\":[\"d\"]}");
+    let client = PooledECSClient::new(client, 2);
+
+    let items = client.pages(ListClustersRequest::new()).items_capped(2).collect().wait().unwrap();
+    assert_eq!(vec![String::from("a"), String::from("b")], items);
+    assert_eq!(1, transport.get_requests().len());
+
+    let items = client.pages(ListClustersRequest::with_next_token(String::from("page2")))
+                      .items()
+                      .collect()
+                      .wait()
+                      .unwrap();
+    assert_eq!(vec![String::from("d")], items);
+}
+
+#[test]
+fn test_rate_limit_wait_is_bounded_by_the_operation_timeout() {
+    let (mut client, transport) = mock_client();
+    let mut rate_limiter = RateLimiter::new();
+    rate_limiter.set_limit(ECSAction::ListClusters, 1, 0.5).unwrap();
+    client.set_rate_limiter(Arc::new(rate_limiter));
+    client.set_operation_timeout(Duration::from_millis(200));
+    transport.push_response(200, "{\"clusterArns\":[]}");
+    transport.push_response(200, "{\"clusterArns\":[]}");
+    let client = PooledECSClient::new(client, 1);
+
+    let start = Instant::now();
+    client.list_clusters(ListClustersRequest::new()).wait().unwrap();
+    let error = client.send_with_metadata::<_, ListClustersResponse>(
+        ECSAction::ListClusters, ListClustersRequest::new()).wait().unwrap_err();
+    assert!(start.elapsed() < Duration::from_millis(200));
+    match *error.get_error() {
+        ECSError::Io(ref error) => assert_eq!(io::ErrorKind::TimedOut, error.kind()),
+        ref other => panic!("expected a timeout, got {:?}", other),
+    }
+    assert_eq!(0, error.get_metadata().get_attempts());
+    assert_eq!(1, transport.get_requests().len());
+}